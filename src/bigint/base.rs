@@ -0,0 +1,290 @@
+//! basic implementation of BigInt
+
+use std::cmp::Ordering;
+
+use crate::bigint::BigInt;
+
+/// remove the leading zero limbs
+fn trim(mut v: Vec<u32>) -> Vec<u32> {
+    while v.last() == Some(&0) {
+        v.pop();
+    }
+    v
+}
+
+pub(crate) fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut out = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0u64;
+    for (i, &x) in a.iter().enumerate() {
+        let s = x as u64 + b.get(i).copied().unwrap_or(0) as u64 + carry;
+        out.push(s as u32);
+        carry = s >> 32;
+    }
+    if carry != 0 {
+        out.push(carry as u32);
+    }
+    out
+}
+
+/// `a - b` for `a >= b`
+fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut out = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &x) in a.iter().enumerate() {
+        let mut d = x as i64 - b.get(i).copied().unwrap_or(0) as i64 - borrow;
+        borrow = if d < 0 {
+            d += 1 << 32;
+            1
+        } else {
+            0
+        };
+        out.push(d as u32);
+    }
+    trim(out)
+}
+
+fn mul_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut out = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u64 * y as u64 + out[i + j] as u64 + carry;
+            out[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        out[i + b.len()] = carry as u32;
+    }
+    trim(out)
+}
+
+/// divide by a single limb, returns the quotient and the remainder
+pub(crate) fn divrem_small(a: &[u32], d: u32) -> (Vec<u32>, u32) {
+    let mut q = vec![0u32; a.len()];
+    let mut r = 0u64;
+    for i in (0..a.len()).rev() {
+        let cur = (r << 32) | a[i] as u64;
+        q[i] = (cur / d as u64) as u32;
+        r = cur % d as u64;
+    }
+    (trim(q), r as u32)
+}
+
+/// shift left by `s < 32` bits, the result has one more limb
+fn shl_bits(a: &[u32], s: u32) -> Vec<u32> {
+    let mut out = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0u32;
+    for &x in a {
+        out.push((x << s) | carry);
+        carry = if s == 0 { 0 } else { x >> (32 - s) };
+    }
+    out.push(carry);
+    out
+}
+
+fn shr_bits(a: &[u32], s: u32) -> Vec<u32> {
+    let mut out = vec![0u32; a.len()];
+    for i in 0..a.len() {
+        let hi = if s == 0 {
+            0
+        } else {
+            a.get(i + 1).map_or(0, |&x| x << (32 - s))
+        };
+        out[i] = (a[i] >> s) | hi;
+    }
+    trim(out)
+}
+
+/// long division of magnitudes by Knuth's algorithm D, `b` must not be zero
+fn divrem_mag(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_mag(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        let (q, r) = divrem_small(a, b[0]);
+        return (q, trim(vec![r]));
+    }
+    // normalize so that the top bit of the divisor is set
+    let s = b[b.len() - 1].leading_zeros();
+    let v = shl_bits(b, s);
+    let mut u = shl_bits(a, s);
+    let n = b.len();
+    let m = a.len() - n;
+    let mut q = vec![0u32; m + 1];
+    let base = 1u64 << 32;
+    for j in (0..=m).rev() {
+        let num = ((u[j + n] as u64) << 32) | u[j + n - 1] as u64;
+        let mut qhat = num / v[n - 1] as u64;
+        let mut rhat = num % v[n - 1] as u64;
+        while qhat >= base || qhat * v[n - 2] as u64 > ((rhat << 32) | u[j + n - 2] as u64) {
+            qhat -= 1;
+            rhat += v[n - 1] as u64;
+            if rhat >= base {
+                break;
+            }
+        }
+        // multiply and subtract
+        let mut k = 0i64;
+        for i in 0..n {
+            let p = qhat * v[i] as u64;
+            let t = u[i + j] as i64 - k - (p & 0xffff_ffff) as i64;
+            u[i + j] = t as u32;
+            k = (p >> 32) as i64 - (t >> 32);
+        }
+        let t = u[j + n] as i64 - k;
+        u[j + n] = t as u32;
+        q[j] = qhat as u32;
+        if t < 0 {
+            // subtracted too much, add back
+            q[j] = q[j].wrapping_sub(1);
+            let mut carry = 0u64;
+            for i in 0..n {
+                let t = u[i + j] as u64 + v[i] as u64 + carry;
+                u[i + j] = t as u32;
+                carry = t >> 32;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u32);
+        }
+    }
+    (trim(q), shr_bits(&u[..n], s))
+}
+
+impl BigInt {
+    pub(crate) fn from_parts(negative: bool, mag: Vec<u32>) -> Self {
+        let mag = trim(mag);
+        BigInt {
+            negative: negative && !mag.is_empty(),
+            mag,
+        }
+    }
+
+    pub(crate) fn mag(&self) -> &[u32] {
+        &self.mag
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// `-1`, `0` or `1`
+    pub fn signum(&self) -> i32 {
+        match (self.mag.is_empty(), self.negative) {
+            (true, _) => 0,
+            (false, true) => -1,
+            (false, false) => 1,
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        BigInt::from_parts(false, self.mag.clone())
+    }
+
+    pub(crate) fn add_ref(&self, rhs: &Self) -> Self {
+        if self.negative == rhs.negative {
+            BigInt::from_parts(self.negative, add_mag(&self.mag, &rhs.mag))
+        } else {
+            match cmp_mag(&self.mag, &rhs.mag) {
+                Ordering::Less => BigInt::from_parts(rhs.negative, sub_mag(&rhs.mag, &self.mag)),
+                _ => BigInt::from_parts(self.negative, sub_mag(&self.mag, &rhs.mag)),
+            }
+        }
+    }
+
+    pub(crate) fn sub_ref(&self, rhs: &Self) -> Self {
+        self.add_ref(&BigInt::from_parts(!rhs.negative, rhs.mag.clone()))
+    }
+
+    pub(crate) fn mul_ref(&self, rhs: &Self) -> Self {
+        BigInt::from_parts(self.negative != rhs.negative, mul_mag(&self.mag, &rhs.mag))
+    }
+
+    /// quotient truncated toward zero and the remainder with the sign of `self`
+    ///
+    /// # Panics
+    ///
+    /// panics if `rhs` is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::bigint::BigInt;
+    /// let (q, r) = BigInt::from(-7).div_rem(&BigInt::from(2));
+    /// assert_eq!((q, r), (BigInt::from(-3), BigInt::from(-1)));
+    /// ```
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        if rhs.mag.is_empty() {
+            panic!("big integer division by zero");
+        }
+        let (q, r) = divrem_mag(&self.mag, &rhs.mag);
+        (
+            BigInt::from_parts(self.negative != rhs.negative, q),
+            BigInt::from_parts(self.negative, r),
+        )
+    }
+
+    /// greatest common divisor, always non-negative
+    pub fn gcd(&self, rhs: &Self) -> Self {
+        let (mut a, mut b) = (self.abs(), rhs.abs());
+        while !b.mag.is_empty() {
+            let r = a.div_rem(&b).1;
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    pub fn pow(&self, mut exp: u32) -> Self {
+        let mut base = self.clone();
+        let mut acc = BigInt::from(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = acc.mul_ref(&base);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.mul_ref(&base);
+            }
+        }
+        acc
+    }
+
+    /// nearest floating point number, infinite if out of range
+    pub fn to_f64(&self) -> f64 {
+        let v = self
+            .mag
+            .iter()
+            .rev()
+            .fold(0.0f64, |acc, &x| acc * 4294967296.0 + x as f64);
+        if self.negative {
+            -v
+        } else {
+            v
+        }
+    }
+
+    /// the value if it fits into `i64`
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.mag.len() > 2 {
+            return None;
+        }
+        let m = self
+            .mag
+            .iter()
+            .rev()
+            .fold(0u64, |acc, &x| (acc << 32) | x as u64);
+        if self.negative {
+            0i64.checked_sub_unsigned(m)
+        } else {
+            i64::try_from(m).ok()
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! type of arbitrary precision integer
+
+pub mod base;
+pub mod num;
+pub mod utils;
+
+/// an integer of unbounded size
+///
+/// the magnitude is stored in base `2^32` with the least significant limb first,
+/// zero has no limbs and is never negative
+///
+/// `/` truncates toward zero like the primitive integers, so it is only a
+/// [`Ring`](crate::number::Ring), algorithms which need exact division like
+/// [`Matrix::inverse`](crate::matrix::Matrix::inverse) should use
+/// [`BigRational`](crate::rational::big::BigRational) instead,
+/// determinants of integer matrices are computed by
+/// [`Matrix::det_bareiss`](crate::matrix::Matrix::det_bareiss)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BigInt {
+    negative: bool,
+    mag: Vec<u32>,
+}
@@ -0,0 +1,53 @@
+//! implementation of Number for BigInt
+
+use crate::bigint::BigInt;
+
+impl std::ops::Add for BigInt {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add_ref(&rhs)
+    }
+}
+
+impl std::ops::Sub for BigInt {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.sub_ref(&rhs)
+    }
+}
+
+impl std::ops::Mul for BigInt {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.mul_ref(&rhs)
+    }
+}
+
+/// truncating division
+impl std::ops::Div for BigInt {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_rem(&rhs).0
+    }
+}
+
+impl std::ops::Rem for BigInt {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.div_rem(&rhs).1
+    }
+}
+
+impl std::ops::Neg for BigInt {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let negative = !self.is_negative();
+        BigInt::from_parts(negative, self.mag().to_vec())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{
+    bigint::{
+        base::{cmp_mag, divrem_small},
+        BigInt,
+    },
+    error::RMatrixError,
+    number::pad_exact,
+};
+
+/// largest power of ten in a limb
+const DECIMAL_BASE: u32 = 1_000_000_000;
+const DECIMAL_DIGITS: usize = 9;
+
+impl From<u64> for BigInt {
+    fn from(n: u64) -> Self {
+        BigInt::from_parts(false, vec![n as u32, (n >> 32) as u32])
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(n: i64) -> Self {
+        let m = n.unsigned_abs();
+        BigInt::from_parts(n < 0, vec![m as u32, (m >> 32) as u32])
+    }
+}
+
+impl From<i32> for BigInt {
+    fn from(n: i32) -> Self {
+        BigInt::from(n as i64)
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (false, true) => std::cmp::Ordering::Greater,
+            (true, false) => std::cmp::Ordering::Less,
+            (false, false) => cmp_mag(self.mag(), other.mag()),
+            (true, true) => cmp_mag(other.mag(), self.mag()),
+        }
+    }
+}
+
+impl std::fmt::Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut chunks = Vec::new();
+        let mut mag = self.mag().to_vec();
+        while !mag.is_empty() {
+            let (q, r) = divrem_small(&mag, DECIMAL_BASE);
+            chunks.push(r);
+            mag = q;
+        }
+        let mut s = String::from(if self.is_negative() { "-" } else { "" });
+        match chunks.split_last() {
+            None => s.push('0'),
+            Some((first, rest)) => {
+                s.push_str(&first.to_string());
+                for c in rest.iter().rev() {
+                    s.push_str(&format!("{:0w$}", c, w = DECIMAL_DIGITS));
+                }
+            }
+        }
+        pad_exact(f, self.is_negative(), &s)
+    }
+}
+
+/// parse decimal digits with an optional sign
+///
+/// # Examples
+///
+/// ```
+/// # use rmatrix_ks::bigint::BigInt;
+/// let n: BigInt = "-123456789012345678901234567890".parse().unwrap();
+/// assert_eq!(n.to_string(), "-123456789012345678901234567890");
+/// assert!("12a".parse::<BigInt>().is_err());
+/// ```
+impl std::str::FromStr for BigInt {
+    type Err = RMatrixError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let v = value.trim();
+        let (negative, digits) = match v.strip_prefix('-') {
+            Some(d) => (true, d),
+            None => (false, v.strip_prefix('+').unwrap_or(v)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(RMatrixError::ParseFailed(value.to_owned()));
+        }
+        let head = digits.len() % DECIMAL_DIGITS;
+        let chunks = std::iter::once(&digits[..head]).chain(
+            digits.as_bytes()[head..]
+                .chunks(DECIMAL_DIGITS)
+                .map(|c| std::str::from_utf8(c).unwrap_or_default()),
+        );
+        let scale = BigInt::from(DECIMAL_BASE as i64);
+        let mut n = BigInt::default();
+        for c in chunks.filter(|c| !c.is_empty()) {
+            let c = c
+                .parse::<i64>()
+                .map_err(|_| RMatrixError::ParseFailed(value.to_owned()))?;
+            n = n.mul_ref(&scale).add_ref(&BigInt::from(c));
+        }
+        Ok(if negative { -n } else { n })
+    }
+}
+
+impl std::iter::Sum for BigInt {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let mut n = BigInt::default();
+        for i in iter {
+            n = n + i;
+        }
+        n
+    }
+}
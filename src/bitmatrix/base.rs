@@ -0,0 +1,313 @@
+//! basic operations of BitMatrix
+
+use crate::{
+    bitmatrix::{BitMatrix, WORD},
+    error::RMatrixError,
+    finite::Gf,
+    matrix::Matrix,
+    number::Ring,
+};
+
+/// rows of a matrix combined at once in the multiplication by the method of four Russians
+const GROUP: usize = 8;
+
+/// transpose a block of 64x64 bits in place, bit `j` of word `i` goes to bit `i` of word `j`
+fn transpose_block(a: &mut [u64; WORD]) {
+    let mut j = 32;
+    let mut m = 0x0000_0000_FFFF_FFFFu64;
+    while j != 0 {
+        // exchange the upper right and the lower left quarter of every block of size 2j
+        let mut k = 0;
+        while k < WORD {
+            let t = ((a[k] >> j) ^ a[k + j]) & m;
+            a[k + j] ^= t;
+            a[k] ^= t << j;
+            k = (k + j + 1) & !j;
+        }
+        j >>= 1;
+        m ^= m << j;
+    }
+}
+
+impl BitMatrix {
+    /// return a zero matrix with specific size
+    pub fn zeros(r: usize, c: usize) -> Result<Self, RMatrixError> {
+        if r == 0 || c == 0 {
+            return Err(RMatrixError::ShapeUnreasonable);
+        }
+        let stride = c.div_ceil(WORD);
+        Ok(BitMatrix {
+            rows: r,
+            cols: c,
+            stride,
+            data: vec![0; r * stride],
+        })
+    }
+
+    /// return an identity matrix with specific size
+    pub fn eyes(n: usize) -> Result<Self, RMatrixError> {
+        let mut m = BitMatrix::zeros(n, n)?;
+        for i in 0..n {
+            m.data[i * m.stride + i / WORD] |= 1 << (i % WORD);
+        }
+        Ok(m)
+    }
+
+    /// generate a matrix from the rows of a string with `0` and `1`,
+    /// whitespace inside a row is ignored
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::bitmatrix::BitMatrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = BitMatrix::from_text("101\n011")?;
+    /// assert_eq!(m.dimensions(), (2, 3));
+    /// assert!(m.get(1, 3)? && !m.get(2, 1)?);
+    /// assert_eq!(m.to_string(), "101\n011\n");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_text(s: &str) -> Result<Self, RMatrixError> {
+        let rows = s
+            .lines()
+            .map(|l| l.chars().filter(|c| !c.is_whitespace()).collect::<Vec<_>>())
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>();
+        let cols = rows.first().map_or(0, |r| r.len());
+        let mut m = BitMatrix::zeros(rows.len(), cols)?;
+        for (i, r) in rows.iter().enumerate() {
+            if r.len() != cols {
+                return Err(RMatrixError::LengthInconsistent(r.len(), cols));
+            }
+            for (j, c) in r.iter().enumerate() {
+                match c {
+                    '0' => {}
+                    '1' => m.data[i * m.stride + j / WORD] |= 1 << (j % WORD),
+                    _ => return Err(RMatrixError::ParseFailed(c.to_string())),
+                }
+            }
+        }
+        Ok(m)
+    }
+
+    /// shape of a matrix
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// position of the word and the mask of an element, counted from 1
+    fn locate(&self, prow: usize, pcol: usize) -> Result<(usize, u64), RMatrixError> {
+        if prow == 0 || prow > self.rows || pcol == 0 || pcol > self.cols {
+            Err(RMatrixError::OutOfBoundary(prow, pcol))
+        } else {
+            let j = pcol - 1;
+            Ok(((prow - 1) * self.stride + j / WORD, 1 << (j % WORD)))
+        }
+    }
+
+    pub fn get(&self, prow: usize, pcol: usize) -> Result<bool, RMatrixError> {
+        let (w, mask) = self.locate(prow, pcol)?;
+        Ok(self.data[w] & mask != 0)
+    }
+
+    pub fn set(&mut self, elem: bool, prow: usize, pcol: usize) -> Result<(), RMatrixError> {
+        let (w, mask) = self.locate(prow, pcol)?;
+        if elem {
+            self.data[w] |= mask;
+        } else {
+            self.data[w] &= !mask;
+        }
+        Ok(())
+    }
+
+    /// bit `j` of row `i`, counted from 0
+    pub(crate) fn bit(&self, i: usize, j: usize) -> bool {
+        self.data[i * self.stride + j / WORD] >> (j % WORD) & 1 == 1
+    }
+
+    /// add row `from` to row `to` from word `start` on, counted from 0
+    pub(crate) fn xor_row(&mut self, from: usize, to: usize, start: usize) {
+        let s = self.stride;
+        for w in start..s {
+            self.data[to * s + w] ^= self.data[from * s + w];
+        }
+    }
+
+    /// exchange rows `i` and `j`, counted from 0
+    pub(crate) fn swap_rows(&mut self, i: usize, j: usize) {
+        let s = self.stride;
+        for w in 0..s {
+            self.data.swap(i * s + w, j * s + w);
+        }
+    }
+
+    /// add row `from` to row `to`, the row operation over GF(2)
+    pub fn add_row(&mut self, from: usize, to: usize) -> Result<(), RMatrixError> {
+        for r in [from, to] {
+            if r == 0 || r > self.rows {
+                return Err(RMatrixError::OutOfRowBoundary(r));
+            }
+        }
+        self.xor_row(from - 1, to - 1, 0);
+        Ok(())
+    }
+
+    /// exchange two rows
+    pub fn exchange_rows(&mut self, i: usize, j: usize) -> Result<(), RMatrixError> {
+        for r in [i, j] {
+            if r == 0 || r > self.rows {
+                return Err(RMatrixError::OutOfRowBoundary(r));
+            }
+        }
+        self.swap_rows(i - 1, j - 1);
+        Ok(())
+    }
+
+    /// transpose by blocks of 64x64 bits
+    pub fn transpose(&self) -> Self {
+        let stride = self.rows.div_ceil(WORD);
+        let mut t = BitMatrix {
+            rows: self.cols,
+            cols: self.rows,
+            stride,
+            data: vec![0; self.cols * stride],
+        };
+        let mut block = [0u64; WORD];
+        for bi in 0..stride {
+            for bj in 0..self.stride {
+                for (k, b) in block.iter_mut().enumerate() {
+                    let i = bi * WORD + k;
+                    *b = if i < self.rows {
+                        self.data[i * self.stride + bj]
+                    } else {
+                        0
+                    };
+                }
+                transpose_block(&mut block);
+                for (k, b) in block.iter().enumerate() {
+                    let j = bj * WORD + k;
+                    if j < self.cols {
+                        t.data[j * stride + bi] = *b;
+                    }
+                }
+            }
+        }
+        t
+    }
+
+    /// product by the method of four Russians
+    ///
+    /// the sums of every subset of `8` rows of `rhs` are tabulated once,
+    /// so a row of the product takes one table lookup per `8` columns of `self`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::bitmatrix::BitMatrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let a = BitMatrix::from_text("11\n01")?;
+    /// assert_eq!(a.times(&a)?, BitMatrix::eyes(2)?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn times(&self, rhs: &Self) -> Result<Self, RMatrixError> {
+        if self.cols != rhs.rows {
+            return Err(RMatrixError::ShapeInconsistent(
+                rhs.dimensions(),
+                self.dimensions(),
+            ));
+        }
+        let mut m = BitMatrix::zeros(self.rows, rhs.cols)?;
+        let s = rhs.stride;
+        let mut table = vec![0u64; (1 << GROUP) * s];
+        for g in (0..self.cols).step_by(GROUP) {
+            let k = GROUP.min(self.cols - g);
+            // table[mask] is the sum of the rows g + b of rhs for the bits b of mask
+            for mask in 1..(1usize << k) {
+                let low = mask.trailing_zeros() as usize;
+                let rest = mask & (mask - 1);
+                for w in 0..s {
+                    table[mask * s + w] = table[rest * s + w] ^ rhs.data[(g + low) * s + w];
+                }
+            }
+            for i in 0..self.rows {
+                let word = self.data[i * self.stride + g / WORD];
+                let mask = (word >> (g % WORD)) as usize & ((1 << k) - 1);
+                if mask != 0 {
+                    for w in 0..s {
+                        m.data[i * s + w] ^= table[mask * s + w];
+                    }
+                }
+            }
+        }
+        Ok(m)
+    }
+
+    pub fn plus(&self, rhs: &Self) -> Result<Self, RMatrixError> {
+        if self.dimensions() != rhs.dimensions() {
+            return Err(RMatrixError::ShapeInconsistent(
+                rhs.dimensions(),
+                self.dimensions(),
+            ));
+        }
+        let mut m = self.clone();
+        m.data.iter_mut().zip(&rhs.data).for_each(|(a, b)| *a ^= b);
+        Ok(m)
+    }
+
+    /// number of ones
+    pub fn count_ones(&self) -> usize {
+        self.data.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// whether every element is zero
+    pub fn is_zero(&self) -> bool {
+        self.data.iter().all(|&w| w == 0)
+    }
+
+    /// the same matrix with one [`Gf<2>`] element per bit
+    pub fn to_matrix(&self) -> Result<Matrix<Gf<2>>, RMatrixError> {
+        let data = (0..self.rows)
+            .flat_map(|i| (0..self.cols).map(move |j| (i, j)))
+            .map(|(i, j)| Gf::new(self.bit(i, j) as i64))
+            .collect();
+        Matrix::from_vec(self.rows, self.cols, data)
+    }
+}
+
+impl From<&Matrix<Gf<2>>> for BitMatrix {
+    fn from(m: &Matrix<Gf<2>>) -> Self {
+        let (r, c) = m.dimensions();
+        let stride = c.div_ceil(WORD);
+        let mut b = BitMatrix {
+            rows: r,
+            cols: c,
+            stride,
+            data: vec![0; r * stride],
+        };
+        for i in 0..r {
+            for j in 0..c {
+                if m.get(i + 1, j + 1).is_ok_and(|v| !v.is_zero()) {
+                    b.data[i * stride + j / WORD] |= 1 << (j % WORD);
+                }
+            }
+        }
+        b
+    }
+}
+
+/// rows of `0` and `1`, each followed by a newline
+impl std::fmt::Display for BitMatrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.rows {
+            let row = (0..self.cols)
+                .map(|j| if self.bit(i, j) { '1' } else { '0' })
+                .collect::<String>();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
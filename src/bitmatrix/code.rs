@@ -0,0 +1,54 @@
+//! binary linear codes given by generator or parity-check matrices
+//!
+//! codewords are rows, a generator matrix `G` has a basis of the code as rows,
+//! and a parity-check matrix `H` has `H * c^T = 0` exactly for the codewords `c`
+
+use crate::{bitmatrix::BitMatrix, error::RMatrixError};
+
+impl BitMatrix {
+    /// parity-check matrix of the code with this generator matrix,
+    /// a basis of the dual code as rows, `None` if the code is the whole space
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::bitmatrix::BitMatrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// // the [7, 4] Hamming code
+    /// let g = BitMatrix::from_text("1000110\n0100101\n0010011\n0001111")?;
+    /// let h = g.parity_check_matrix()?.unwrap();
+    /// assert_eq!(h.dimensions(), (3, 7));
+    /// assert!(g.times(&h.transpose())?.is_zero());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn parity_check_matrix(&self) -> Result<Option<Self>, RMatrixError> {
+        self.null_space_rows()
+    }
+
+    /// generator matrix of the code with this parity-check matrix,
+    /// a basis of the solutions of `H * c^T = 0` as rows, `None` for the zero code
+    pub fn generator_matrix(&self) -> Result<Option<Self>, RMatrixError> {
+        self.null_space_rows()
+    }
+
+    /// syndromes `H * r^T` of the received words, which are the rows of `words`,
+    /// as rows, a syndrome is zero exactly for a codeword
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::bitmatrix::BitMatrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let h = BitMatrix::from_text("1101100\n1011010\n0111001")?;
+    /// let s = h.syndrome(&BitMatrix::from_text("1000110\n1000111")?)?;
+    /// assert_eq!(s, BitMatrix::from_text("000\n001")?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn syndrome(&self, words: &Self) -> Result<Self, RMatrixError> {
+        words.times(&self.transpose())
+    }
+}
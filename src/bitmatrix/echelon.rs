@@ -0,0 +1,153 @@
+//! elimination of BitMatrix by the method of four Russians
+
+use crate::{
+    bitmatrix::{BitMatrix, WORD},
+    error::RMatrixError,
+};
+
+/// columns handled together in one elimination step
+const STRIP: usize = 8;
+
+impl BitMatrix {
+    /// gaussian elimination in place, returns the pivot columns counted from 0
+    ///
+    /// the columns are taken in strips of `8`, the pivot rows of a strip are reduced
+    /// among each other, then the sums of every subset of them are tabulated
+    /// and every other row is cleared in the strip by one table lookup,
+    /// if `reduce` is set the rows above the pivots are cleared too
+    pub(crate) fn eliminate(&mut self, reduce: bool) -> Vec<usize> {
+        let s = self.stride;
+        let mut pivots = Vec::new();
+        let mut table = vec![0u64; (1 << STRIP) * s];
+        let mut col = 0;
+        while col < self.cols && pivots.len() < self.rows {
+            let r = pivots.len();
+            let end = (col + STRIP).min(self.cols);
+            let start = col / WORD;
+            // pivot rows of the strip, reduced among each other
+            let mut strip: Vec<(usize, usize)> = Vec::new();
+            for c in col..end {
+                let first = r + strip.len();
+                let mut found = None;
+                for i in first..self.rows {
+                    for &(p, pc) in strip.iter() {
+                        if self.bit(i, pc) {
+                            self.xor_row(p, i, start);
+                        }
+                    }
+                    if self.bit(i, c) {
+                        found = Some(i);
+                        break;
+                    }
+                }
+                if let Some(i) = found {
+                    self.swap_rows(i, first);
+                    for &(p, _) in strip.iter() {
+                        if self.bit(p, c) {
+                            self.xor_row(first, p, start);
+                        }
+                    }
+                    strip.push((first, c));
+                }
+            }
+            if !strip.is_empty() {
+                // table[mask] is the sum of the pivot rows for the bits of mask
+                for mask in 1..(1usize << strip.len()) {
+                    let low = mask.trailing_zeros() as usize;
+                    let rest = mask & (mask - 1);
+                    let p = strip[low].0;
+                    for w in start..s {
+                        table[mask * s + w] = table[rest * s + w] ^ self.data[p * s + w];
+                    }
+                }
+                let above = if reduce { 0..r } else { 0..0 };
+                for i in above.chain((r + strip.len())..self.rows) {
+                    let mask = strip
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, &(_, pc))| self.bit(i, pc))
+                        .fold(0usize, |m, (b, _)| m | 1 << b);
+                    if mask != 0 {
+                        for w in start..s {
+                            self.data[i * s + w] ^= table[mask * s + w];
+                        }
+                    }
+                }
+                pivots.extend(strip.iter().map(|&(_, pc)| pc));
+            }
+            col = end;
+        }
+        pivots
+    }
+
+    /// reduced row echelon form with the pivot columns, counted from 1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::bitmatrix::BitMatrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = BitMatrix::from_text("110\n011\n101")?;
+    /// let (r, pivots) = m.row_reduce();
+    /// assert_eq!(r, BitMatrix::from_text("101\n011\n000")?);
+    /// assert_eq!(pivots, vec![1, 2]);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn row_reduce(&self) -> (Self, Vec<usize>) {
+        let mut m = self.clone();
+        let pivots = m.eliminate(true).into_iter().map(|p| p + 1).collect();
+        (m, pivots)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().eliminate(false).len()
+    }
+
+    /// basis of the solutions of `A * x = 0` as rows, `None` for the zero subspace
+    ///
+    /// there is one vector for every free column of the reduced row echelon form,
+    /// it is one at the free column and zero at the other free columns
+    pub fn null_space_rows(&self) -> Result<Option<Self>, RMatrixError> {
+        let mut m = self.clone();
+        let pivots = m.eliminate(true);
+        let mut is_pivot = vec![false; self.cols];
+        pivots.iter().for_each(|&p| is_pivot[p] = true);
+        let free = (0..self.cols).filter(|&j| !is_pivot[j]).collect::<Vec<_>>();
+        if free.is_empty() {
+            return Ok(None);
+        }
+        let mut n = BitMatrix::zeros(free.len(), self.cols)?;
+        let ns = n.stride;
+        for (k, &f) in free.iter().enumerate() {
+            n.data[k * ns + f / WORD] |= 1 << (f % WORD);
+            for (i, &p) in pivots.iter().enumerate() {
+                if m.bit(i, f) {
+                    n.data[k * ns + p / WORD] |= 1 << (p % WORD);
+                }
+            }
+        }
+        Ok(Some(n))
+    }
+
+    /// basis of the solutions of `A * x = 0` as columns like
+    /// [`Matrix::null_space`](crate::matrix::Matrix::null_space), `None` for the zero subspace
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::bitmatrix::BitMatrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = BitMatrix::from_text("1100\n0110")?;
+    /// let n = m.null_space()?.unwrap();
+    /// assert_eq!(n.dimensions(), (4, 2));
+    /// assert!(m.times(&n)?.is_zero());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn null_space(&self) -> Result<Option<Self>, RMatrixError> {
+        Ok(self.null_space_rows()?.map(|n| n.transpose()))
+    }
+}
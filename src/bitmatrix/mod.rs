@@ -0,0 +1,24 @@
+//! bit packed matrices over GF(2)
+//!
+//! a row is stored in `u64` words, bit `j % 64` of word `j / 64` is column `j + 1`,
+//! so a row operation handles 64 elements at once
+
+pub mod base;
+pub mod code;
+pub mod echelon;
+
+/// bits in a word
+const WORD: usize = 64;
+
+/// a matrix over GF(2), which uses one bit per element
+///
+/// rows and columns start from 1 like in [`Matrix`](crate::matrix::Matrix),
+/// bits after the last column of a row are always zero
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitMatrix {
+    rows: usize,
+    cols: usize,
+    /// words per row
+    stride: usize,
+    data: Vec<u64>,
+}
@@ -1,25 +1,60 @@
 //! basic implementation of Complex
 
-use crate::complex::Complex;
+use crate::{
+    complex::Complex,
+    number::{Float, Real, Ring},
+};
 
-impl Complex {
+impl<T: Real> Complex<T> {
     /// generate a complex number
-    pub fn new(re: f64, im: f64) -> Self {
+    pub fn new(re: T, im: T) -> Self {
         Complex(re, im)
     }
 
     /// get the real part of a complex number
-    pub fn re(&self) -> f64 {
-        self.0
+    pub fn re(&self) -> T {
+        self.0.clone()
     }
 
     /// get the imagine part of a complex number
-    pub fn im(&self) -> f64 {
-        self.1
+    pub fn im(&self) -> T {
+        self.1.clone()
     }
 
+    /// get the square of the norm, exact for exact parts
+    pub fn norm_sqr(&self) -> T {
+        self.re() * self.re() + self.im() * self.im()
+    }
+
+    /// get the complex conjugate
+    pub fn conj(&self) -> Self {
+        Complex::new(self.re(), -self.im())
+    }
+
+    /// get `1 / self`
+    pub fn recip(&self) -> Self {
+        Complex::one() / self.clone()
+    }
+}
+
+impl<T: Float> Complex<T> {
     /// get the norm of a complex number
-    pub fn norm(&self) -> f64 {
-        (self.re().powi(2) + self.im().powi(2)).sqrt()
+    pub fn norm(&self) -> T {
+        self.re().hypot(self.im())
+    }
+
+    /// get the argument in `(-pi, pi]`
+    pub fn arg(&self) -> T {
+        self.im().atan2(self.re())
+    }
+
+    /// generate a complex number from its norm and argument
+    pub fn from_polar(r: T, theta: T) -> Self {
+        Complex::new(r * theta.cos(), r * theta.sin())
+    }
+
+    /// get the norm and argument
+    pub fn to_polar(&self) -> (T, T) {
+        (self.norm(), self.arg())
     }
 }
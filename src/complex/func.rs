@@ -0,0 +1,164 @@
+//! elementary functions of Complex
+//!
+//! multivalued functions return the principal value,
+//! with branch cuts on the real or imaginary axis as in the C99 standard
+
+use crate::{
+    complex::Complex,
+    number::{Float, Ring},
+};
+
+impl<T: Float> Complex<T> {
+    /// the imaginary unit
+    fn i() -> Self {
+        Complex::new(T::default(), T::one())
+    }
+
+    fn half() -> T {
+        T::from_f64(0.5)
+    }
+
+    pub fn exp(&self) -> Self {
+        Complex::from_polar(self.re().exp(), self.im())
+    }
+
+    /// natural logarithm, the imaginary part is in `(-pi, pi]`
+    pub fn ln(&self) -> Self {
+        Complex::new(self.norm().ln(), self.arg())
+    }
+
+    /// square root with a non-negative real part
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::complex::Complex;
+    /// let z = Complex::new(-4.0, 0.0).sqrt();
+    /// assert_eq!((z.re(), z.im()), (0.0, 2.0));
+    /// ```
+    pub fn sqrt(&self) -> Self {
+        let (re, im) = (self.re(), self.im());
+        let zero = T::default();
+        if re == zero && im == zero {
+            return Complex::new(zero, im);
+        }
+        // avoid cancellation by computing the larger part first
+        let t = ((re.abs() + self.norm()) * Self::half()).sqrt();
+        let two_t = t + t;
+        if re >= zero {
+            Complex::new(t, im / two_t)
+        } else {
+            Complex::new(im.abs() / two_t, t.copysign(im))
+        }
+    }
+
+    /// integer power by repeated squaring
+    pub fn powi(&self, n: i32) -> Self {
+        let mut base = if n < 0 { self.recip() } else { *self };
+        let mut exp = n.unsigned_abs();
+        let mut acc = Complex::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = acc * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        acc
+    }
+
+    /// the power of zero, zero for a positive exponent and otherwise undefined
+    fn zero_pow(positive: bool) -> Self {
+        if positive {
+            Complex::default()
+        } else {
+            Complex::new(T::NAN, T::NAN)
+        }
+    }
+
+    /// real power, `0^x` is zero for positive `x`
+    pub fn powf(&self, x: T) -> Self {
+        if self.norm_sqr() == T::default() {
+            return Self::zero_pow(x > T::default());
+        }
+        let (r, theta) = self.to_polar();
+        Complex::from_polar(r.powf(x), theta * x)
+    }
+
+    /// complex power `exp(w * ln(self))`
+    pub fn powc(&self, w: Self) -> Self {
+        if self.norm_sqr() == T::default() {
+            return Self::zero_pow(w.re() > T::default());
+        }
+        (w * self.ln()).exp()
+    }
+
+    pub fn sin(&self) -> Self {
+        let (re, im) = (self.re(), self.im());
+        Complex::new(re.sin() * im.cosh(), re.cos() * im.sinh())
+    }
+
+    pub fn cos(&self) -> Self {
+        let (re, im) = (self.re(), self.im());
+        Complex::new(re.cos() * im.cosh(), -re.sin() * im.sinh())
+    }
+
+    pub fn tan(&self) -> Self {
+        // tan(x + iy) = (sin 2x + i sinh 2y) / (cos 2x + cosh 2y)
+        let (x, y) = (self.re() + self.re(), self.im() + self.im());
+        let d = x.cos() + y.cosh();
+        Complex::new(x.sin() / d, y.sinh() / d)
+    }
+
+    pub fn sinh(&self) -> Self {
+        let (re, im) = (self.re(), self.im());
+        Complex::new(re.sinh() * im.cos(), re.cosh() * im.sin())
+    }
+
+    pub fn cosh(&self) -> Self {
+        let (re, im) = (self.re(), self.im());
+        Complex::new(re.cosh() * im.cos(), re.sinh() * im.sin())
+    }
+
+    pub fn tanh(&self) -> Self {
+        // tanh(x + iy) = (sinh 2x + i sin 2y) / (cosh 2x + cos 2y)
+        let (x, y) = (self.re() + self.re(), self.im() + self.im());
+        let d = x.cosh() + y.cos();
+        Complex::new(x.sinh() / d, y.sin() / d)
+    }
+
+    /// `-i ln(iz + sqrt(1 - z^2))`
+    pub fn asin(&self) -> Self {
+        let (z, i, one) = (*self, Self::i(), Self::one());
+        -i * (i * z + (one - z * z).sqrt()).ln()
+    }
+
+    /// `pi / 2 - asin(z)`
+    pub fn acos(&self) -> Self {
+        Complex::from(T::PI * Self::half()) - self.asin()
+    }
+
+    /// `i / 2 (ln(1 - iz) - ln(1 + iz))`
+    pub fn atan(&self) -> Self {
+        let (z, i, one) = (*self, Self::i(), Self::one());
+        i * Self::half() * ((one - i * z).ln() - (one + i * z).ln())
+    }
+
+    /// `ln(z + sqrt(z^2 + 1))`
+    pub fn asinh(&self) -> Self {
+        let (z, one) = (*self, Self::one());
+        (z + (z * z + one).sqrt()).ln()
+    }
+
+    /// `ln(z + sqrt(z + 1) sqrt(z - 1))`
+    pub fn acosh(&self) -> Self {
+        let (z, one) = (*self, Self::one());
+        (z + (z + one).sqrt() * (z - one).sqrt()).ln()
+    }
+
+    /// `(ln(1 + z) - ln(1 - z)) / 2`
+    pub fn atanh(&self) -> Self {
+        let (z, one) = (*self, Self::one());
+        ((one + z).ln() - (one - z).ln()) * Self::half()
+    }
+}
@@ -1,8 +1,15 @@
 //! type of complex number
 
 pub mod base;
+pub mod func;
 pub mod num;
 pub mod utils;
 
+/// a complex number with parts of a [`Real`](crate::number::Real) type,
+/// `Complex` alone has `f64` parts
+///
+/// arithmetic works for any real type, like `Complex<Rational>` for exact Gaussian rationals,
+/// the elementary functions need a [`Float`](crate::number::Float)
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Complex(f64, f64);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Complex<T = f64>(T, T);
@@ -1,24 +1,24 @@
 //! implementation of Number for Complex
 
-use crate::complex::Complex;
+use crate::{complex::Complex, number::Real};
 
-impl std::ops::Add for Complex {
+impl<T: Real> std::ops::Add for Complex<T> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Complex::new(self.re() + rhs.re(), self.im() + rhs.re())
+        Complex::new(self.0 + rhs.0, self.1 + rhs.1)
     }
 }
 
-impl std::ops::Sub for Complex {
+impl<T: Real> std::ops::Sub for Complex<T> {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Complex::new(self.re() - rhs.re(), self.im() - self.im())
+        Complex::new(self.0 - rhs.0, self.1 - rhs.1)
     }
 }
 
-impl std::ops::Mul for Complex {
+impl<T: Real> std::ops::Mul for Complex<T> {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
@@ -29,18 +29,72 @@ impl std::ops::Mul for Complex {
     }
 }
 
-impl std::ops::Div for Complex {
+impl<T: Real> std::ops::Div for Complex<T> {
     type Output = Self;
 
+    /// Smith's algorithm, which avoids overflow in `|rhs|^2`
     fn div(self, rhs: Self) -> Self::Output {
-        Complex::new(self.re() / rhs.norm(), self.im() / rhs.norm())
+        let (a, b, c, d) = (self.0, self.1, rhs.0, rhs.1);
+        if c.abs_value() >= d.abs_value() {
+            let r = d.clone() / c.clone();
+            let t = c + d * r.clone();
+            Complex::new(
+                (a.clone() + b.clone() * r.clone()) / t.clone(),
+                (b - a * r) / t,
+            )
+        } else {
+            let r = c.clone() / d.clone();
+            let t = c * r.clone() + d;
+            Complex::new(
+                (a.clone() * r.clone() + b.clone()) / t.clone(),
+                (b * r - a) / t,
+            )
+        }
     }
 }
 
-impl std::ops::Neg for Complex {
+impl<T: Real> std::ops::Neg for Complex<T> {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
-        Complex::new(-self.re(), -self.im())
+        Complex::new(-self.0, -self.1)
     }
 }
+
+/// operators with a real number on the right
+macro_rules! impl_real_rhs {
+    ($($op:ident $method:ident),*) => {
+        $(
+            impl<T: Real> std::ops::$op<T> for Complex<T> {
+                type Output = Complex<T>;
+
+                fn $method(self, rhs: T) -> Self::Output {
+                    std::ops::$op::$method(self, Complex::from(rhs))
+                }
+            }
+        )*
+    };
+}
+
+/// operators with a primitive real number on the left
+macro_rules! impl_real_lhs {
+    ($($t:ident),*) => {
+        $(
+            impl_real_lhs!(@ops $t, Add add, Sub sub, Mul mul, Div div);
+        )*
+    };
+    (@ops $t:ident, $($op:ident $method:ident),*) => {
+        $(
+            impl std::ops::$op<Complex<$t>> for $t {
+                type Output = Complex<$t>;
+
+                fn $method(self, rhs: Complex<$t>) -> Self::Output {
+                    std::ops::$op::$method(Complex::from(self), rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_real_rhs!(Add add, Sub sub, Mul mul, Div div);
+impl_real_lhs!(f32, f64);
@@ -1,61 +1,107 @@
-use crate::{complex::Complex, error::RMatrixError};
+use crate::{
+    complex::Complex,
+    error::RMatrixError,
+    number::{pad_width, Real},
+};
 
-impl Default for Complex {
+impl<T: Real> Default for Complex<T> {
     fn default() -> Self {
-        Complex::new(f64::default(), f64::default())
+        Complex::new(T::default(), T::default())
     }
 }
 
-impl std::fmt::Display for Complex {
+impl<T: Real> From<T> for Complex<T> {
+    fn from(re: T) -> Self {
+        Complex::new(re, T::default())
+    }
+}
+
+/// `a+bI`, the precision applies to both parts and the width to the whole number
+///
+/// # Examples
+///
+/// ```
+/// # use rmatrix_ks::complex::Complex;
+/// let z = Complex::new(1.5, -0.25);
+/// assert_eq!(z.to_string(), "1.5-0.25I");
+/// assert_eq!(format!("{:.1}", z), "1.5-0.2I");
+/// assert_eq!(format!("[{:>12.2}]", z), "[  1.50-0.25I]");
+/// ```
+impl<T: Real> std::fmt::Display for Complex<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:.3}{:+.3}I", self.re(), self.im())
+        let s = match (f.precision(), f.sign_plus()) {
+            (Some(p), false) => format!("{:.p$}{:+.p$}I", self.re(), self.im()),
+            (Some(p), true) => format!("{:+.p$}{:+.p$}I", self.re(), self.im()),
+            (None, false) => format!("{}{:+}I", self.re(), self.im()),
+            (None, true) => format!("{:+}{:+}I", self.re(), self.im()),
+        };
+        pad_width(f, &s)
+    }
+}
+
+/// whether `c` ends a number with an imaginary unit
+fn is_unit(c: char) -> bool {
+    matches!(c, 'i' | 'j' | 'I' | 'J')
+}
+
+/// the imaginary part before a unit, which may be only a sign
+fn parse_imaginary<T: Real>(s: &str) -> Option<T> {
+    match s {
+        "" | "+" => Some(T::one()),
+        "-" => Some(T::neg_one()),
+        _ => s.parse().ok(),
     }
 }
 
-impl std::str::FromStr for Complex {
-    type Err = Box<dyn std::error::Error>;
+/// parse `a+bi`, `a`, `bi`, `-i` or `(a, b)`
+///
+/// the imaginary unit is one of `i`, `j`, `I` and `J`,
+/// whitespace is ignored and the parts may be in scientific notation
+///
+/// # Examples
+///
+/// ```
+/// # use rmatrix_ks::complex::Complex;
+/// assert_eq!("1e-3+2e5I".parse::<Complex>().unwrap(), Complex::new(1e-3, 2e5));
+/// assert_eq!(" - 2.5 j ".parse::<Complex>().unwrap(), Complex::new(0.0, -2.5));
+/// assert_eq!("-i".parse::<Complex>().unwrap(), Complex::new(0.0, -1.0));
+/// assert_eq!("(3, -4)".parse::<Complex>().unwrap(), Complex::new(3.0, -4.0));
+/// assert!("1+2xi".parse::<Complex>().is_err());
+/// ```
+impl<T: Real> std::str::FromStr for Complex<T> {
+    type Err = RMatrixError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let failed = || RMatrixError::ParseFailed(value.to_owned());
         let v = value.trim();
-        if v.is_empty() && !v.ends_with('I') {
-            Err(Box::new(RMatrixError::ParseFailed(value.to_owned())))
-        } else {
-            let mut v = v.chars();
-            let mut re: Vec<char> = Vec::new();
-            re.push(v.next().unwrap_or('0'));
-            let mut im: Vec<char> = Vec::new();
-            let mut is_re: bool = true;
-            v.for_each(|c| {
-                if is_re && !(c == '+' || c == '-') {
-                    re.push(c);
-                } else {
-                    if is_re {
-                        is_re = false;
-                    }
-                    im.push(c)
-                }
-            });
-            Ok(Complex(
-                re.iter()
-                    .filter(|c| !c.is_whitespace())
-                    .collect::<String>()
-                    .parse()?,
-                if im.is_empty() {
-                    f64::default()
-                } else {
-                    im[..im.len() - 1]
-                        .iter()
-                        .filter(|c| !c.is_whitespace())
-                        .collect::<String>()
-                        .parse()
-                        .unwrap_or_default()
-                },
-            ))
+        if let Some(inner) = v.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+            let (re, im) = inner.split_once(',').ok_or_else(failed)?;
+            return Ok(Complex::new(
+                re.trim().parse().map_err(|_| failed())?,
+                im.trim().parse().map_err(|_| failed())?,
+            ));
         }
+        let v: String = v.chars().filter(|c| !c.is_whitespace()).collect();
+        let body = match v.strip_suffix(is_unit) {
+            Some(body) => body,
+            None => {
+                return Ok(Complex::from(v.parse::<T>().map_err(|_| failed())?));
+            }
+        };
+        // the imaginary part starts at the last sign which is not in an exponent
+        let bytes = body.as_bytes();
+        let split = (1..bytes.len())
+            .rev()
+            .find(|&k| matches!(bytes[k], b'+' | b'-') && !matches!(bytes[k - 1], b'e' | b'E'));
+        let (re, im) = match split {
+            Some(k) => (body[..k].parse().map_err(|_| failed())?, &body[k..]),
+            None => (T::default(), body),
+        };
+        Ok(Complex::new(re, parse_imaginary(im).ok_or_else(failed)?))
     }
 }
 
-impl std::iter::Sum for Complex {
+impl<T: Real> std::iter::Sum for Complex<T> {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         let mut n = Complex::default();
         for i in iter {
@@ -2,6 +2,7 @@
 pub enum RMatrixError {
     ShapeUnreasonable,
     MatrixNotSquare,
+    MatrixSingular,
     ParseFailed(String),
     OutOfBoundary(usize, usize),
     OutOfRowBoundary(usize),
@@ -9,6 +10,13 @@ pub enum RMatrixError {
     LengthInconsistent(usize, usize),
     LinearEquationsNoSolution((usize, usize)),
     ShapeInconsistent((usize, usize), (usize, usize)),
+    IoFailed(String),
+    FormatUnsupported(String),
+    DimensionUnsupported(Vec<usize>),
+    ElementTypeMismatch(u8, u8),
+    ChecksumMismatch(u32, u32),
+    NotConverged(usize),
+    FunctionUndefined(String),
 }
 
 impl std::fmt::Display for RMatrixError {
@@ -16,6 +24,7 @@ impl std::fmt::Display for RMatrixError {
         match self {
             RMatrixError::ShapeUnreasonable => write!(f, "shape should be at least (1, 1)"),
             RMatrixError::MatrixNotSquare => write!(f, "matrix is not square"),
+            RMatrixError::MatrixSingular => write!(f, "matrix is singular"),
             RMatrixError::ParseFailed(s) => write!(f, "parse failed with {}", s),
             RMatrixError::OutOfBoundary(r, c) => {
                 write!(f, "position ({}, {}) out of boundary!", r, c)
@@ -37,8 +46,38 @@ impl std::fmt::Display for RMatrixError {
                     s1.0, s1.1, s2.0, s2.1
                 )
             }
+            RMatrixError::IoFailed(s) => write!(f, "io failed with {}", s),
+            RMatrixError::FormatUnsupported(s) => write!(f, "format {} is not supported", s),
+            RMatrixError::DimensionUnsupported(d) => {
+                write!(
+                    f,
+                    "array of shape ({}) can not be loaded as a matrix",
+                    d.iter()
+                        .map(|v| v.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+            RMatrixError::ElementTypeMismatch(e, t) => {
+                write!(f, "element type {} is inconsistent with expected {}", t, e)
+            }
+            RMatrixError::ChecksumMismatch(e, t) => {
+                write!(
+                    f,
+                    "checksum {:08x} is inconsistent with expected {:08x}",
+                    t, e
+                )
+            }
+            RMatrixError::NotConverged(n) => write!(f, "not converged after {} iterations", n),
+            RMatrixError::FunctionUndefined(s) => write!(f, "function is undefined, {}", s),
         }
     }
 }
 
 impl std::error::Error for RMatrixError {}
+
+impl From<std::io::Error> for RMatrixError {
+    fn from(e: std::io::Error) -> Self {
+        RMatrixError::IoFailed(e.to_string())
+    }
+}
@@ -0,0 +1,277 @@
+//! evaluation of parsed expressions
+
+use std::collections::BTreeMap;
+
+use crate::{
+    expr::{
+        parser::{BinaryOp, Expr, ExprKind},
+        ExprError, Value,
+    },
+    matrix::Matrix,
+    number::Ring,
+};
+
+/// names of the built-in functions with their usage
+pub const FUNCTIONS: &[(&str, &str)] = &[
+    ("det", "det(A)"),
+    ("inv", "inv(A)"),
+    ("rank", "rank(A)"),
+    ("tr", "tr(A)"),
+    ("rref", "rref(A)"),
+    ("transpose", "transpose(A)"),
+    ("expm", "expm(A)"),
+    ("logm", "logm(A)"),
+    ("sqrtm", "sqrtm(A)"),
+    ("sinm", "sinm(A)"),
+    ("cosm", "cosm(A)"),
+    ("sinhm", "sinhm(A)"),
+    ("coshm", "coshm(A)"),
+    ("powm", "powm(A, p)"),
+    ("eye", "eye(n) or eye(r, c)"),
+    ("zeros", "zeros(n) or zeros(r, c)"),
+    ("rand", "rand(r, c)"),
+    ("sqrt", "sqrt(x)"),
+    ("abs", "abs(x)"),
+    ("exp", "exp(x)"),
+    ("log", "log(x)"),
+    ("sin", "sin(x)"),
+    ("cos", "cos(x)"),
+];
+
+fn as_matrix(v: Value, e: &Expr) -> Result<Matrix<f64>, ExprError> {
+    match v {
+        Value::Matrix(m) => Ok(m),
+        Value::Scalar(s) => {
+            Matrix::from_vec(1, 1, vec![s]).map_err(|err| ExprError::matrix(e.span, err))
+        }
+    }
+}
+
+fn as_scalar(v: Value, e: &Expr) -> Result<f64, ExprError> {
+    match v {
+        Value::Scalar(s) => Ok(s),
+        Value::Matrix(_) => Err(ExprError::new(e.span, "expected a scalar")),
+    }
+}
+
+/// a non-negative integer argument, like a size
+fn as_size(v: Value, e: &Expr) -> Result<usize, ExprError> {
+    let s = as_scalar(v, e)?;
+    if s >= 0.0 && s.fract() == 0.0 {
+        Ok(s as usize)
+    } else {
+        Err(ExprError::new(e.span, "expected a non-negative integer"))
+    }
+}
+
+/// the most elements a matrix built by `eye`, `zeros` or `rand` may have
+pub const MAX_ELEMENTS: usize = 1 << 24;
+
+/// a matrix shape whose element count is at most [`MAX_ELEMENTS`]
+fn as_shape(r: usize, c: usize, e: &Expr) -> Result<(usize, usize), ExprError> {
+    match r.checked_mul(c) {
+        Some(n) if n <= MAX_ELEMENTS => Ok((r, c)),
+        _ => Err(ExprError::new(
+            e.span,
+            format!(
+                "a {}x{} matrix has more than {} elements",
+                r, c, MAX_ELEMENTS
+            ),
+        )),
+    }
+}
+
+fn call(
+    name: &str,
+    args: &[Expr],
+    e: &Expr,
+    vars: &BTreeMap<String, Value>,
+) -> Result<Value, ExprError> {
+    let usage = FUNCTIONS
+        .iter()
+        .find(|(f, _)| *f == name)
+        .map(|(_, u)| *u)
+        .ok_or_else(|| ExprError::new(e.span, format!("unknown function '{}'", name)))?;
+    let values = args
+        .iter()
+        .map(|a| eval(a, vars))
+        .collect::<Result<Vec<_>, _>>()?;
+    let wrap = |err| ExprError::matrix(e.span, err);
+    let mut values = values.into_iter().zip(args);
+    let value = match (name, values.next(), values.next(), values.next()) {
+        ("det", Some((a, ea)), None, _) => Value::Scalar(as_matrix(a, ea)?.det().map_err(wrap)?),
+        ("inv", Some((a, ea)), None, _) => {
+            Value::Matrix(as_matrix(a, ea)?.inverse().map_err(wrap)?)
+        }
+        ("rank", Some((a, ea)), None, _) => {
+            Value::Scalar(as_matrix(a, ea)?.rank().map_err(wrap)? as f64)
+        }
+        ("tr", Some((a, ea)), None, _) => Value::Scalar(as_matrix(a, ea)?.tr().map_err(wrap)?),
+        ("rref", Some((a, ea)), None, _) => {
+            Value::Matrix(as_matrix(a, ea)?.row_reduce().map_err(wrap)?.matrix)
+        }
+        ("transpose", Some((a, ea)), None, _) => {
+            Value::Matrix(as_matrix(a, ea)?.transpose().map_err(wrap)?)
+        }
+        ("expm", Some((a, ea)), None, _) => Value::Matrix(as_matrix(a, ea)?.expm().map_err(wrap)?),
+        ("logm", Some((a, ea)), None, _) => Value::Matrix(as_matrix(a, ea)?.logm().map_err(wrap)?),
+        ("sqrtm", Some((a, ea)), None, _) => {
+            Value::Matrix(as_matrix(a, ea)?.sqrtm().map_err(wrap)?)
+        }
+        ("sinm", Some((a, ea)), None, _) => Value::Matrix(as_matrix(a, ea)?.sinm().map_err(wrap)?),
+        ("cosm", Some((a, ea)), None, _) => Value::Matrix(as_matrix(a, ea)?.cosm().map_err(wrap)?),
+        ("sinhm", Some((a, ea)), None, _) => {
+            Value::Matrix(as_matrix(a, ea)?.sinhm().map_err(wrap)?)
+        }
+        ("coshm", Some((a, ea)), None, _) => {
+            Value::Matrix(as_matrix(a, ea)?.coshm().map_err(wrap)?)
+        }
+        ("powm", Some((a, ea)), Some((p, ep)), None) => {
+            let p = as_scalar(p, ep)?;
+            Value::Matrix(as_matrix(a, ea)?.powf(p).map_err(wrap)?)
+        }
+        ("eye" | "zeros", Some((r, er)), c, None) => {
+            let r = as_size(r, er)?;
+            let c = match c {
+                Some((c, ec)) => as_size(c, ec)?,
+                None => r,
+            };
+            let (r, c) = as_shape(r, c, e)?;
+            Value::Matrix(if name == "eye" {
+                Matrix::eyes(r, c).map_err(wrap)?
+            } else {
+                Matrix::zeros(r, c).map_err(wrap)?
+            })
+        }
+        ("rand", Some((r, er)), Some((c, ec)), None) => {
+            let (r, c) = as_shape(as_size(r, er)?, as_size(c, ec)?, e)?;
+            Value::Matrix(
+                Matrix::<f64>::rand(r, c, 0.0, 1.0)
+                    .map_err(|err| ExprError::new(e.span, err.to_string()))?,
+            )
+        }
+        ("sqrt" | "abs" | "exp" | "log" | "sin" | "cos", Some((x, ex)), None, _) => {
+            let x = as_scalar(x, ex)?;
+            Value::Scalar(match name {
+                "sqrt" => x.sqrt(),
+                "abs" => x.abs(),
+                "exp" => x.exp(),
+                "log" => x.ln(),
+                "sin" => x.sin(),
+                _ => x.cos(),
+            })
+        }
+        _ => return Err(ExprError::new(e.span, format!("usage: {}", usage))),
+    };
+    Ok(value)
+}
+
+fn binary(op: BinaryOp, lhs: Value, rhs: Value, e: &Expr) -> Result<Value, ExprError> {
+    let wrap = |err| ExprError::matrix(e.span, err);
+    let value = match (op, lhs, rhs) {
+        (BinaryOp::Add, Value::Scalar(a), Value::Scalar(b)) => Value::Scalar(a + b),
+        (BinaryOp::Sub, Value::Scalar(a), Value::Scalar(b)) => Value::Scalar(a - b),
+        (BinaryOp::Mul, Value::Scalar(a), Value::Scalar(b)) => Value::Scalar(a * b),
+        (BinaryOp::Div, Value::Scalar(a), Value::Scalar(b)) => Value::Scalar(a / b),
+        (BinaryOp::Pow, Value::Scalar(a), Value::Scalar(b)) => Value::Scalar(a.powf(b)),
+        (BinaryOp::Add, Value::Matrix(a), Value::Matrix(b)) => {
+            Value::Matrix(a.plus(&b).map_err(wrap)?)
+        }
+        (BinaryOp::Sub, Value::Matrix(a), Value::Matrix(b)) => {
+            Value::Matrix(a.subtract(&b).map_err(wrap)?)
+        }
+        (BinaryOp::Mul, Value::Matrix(a), Value::Matrix(b)) => {
+            Value::Matrix(a.times(&b).map_err(wrap)?)
+        }
+        (BinaryOp::Mul, Value::Scalar(k), Value::Matrix(m))
+        | (BinaryOp::Mul, Value::Matrix(m), Value::Scalar(k)) => {
+            Value::Matrix(m.smul(k).map_err(wrap)?)
+        }
+        (BinaryOp::Div, Value::Matrix(m), Value::Scalar(k)) => {
+            Value::Matrix(m.smul(f64::one() / k).map_err(wrap)?)
+        }
+        (BinaryOp::Div, Value::Matrix(a), Value::Matrix(b)) => {
+            Value::Matrix(a.times(&b.inverse().map_err(wrap)?).map_err(wrap)?)
+        }
+        (BinaryOp::Pow, Value::Matrix(m), Value::Scalar(k)) => {
+            if k.fract() != 0.0 {
+                return Err(ExprError::new(e.span, "matrix power must be an integer"));
+            }
+            Value::Matrix(m.pow(k as i64).map_err(wrap)?)
+        }
+        (op, lhs, rhs) => {
+            let kind = |v: &Value| match v {
+                Value::Scalar(_) => "scalar",
+                Value::Matrix(_) => "matrix",
+            };
+            let verb = match op {
+                BinaryOp::Add => "add",
+                BinaryOp::Sub => "subtract",
+                BinaryOp::Mul => "multiply",
+                BinaryOp::Div => "divide",
+                BinaryOp::Pow => "raise",
+            };
+            return Err(ExprError::new(
+                e.span,
+                format!("can not {} {} and {}", verb, kind(&lhs), kind(&rhs)),
+            ));
+        }
+    };
+    Ok(value)
+}
+
+/// evaluate an expression with the given variables
+pub fn eval(e: &Expr, vars: &BTreeMap<String, Value>) -> Result<Value, ExprError> {
+    match &e.kind {
+        ExprKind::Number(v) => Ok(Value::Scalar(*v)),
+        ExprKind::Var(name) => match vars.get(name) {
+            Some(v) => Ok(v.clone()),
+            None if name == "pi" => Ok(Value::Scalar(std::f64::consts::PI)),
+            None => Err(ExprError::new(
+                e.span,
+                format!("unknown variable '{}'", name),
+            )),
+        },
+        ExprKind::Call(name, args) => call(name, args, e, vars),
+        ExprKind::Matrix(rows) => {
+            let mut m: Option<Matrix<f64>> = None;
+            for row in rows.iter().filter(|r| !r.is_empty()) {
+                let mut block: Option<Matrix<f64>> = None;
+                for elem in row {
+                    let v = as_matrix(eval(elem, vars)?, elem)?;
+                    block = Some(match block {
+                        None => v,
+                        Some(b) => {
+                            Matrix::hcat(&b, &v).map_err(|err| ExprError::matrix(elem.span, err))?
+                        }
+                    });
+                }
+                if let Some(block) = block {
+                    m = Some(match m {
+                        None => block,
+                        Some(m) => Matrix::vcat(&m, &block).map_err(|err| {
+                            ExprError::matrix(row[0].span.join(&row[row.len() - 1].span), err)
+                        })?,
+                    });
+                }
+            }
+            m.map(Value::Matrix)
+                .ok_or_else(|| ExprError::new(e.span, "empty matrix"))
+        }
+        ExprKind::Neg(operand) => match eval(operand, vars)? {
+            Value::Scalar(v) => Ok(Value::Scalar(-v)),
+            Value::Matrix(m) => Ok(Value::Matrix(
+                m.smul(f64::neg_one())
+                    .map_err(|err| ExprError::matrix(e.span, err))?,
+            )),
+        },
+        ExprKind::Transpose(operand) => match eval(operand, vars)? {
+            Value::Scalar(v) => Ok(Value::Scalar(v)),
+            Value::Matrix(m) => Ok(Value::Matrix(
+                m.transpose()
+                    .map_err(|err| ExprError::matrix(e.span, err))?,
+            )),
+        },
+        ExprKind::Binary(op, lhs, rhs) => binary(*op, eval(lhs, vars)?, eval(rhs, vars)?, e),
+    }
+}
@@ -0,0 +1,116 @@
+//! tokenizer of the expression language
+
+use crate::expr::{ExprError, Span};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Number(f64),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    /// postfix `'`, the transpose
+    Quote,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Semicolon,
+    Assign,
+    End,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+    /// whether whitespace precedes the token, separates elements in `[1 -2]`
+    pub space_before: bool,
+}
+
+/// split the input into tokens, the last token is always [`TokenKind::End`]
+pub fn tokenize(input: &str) -> Result<Vec<Token>, ExprError> {
+    let bytes = input.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut space_before = false;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            space_before = true;
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let kind = match c {
+            b'0'..=b'9' | b'.' => {
+                while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+                    i += 1;
+                }
+                // exponent, only if digits follow
+                if i < bytes.len() && (bytes[i] == b'e' || bytes[i] == b'E') {
+                    let mut j = i + 1;
+                    if j < bytes.len() && (bytes[j] == b'+' || bytes[j] == b'-') {
+                        j += 1;
+                    }
+                    if j < bytes.len() && bytes[j].is_ascii_digit() {
+                        i = j;
+                        while i < bytes.len() && bytes[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                }
+                let text = &input[start..i];
+                TokenKind::Number(text.parse().map_err(|_| {
+                    ExprError::new(Span::new(start, i), format!("invalid number '{}'", text))
+                })?)
+            }
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                TokenKind::Ident(input[start..i].to_owned())
+            }
+            _ => {
+                i += 1;
+                match c {
+                    b'+' => TokenKind::Plus,
+                    b'-' => TokenKind::Minus,
+                    b'*' => TokenKind::Star,
+                    b'/' => TokenKind::Slash,
+                    b'^' => TokenKind::Caret,
+                    b'\'' => TokenKind::Quote,
+                    b'(' => TokenKind::LParen,
+                    b')' => TokenKind::RParen,
+                    b'[' => TokenKind::LBracket,
+                    b']' => TokenKind::RBracket,
+                    b',' => TokenKind::Comma,
+                    b';' => TokenKind::Semicolon,
+                    b'=' => TokenKind::Assign,
+                    _ => {
+                        let ch = input[start..].chars().next().unwrap_or_default();
+                        return Err(ExprError::new(
+                            Span::new(start, start + ch.len_utf8()),
+                            format!("unexpected character '{}'", ch),
+                        ));
+                    }
+                }
+            }
+        };
+        tokens.push(Token {
+            kind,
+            span: Span::new(start, i),
+            space_before,
+        });
+        space_before = false;
+    }
+    tokens.push(Token {
+        kind: TokenKind::End,
+        span: Span::new(input.len(), input.len()),
+        space_before,
+    });
+    Ok(tokens)
+}
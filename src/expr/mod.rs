@@ -0,0 +1,155 @@
+//! matrix expression language
+//!
+//! a [`Session`] evaluates statements like `A = [1 2; 3 4]` or `inv(A) * B + 2 * eye(2)`
+//! and keeps the assigned variables
+
+pub mod eval;
+pub mod lexer;
+pub mod parser;
+
+use std::collections::BTreeMap;
+
+use crate::{error::RMatrixError, matrix::Matrix};
+
+/// byte range of a piece of the input
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+
+    /// the smallest span covering both spans
+    pub fn join(&self, other: &Span) -> Self {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+}
+
+/// error of an expression with the span of the failing subexpression
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprError {
+    pub span: Span,
+    pub message: String,
+}
+
+impl ExprError {
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        ExprError {
+            span,
+            message: message.into(),
+        }
+    }
+
+    /// wrap an error of matrix operations
+    pub fn matrix(span: Span, e: RMatrixError) -> Self {
+        ExprError::new(span, e.to_string())
+    }
+
+    /// carets under the failing subexpression of `input`, followed by the message
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::expr::Session;
+    /// let mut s = Session::new();
+    /// let input = "1 + det([1 2 3])";
+    /// let e = s.execute(input).unwrap_err();
+    /// assert_eq!(e.underline(input), "    ^^^^^^^^^^^^ matrix is not square");
+    /// ```
+    pub fn underline(&self, input: &str) -> String {
+        let start = input
+            .get(..self.span.start)
+            .map_or(0, |s| s.chars().count());
+        let len = input
+            .get(self.span.start..self.span.end)
+            .map_or(1, |s| s.chars().count().max(1));
+        format!("{}{} {}", " ".repeat(start), "^".repeat(len), self.message)
+    }
+}
+
+impl std::fmt::Display for ExprError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at {}..{}",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+/// value of an expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Scalar(f64),
+    Matrix(Matrix<f64>),
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Scalar(v) => write!(f, "{}", v),
+            Value::Matrix(m) => write!(f, "{}", m),
+        }
+    }
+}
+
+/// variables of an interactive session
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    vars: BTreeMap<String, Value>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// execute an assignment `name = expr` or an expression
+    ///
+    /// the value of an expression is assigned to `ans`,
+    /// assigned matrices take the variable name as their `tag`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::expr::{Session, Value};
+    /// let mut s = Session::new();
+    /// s.execute("A = [1 2; 3 4]").unwrap();
+    /// let (name, value) = s.execute("det(A') + rank(A)").unwrap();
+    /// assert_eq!(name, "ans");
+    /// assert!(matches!(value, Value::Scalar(v) if (v - 0.0).abs() < 1e-12));
+    /// ```
+    pub fn execute(&mut self, input: &str) -> Result<(String, Value), ExprError> {
+        let (name, expr) = parser::parse_statement(&lexer::tokenize(input)?)?;
+        let mut value = eval::eval(&expr, &self.vars)?;
+        let name = name.unwrap_or_else(|| "ans".to_owned());
+        if let Value::Matrix(m) = &mut value {
+            m.tag = name.clone();
+        }
+        self.vars.insert(name.clone(), value.clone());
+        Ok((name, value))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.vars.get(name)
+    }
+
+    /// variables sorted by name
+    pub fn variables(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.vars.iter()
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Value> {
+        self.vars.remove(name)
+    }
+
+    pub fn clear(&mut self) {
+        self.vars.clear();
+    }
+}
@@ -0,0 +1,264 @@
+//! precedence climbing parser of the expression language
+//!
+//! | operator      | precedence | associativity |
+//! | ------------- | ---------- | ------------- |
+//! | `+` `-`       | 1          | left          |
+//! | `*` `/`       | 2          | left          |
+//! | unary `-` `+` | 3          |               |
+//! | `^`           | 4          | right         |
+//! | postfix `'`   | 5          |               |
+
+use crate::expr::{
+    lexer::{Token, TokenKind},
+    ExprError, Span,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind {
+    Number(f64),
+    Var(String),
+    Call(String, Vec<Expr>),
+    /// rows of blocks, concatenated horizontally then vertically
+    Matrix(Vec<Vec<Expr>>),
+    Neg(Box<Expr>),
+    Transpose(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+/// precedence of unary `-` and `+`
+const UNARY_PRECEDENCE: u8 = 3;
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    /// whether the parser is directly inside `[]`, where whitespace separates elements
+    in_matrix: Vec<bool>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &'a Token {
+        &self.tokens[self.pos.min(self.tokens.len() - 1)]
+    }
+
+    fn next(&mut self) -> &'a Token {
+        let t = self.peek();
+        if self.pos < self.tokens.len() {
+            self.pos += 1;
+        }
+        t
+    }
+
+    fn expect(&mut self, kind: TokenKind, what: &str) -> Result<&'a Token, ExprError> {
+        let t = self.next();
+        if t.kind == kind {
+            Ok(t)
+        } else {
+            Err(unexpected(t, what))
+        }
+    }
+
+    fn in_matrix(&self) -> bool {
+        self.in_matrix.last().copied().unwrap_or(false)
+    }
+
+    fn parse_expr(&mut self, min_precedence: u8) -> Result<Expr, ExprError> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            let t = self.peek();
+            let (op, precedence, right_assoc) = match t.kind {
+                TokenKind::Plus => (BinaryOp::Add, 1, false),
+                TokenKind::Minus => (BinaryOp::Sub, 1, false),
+                TokenKind::Star => (BinaryOp::Mul, 2, false),
+                TokenKind::Slash => (BinaryOp::Div, 2, false),
+                TokenKind::Caret => (BinaryOp::Pow, 4, true),
+                _ => break,
+            };
+            if precedence < min_precedence {
+                break;
+            }
+            // `[1 -2]` has two elements, `[1 - 2]` has one
+            if self.in_matrix()
+                && matches!(op, BinaryOp::Add | BinaryOp::Sub)
+                && t.space_before
+                && !self.tokens[self.pos + 1].space_before
+            {
+                break;
+            }
+            self.next();
+            let rhs = self.parse_expr(if right_assoc {
+                precedence
+            } else {
+                precedence + 1
+            })?;
+            let span = lhs.span.join(&rhs.span);
+            lhs = Expr {
+                kind: ExprKind::Binary(op, Box::new(lhs), Box::new(rhs)),
+                span,
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ExprError> {
+        let t = self.peek();
+        match t.kind {
+            TokenKind::Minus | TokenKind::Plus => {
+                self.next();
+                let operand = self.parse_expr(UNARY_PRECEDENCE + 1)?;
+                let span = t.span.join(&operand.span);
+                if t.kind == TokenKind::Plus {
+                    Ok(Expr {
+                        kind: operand.kind,
+                        span,
+                    })
+                } else {
+                    Ok(Expr {
+                        kind: ExprKind::Neg(Box::new(operand)),
+                        span,
+                    })
+                }
+            }
+            _ => self.parse_postfix(),
+        }
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expr, ExprError> {
+        let mut e = self.parse_primary()?;
+        while self.peek().kind == TokenKind::Quote && !self.peek().space_before {
+            let t = self.next();
+            let span = e.span.join(&t.span);
+            e = Expr {
+                kind: ExprKind::Transpose(Box::new(e)),
+                span,
+            };
+        }
+        Ok(e)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ExprError> {
+        let t = self.next();
+        match &t.kind {
+            TokenKind::Number(v) => Ok(Expr {
+                kind: ExprKind::Number(*v),
+                span: t.span,
+            }),
+            TokenKind::Ident(name) => {
+                if self.peek().kind == TokenKind::LParen && !self.peek().space_before {
+                    self.next();
+                    self.in_matrix.push(false);
+                    let mut args = Vec::new();
+                    if self.peek().kind != TokenKind::RParen {
+                        loop {
+                            args.push(self.parse_expr(1)?);
+                            if self.peek().kind == TokenKind::Comma {
+                                self.next();
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                    self.in_matrix.pop();
+                    let close = self.expect(TokenKind::RParen, "')'")?;
+                    Ok(Expr {
+                        kind: ExprKind::Call(name.clone(), args),
+                        span: t.span.join(&close.span),
+                    })
+                } else {
+                    Ok(Expr {
+                        kind: ExprKind::Var(name.clone()),
+                        span: t.span,
+                    })
+                }
+            }
+            TokenKind::LParen => {
+                self.in_matrix.push(false);
+                let e = self.parse_expr(1)?;
+                self.in_matrix.pop();
+                let close = self.expect(TokenKind::RParen, "')'")?;
+                Ok(Expr {
+                    kind: e.kind,
+                    span: t.span.join(&close.span),
+                })
+            }
+            TokenKind::LBracket => {
+                self.in_matrix.push(true);
+                let mut rows = vec![Vec::new()];
+                loop {
+                    match self.peek().kind {
+                        TokenKind::RBracket => break,
+                        TokenKind::Semicolon => {
+                            self.next();
+                            rows.push(Vec::new());
+                        }
+                        TokenKind::Comma => {
+                            self.next();
+                        }
+                        TokenKind::End => break,
+                        _ => {
+                            let e = self.parse_expr(1)?;
+                            if let Some(row) = rows.last_mut() {
+                                row.push(e);
+                            }
+                        }
+                    }
+                }
+                self.in_matrix.pop();
+                let close = self.expect(TokenKind::RBracket, "']'")?;
+                Ok(Expr {
+                    kind: ExprKind::Matrix(rows),
+                    span: t.span.join(&close.span),
+                })
+            }
+            _ => Err(unexpected(t, "an expression")),
+        }
+    }
+}
+
+fn unexpected(t: &Token, what: &str) -> ExprError {
+    if t.kind == TokenKind::End {
+        ExprError::new(t.span, format!("expected {} before the end", what))
+    } else {
+        ExprError::new(t.span, format!("expected {}", what))
+    }
+}
+
+/// parse an expression covering all tokens
+pub fn parse(tokens: &[Token]) -> Result<Expr, ExprError> {
+    let mut p = Parser {
+        tokens,
+        pos: 0,
+        in_matrix: Vec::new(),
+    };
+    let e = p.parse_expr(1)?;
+    p.expect(TokenKind::End, "an operator")?;
+    Ok(e)
+}
+
+/// parse `name = expr` or `expr`
+pub fn parse_statement(tokens: &[Token]) -> Result<(Option<String>, Expr), ExprError> {
+    match tokens {
+        [Token {
+            kind: TokenKind::Ident(name),
+            ..
+        }, Token {
+            kind: TokenKind::Assign,
+            ..
+        }, rest @ ..] => Ok((Some(name.clone()), parse(rest)?)),
+        _ => Ok((None, parse(tokens)?)),
+    }
+}
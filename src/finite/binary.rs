@@ -0,0 +1,228 @@
+//! implementation of the binary extension field GF(2^k)
+
+use crate::{error::RMatrixError, finite::Gf2k, number::pad_exact};
+
+/// degree of a polynomial over GF(2) given by its bits, `None` for zero
+fn degree(p: u64) -> Option<u32> {
+    p.checked_ilog2()
+}
+
+impl<const POLY: u64> Gf2k<POLY> {
+    /// the degree `k` of the field, there are `2^k` elements
+    pub const K: u32 = POLY.ilog2();
+
+    /// evaluated by every constructor and operation, so a `POLY` of degree zero does not compile,
+    /// the degree is at most 63 as `POLY` has 64 bits
+    const VALID_POLY: () = assert!(
+        POLY >= 2,
+        "the polynomial of GF(2^k) must have a degree of at least 1"
+    );
+
+    /// the element with the coefficients of the polynomial as bits,
+    /// reduced modulo `POLY`
+    pub fn new(bits: u64) -> Self {
+        let () = Self::VALID_POLY;
+        Gf2k(Self::reduce(bits as u128))
+    }
+
+    /// coefficients as bits, bit `i` is the coefficient of `x^i`
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// remainder of a polynomial of degree below 128 modulo `POLY`
+    fn reduce(mut p: u128) -> u64 {
+        let k = Self::K;
+        while p >> k != 0 {
+            let shift = p.ilog2() - k;
+            p ^= (POLY as u128) << shift;
+        }
+        p as u64
+    }
+
+    /// `self^e` by repeated squaring
+    pub fn pow(&self, mut e: u64) -> Self {
+        let () = Self::VALID_POLY;
+        let (mut base, mut result) = (*self, Gf2k::new(1));
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            e >>= 1;
+        }
+        result
+    }
+
+    /// the multiplicative inverse by the extended Euclidean algorithm for polynomials,
+    /// `None` for zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::finite::Gf2k;
+    /// // the inverse in the field of AES
+    /// assert_eq!(Gf2k::<0x11B>::new(0x53).inv(), Some(Gf2k::new(0xCA)));
+    /// ```
+    pub fn inv(&self) -> Option<Self> {
+        let () = Self::VALID_POLY;
+        // invariant: r_i = t_i * self (mod POLY), the t_i stay below degree k
+        let (mut r0, mut r1) = (POLY, self.0);
+        let (mut t0, mut t1) = (0u64, 1u64);
+        while let Some(d1) = degree(r1) {
+            // r0 modulo r1
+            while let Some(d0) = degree(r0).filter(|&d0| d0 >= d1) {
+                r0 ^= r1 << (d0 - d1);
+                t0 ^= t1 << (d0 - d1);
+            }
+            (r0, r1) = (r1, r0);
+            (t0, t1) = (t1, t0);
+        }
+        if r0 == 1 {
+            Some(Gf2k::new(t0))
+        } else {
+            None
+        }
+    }
+}
+
+/// addition is the exclusive or of the coefficients
+impl<const POLY: u64> std::ops::Add for Gf2k<POLY> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self::Output {
+        let () = Self::VALID_POLY;
+        Gf2k(self.0 ^ rhs.0)
+    }
+}
+
+/// subtraction is the same as addition in characteristic two
+impl<const POLY: u64> std::ops::Sub for Gf2k<POLY> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        let () = Self::VALID_POLY;
+        Gf2k(self.0 ^ rhs.0)
+    }
+}
+
+/// carry-less multiplication followed by the reduction modulo `POLY`
+impl<const POLY: u64> std::ops::Mul for Gf2k<POLY> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Self) -> Self::Output {
+        let () = Self::VALID_POLY;
+        let mut p = 0u128;
+        for i in 0..64 {
+            if rhs.0 >> i & 1 == 1 {
+                p ^= (self.0 as u128) << i;
+            }
+        }
+        Gf2k(Self::reduce(p))
+    }
+}
+
+/// multiplication by the inverse
+///
+/// # Panics
+///
+/// panics if `rhs` is zero
+impl<const POLY: u64> std::ops::Div for Gf2k<POLY> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv().expect("division by zero")
+    }
+}
+
+/// every element is its own negative in characteristic two
+impl<const POLY: u64> std::ops::Neg for Gf2k<POLY> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self
+    }
+}
+
+impl<const POLY: u64> std::iter::Sum for Gf2k<POLY> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let mut n = Gf2k::default();
+        for i in iter {
+            n = n + i;
+        }
+        n
+    }
+}
+
+/// the coefficients as bits, which must be a polynomial of degree below `k`
+/// unlike for [`Gf2k::new`]
+///
+/// # Examples
+///
+/// ```
+/// # use rmatrix_ks::finite::Gf2k;
+/// assert_eq!(Gf2k::<0x11B>::try_from(0x53).unwrap(), Gf2k::new(0x53));
+/// assert!(Gf2k::<0x11B>::try_from(0x153).is_err());
+/// ```
+impl<const POLY: u64> TryFrom<u64> for Gf2k<POLY> {
+    type Error = RMatrixError;
+
+    fn try_from(bits: u64) -> Result<Self, Self::Error> {
+        let () = Self::VALID_POLY;
+        if bits >> Self::K == 0 {
+            Ok(Gf2k(bits))
+        } else {
+            Err(RMatrixError::ParseFailed(format!(
+                "{:#x} is not reduced modulo {:#x}",
+                bits, POLY
+            )))
+        }
+    }
+}
+
+impl<const POLY: u64> From<Gf2k<POLY>> for u64 {
+    fn from(n: Gf2k<POLY>) -> Self {
+        n.0
+    }
+}
+
+/// the coefficients as a hexadecimal number, like `0x53`
+impl<const POLY: u64> std::fmt::Display for Gf2k<POLY> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        pad_exact(f, false, &format!("{:#x}", self.0))
+    }
+}
+
+/// parse the coefficients as a hexadecimal number with `0x`,
+/// a binary number with `0b` or a decimal number, which is reduced modulo `POLY`
+///
+/// # Examples
+///
+/// ```
+/// # use rmatrix_ks::finite::Gf2k;
+/// type Gf256 = Gf2k<0x11B>;
+/// assert_eq!("0x53".parse::<Gf256>().unwrap(), Gf256::new(0x53));
+/// assert_eq!("0b11".parse::<Gf256>().unwrap(), Gf256::new(3));
+/// assert_eq!("0x11B".parse::<Gf256>().unwrap(), Gf256::new(0));
+/// assert_eq!(Gf256::new(0x53).to_string(), "0x53");
+/// ```
+impl<const POLY: u64> std::str::FromStr for Gf2k<POLY> {
+    type Err = RMatrixError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let v = s.trim();
+        let bits = if let Some(h) = v.strip_prefix("0x").or_else(|| v.strip_prefix("0X")) {
+            u64::from_str_radix(h, 16)
+        } else if let Some(b) = v.strip_prefix("0b").or_else(|| v.strip_prefix("0B")) {
+            u64::from_str_radix(b, 2)
+        } else {
+            v.parse::<u64>()
+        };
+        bits.map(Gf2k::new)
+            .map_err(|_| RMatrixError::ParseFailed(s.to_owned()))
+    }
+}
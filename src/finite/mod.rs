@@ -0,0 +1,42 @@
+//! types of finite field elements
+//!
+//! arithmetic is exact, so eliminations like [`Matrix::rank`](crate::matrix::Matrix::rank),
+//! [`Matrix::det`](crate::matrix::Matrix::det) and [`Matrix::inverse`](crate::matrix::Matrix::inverse)
+//! are exact as well
+
+pub mod binary;
+pub mod prime;
+
+/// an element of the prime field `GF(P)`, the residues `0..P` modulo a prime `P`
+///
+/// `P` is not checked to be a prime, with a composite modulus `GF(P)` is only a ring
+/// and dividing by a zero divisor panics like dividing by zero,
+/// a modulus below 2 is rejected when compiling
+///
+/// ```compile_fail
+/// # use rmatrix_ks::finite::Gf;
+/// let x = Gf::<1>::new(3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u64", into = "u64"))]
+pub struct Gf<const P: u64>(u64);
+
+/// an element of the binary extension field `GF(2^k)`, a polynomial over `GF(2)`
+/// modulo the irreducible polynomial `POLY` of degree `k`
+///
+/// bit `i` is the coefficient of `x^i`, like `Gf2k<0x11B>` for the field of AES
+/// with `x^8 + x^4 + x^3 + x + 1`, the degree is at most 63
+///
+/// `POLY` is not checked to be irreducible, with a reducible polynomial `GF(2^k)` is only a ring
+/// and dividing by a zero divisor panics like dividing by zero,
+/// a polynomial of degree zero is rejected when compiling
+///
+/// ```compile_fail
+/// # use rmatrix_ks::finite::Gf2k;
+/// let x = Gf2k::<1>::new(1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u64", into = "u64"))]
+pub struct Gf2k<const POLY: u64>(u64);
@@ -0,0 +1,196 @@
+//! implementation of the prime field GF(P)
+
+use crate::{error::RMatrixError, finite::Gf, number::pad_exact};
+
+impl<const P: u64> Gf<P> {
+    /// evaluated by every constructor and operation, so `GF(0)` and `GF(1)` do not compile
+    const VALID_MODULUS: () = assert!(P >= 2, "the modulus of GF(P) must be at least 2");
+
+    /// the residue of `n` modulo `P`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::finite::Gf;
+    /// assert_eq!(Gf::<7>::new(-1).value(), 6);
+    /// assert_eq!(Gf::<7>::new(3) * Gf::new(5), Gf::new(1));
+    /// ```
+    pub fn new(n: i64) -> Self {
+        let () = Self::VALID_MODULUS;
+        Gf((n as i128).rem_euclid(P as i128) as u64)
+    }
+
+    /// the residue in `0..P`
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// `self^e` by repeated squaring
+    pub fn pow(&self, mut e: u64) -> Self {
+        let () = Self::VALID_MODULUS;
+        let (mut base, mut result) = (*self, Gf(1 % P));
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            e >>= 1;
+        }
+        result
+    }
+
+    /// the multiplicative inverse by the extended Euclidean algorithm,
+    /// `None` if the element has no inverse
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::finite::Gf;
+    /// assert_eq!(Gf::<101>::new(2).inv(), Some(Gf::new(51)));
+    /// assert_eq!(Gf::<101>::new(0).inv(), None);
+    /// ```
+    pub fn inv(&self) -> Option<Self> {
+        // invariant: r_i = t_i * self (mod P)
+        let (mut r0, mut r1) = (P as i128, self.0 as i128);
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        if r0 == 1 {
+            Some(Gf(t0.rem_euclid(P as i128) as u64))
+        } else {
+            None
+        }
+    }
+}
+
+impl<const P: u64> std::ops::Add for Gf<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let () = Self::VALID_MODULUS;
+        Gf(((self.0 as u128 + rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> std::ops::Sub for Gf<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl<const P: u64> std::ops::Mul for Gf<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let () = Self::VALID_MODULUS;
+        Gf(((self.0 as u128 * rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+/// multiplication by the inverse
+///
+/// # Panics
+///
+/// panics if `rhs` is not invertible
+impl<const P: u64> std::ops::Div for Gf<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv().expect("division by a non-invertible element")
+    }
+}
+
+impl<const P: u64> std::ops::Neg for Gf<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.0 == 0 {
+            self
+        } else {
+            Gf(P - self.0)
+        }
+    }
+}
+
+impl<const P: u64> std::iter::Sum for Gf<P> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let mut n = Gf::default();
+        for i in iter {
+            n = n + i;
+        }
+        n
+    }
+}
+
+impl<const P: u64> From<i64> for Gf<P> {
+    fn from(n: i64) -> Self {
+        Gf::new(n)
+    }
+}
+
+/// the residue `n`, which must be in `0..P` unlike for [`Gf::new`]
+///
+/// # Examples
+///
+/// ```
+/// # use rmatrix_ks::finite::Gf;
+/// assert_eq!(Gf::<7>::try_from(3u64).unwrap(), Gf::new(3));
+/// assert!(Gf::<7>::try_from(100u64).is_err());
+/// ```
+impl<const P: u64> TryFrom<u64> for Gf<P> {
+    type Error = RMatrixError;
+
+    fn try_from(n: u64) -> Result<Self, Self::Error> {
+        let () = Self::VALID_MODULUS;
+        if n < P {
+            Ok(Gf(n))
+        } else {
+            Err(RMatrixError::ParseFailed(format!(
+                "{} is not a residue modulo {}",
+                n, P
+            )))
+        }
+    }
+}
+
+impl<const P: u64> From<Gf<P>> for u64 {
+    fn from(n: Gf<P>) -> Self {
+        n.0
+    }
+}
+
+/// the residue in `0..P`
+impl<const P: u64> std::fmt::Display for Gf<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        pad_exact(f, false, &self.0.to_string())
+    }
+}
+
+/// parse an integer, which is reduced modulo `P`
+///
+/// # Examples
+///
+/// ```
+/// # use rmatrix_ks::finite::Gf;
+/// assert_eq!("-3".parse::<Gf<7>>().unwrap(), Gf::new(4));
+/// assert_eq!("100".parse::<Gf<7>>().unwrap(), Gf::new(2));
+/// assert!("0.5".parse::<Gf<7>>().is_err());
+/// ```
+impl<const P: u64> std::str::FromStr for Gf<P> {
+    type Err = RMatrixError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let () = Self::VALID_MODULUS;
+        let n = s
+            .trim()
+            .parse::<i128>()
+            .map_err(|_| RMatrixError::ParseFailed(s.to_owned()))?;
+        Ok(Gf(n.rem_euclid(P as i128) as u64))
+    }
+}
@@ -1,4 +1,10 @@
+pub mod bigint;
+pub mod bitmatrix;
 pub mod complex;
 pub mod error;
+pub mod expr;
+pub mod finite;
 pub mod matrix;
 pub mod number;
+pub mod polynomial;
+pub mod rational;
@@ -1,15 +1,399 @@
-use rmatrix_ks::matrix::Matrix;
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // let m = Matrix::<f64>::from_stdin()?;
-    // let a = Matrix::from_vec(
-    //     3,
-    //     4,
-    //     vec![1.0, 1.0, 2.0, 1.0, 1.0, 1.0, 2.0, 6.0, 1.0, 2.0, 5.0, 2.0],
-    // )?;
-    // let b = Matrix::from_vec(3, 1, vec![5.0, 10.0, 7.0])?;
-    // Matrix::solve_linear_equations(&a, &b)?;
-    let a = Matrix::from_vec(3, 3, vec![1.0, 7.0, 3.0, 4.0, 5.0, 9.0, 7.0, 8.0, 9.0])?;
-    println!("{}", a.det()?);
-    Ok(())
+//! command-line matrix calculator
+
+use std::io::{Read, Write};
+use std::process::ExitCode;
+
+use rmatrix_ks::{
+    complex::Complex,
+    error::RMatrixError,
+    expr::{eval::FUNCTIONS, Session},
+    matrix::{market::MarketElement, Matrix},
+};
+
+const REPL_HELP: &str = "\
+statements:
+    A = [1 2; 3 4]      assign a matrix, rows are separated by `;`
+    inv(A) * B + 2      evaluate an expression, the value is assigned to `ans`
+    vars                list variables
+    clear [NAME...]     remove some or all variables
+    exit                leave the calculator
+
+operators: + - * / ^ and the transpose A'";
+
+const USAGE: &str = "\
+usage: rmatrix <command> [options] [FILE...]
+
+commands:
+    det          determinant of a square matrix
+    inv          inverse of a square matrix
+    rank         rank of a matrix
+    rref         reduced row echelon form of a matrix
+    transpose    transpose of a matrix
+    eigen        eigenvalues of a real square matrix
+    add A B      sum A + B
+    sub A B      difference A - B
+    mul A B      product A * B
+    solve A B    solution X of A * X = B, followed by a basis of the null space of A
+                 if the solution is not unique
+    repl         interactive calculator, try `help` inside
+
+options:
+    -f, --from <text|csv|mm>    input format, guessed from the file extension by default
+    -t, --to <text|csv|mm>      output format, text by default
+    -c, --complex               read complex elements
+    -h, --help                  show this message
+
+a FILE of `-` is stdin, if no FILE is given all matrices are read from stdin,
+separated by empty lines or by the header lines of Matrix Market
+
+exit codes:
+    0    success
+    1    usage or io error
+    2    parse error
+    3    shape error
+    4    singular input
+    5    no convergence";
+
+/// exchange formats of matrices
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Csv,
+    Market,
+}
+
+impl Format {
+    fn parse(s: &str) -> Result<Self, CliError> {
+        match s {
+            "text" | "txt" => Ok(Format::Text),
+            "csv" => Ok(Format::Csv),
+            "mm" | "mtx" | "market" => Ok(Format::Market),
+            _ => Err(CliError::Usage(format!("unknown format '{}'", s))),
+        }
+    }
+
+    fn guess(path: &str) -> Self {
+        match std::path::Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+        {
+            Some("csv") => Format::Csv,
+            Some("mtx") | Some("mm") => Format::Market,
+            _ => Format::Text,
+        }
+    }
+
+    fn read<N: MarketElement>(&self, s: &str) -> Result<Matrix<N>, RMatrixError> {
+        match self {
+            Format::Text => Matrix::from_text(s),
+            Format::Csv => Matrix::from_csv(s),
+            Format::Market => Matrix::from_matrix_market(s),
+        }
+    }
+
+    fn write<N: MarketElement>(&self, m: &Matrix<N>) -> String {
+        match self {
+            Format::Text => m.to_text(),
+            Format::Csv => m.to_csv(),
+            Format::Market => m.to_matrix_market(),
+        }
+    }
+
+    /// split a stream holding several matrices
+    fn split<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        let mut blocks = Vec::new();
+        let mut start = 0;
+        let mut offset = 0;
+        for line in s.split_inclusive('\n') {
+            let separator = match self {
+                Format::Market => line.to_lowercase().starts_with("%%matrixmarket"),
+                _ => line.trim().is_empty(),
+            };
+            if separator {
+                blocks.push(&s[start..offset]);
+                start = offset;
+            }
+            offset += line.len();
+        }
+        blocks.push(&s[start..]);
+        blocks
+            .into_iter()
+            .filter(|b| b.lines().any(|l| !l.trim().is_empty()))
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+enum CliError {
+    Usage(String),
+    Matrix(RMatrixError),
+}
+
+impl From<RMatrixError> for CliError {
+    fn from(e: RMatrixError) -> Self {
+        CliError::Matrix(e)
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(e: std::io::Error) -> Self {
+        CliError::Matrix(e.into())
+    }
+}
+
+impl CliError {
+    fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Usage(_) => 1,
+            CliError::Matrix(e) => match e {
+                RMatrixError::IoFailed(_) => 1,
+                RMatrixError::ParseFailed(_)
+                | RMatrixError::FormatUnsupported(_)
+                | RMatrixError::DimensionUnsupported(_)
+                | RMatrixError::ElementTypeMismatch(_, _)
+                | RMatrixError::ChecksumMismatch(_, _) => 2,
+                RMatrixError::ShapeUnreasonable
+                | RMatrixError::MatrixNotSquare
+                | RMatrixError::OutOfBoundary(_, _)
+                | RMatrixError::OutOfRowBoundary(_)
+                | RMatrixError::OutOfColumnBoundary(_)
+                | RMatrixError::LengthInconsistent(_, _)
+                | RMatrixError::ShapeInconsistent(_, _) => 3,
+                RMatrixError::MatrixSingular
+                | RMatrixError::LinearEquationsNoSolution(_)
+                | RMatrixError::FunctionUndefined(_) => 4,
+                RMatrixError::NotConverged(_) => 5,
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Usage(s) => write!(f, "{}", s),
+            CliError::Matrix(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Options {
+    command: String,
+    from: Option<Format>,
+    to: Format,
+    complex: bool,
+    files: Vec<String>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Option<Self>, CliError> {
+        let mut command = None;
+        let mut from = None;
+        let mut to = Format::Text;
+        let mut complex = false;
+        let mut files = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| CliError::Usage(format!("missing value of {}", name)))
+            };
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-f" | "--from" => from = Some(Format::parse(value(arg)?)?),
+                "-t" | "--to" => to = Format::parse(value(arg)?)?,
+                "-c" | "--complex" => complex = true,
+                s if s.starts_with('-') && s != "-" => {
+                    return Err(CliError::Usage(format!("unknown option '{}'", s)))
+                }
+                s if command.is_none() => command = Some(s.to_owned()),
+                s => files.push(s.to_owned()),
+            }
+        }
+        match command {
+            Some(command) => Ok(Some(Options {
+                command,
+                from,
+                to,
+                complex,
+                files,
+            })),
+            None => Err(CliError::Usage("missing command".to_owned())),
+        }
+    }
+
+    fn arity(&self) -> Result<usize, CliError> {
+        match self.command.as_str() {
+            "det" | "inv" | "rank" | "rref" | "transpose" | "eigen" => Ok(1),
+            "add" | "sub" | "mul" | "solve" => Ok(2),
+            s => Err(CliError::Usage(format!("unknown command '{}'", s))),
+        }
+    }
+
+    fn read_inputs<N: MarketElement>(&self) -> Result<Vec<Matrix<N>>, CliError> {
+        let count = self.arity()?;
+        let mut sources: Vec<(String, Format)> = Vec::new();
+        if self.files.is_empty() {
+            let mut s = String::new();
+            std::io::stdin().read_to_string(&mut s)?;
+            let format = self.from.unwrap_or(Format::Text);
+            sources.extend(format.split(&s).into_iter().map(|b| (b.to_owned(), format)));
+        } else {
+            if self.files.iter().filter(|f| f.as_str() == "-").count() > 1 {
+                return Err(CliError::Usage("stdin can only be read once".to_owned()));
+            }
+            for file in self.files.iter() {
+                let mut s = String::new();
+                if file == "-" {
+                    std::io::stdin().read_to_string(&mut s)?;
+                } else {
+                    s = std::fs::read_to_string(file)?;
+                }
+                sources.push((s, self.from.unwrap_or_else(|| Format::guess(file))));
+            }
+        }
+        if sources.len() != count {
+            return Err(CliError::Usage(format!(
+                "command '{}' needs {} matrices, got {}",
+                self.command,
+                count,
+                sources.len()
+            )));
+        }
+        Ok(sources
+            .iter()
+            .map(|(s, format)| format.read(s))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+}
+
+fn run<N: MarketElement>(opts: &Options) -> Result<String, CliError> {
+    let inputs = opts.read_inputs::<N>()?;
+    let (a, b) = (&inputs[0], inputs.get(1));
+    let out = match (opts.command.as_str(), b) {
+        ("det", _) => format!("{}\n", a.det()?),
+        ("rank", _) => format!("{}\n", a.rank()?),
+        ("inv", _) => opts.to.write(&a.inverse()?),
+        ("rref", _) => opts.to.write(&a.row_reduce()?.matrix),
+        ("transpose", _) => opts.to.write(&a.transpose()?),
+        ("add", Some(b)) => opts.to.write(&a.plus(b)?),
+        ("sub", Some(b)) => opts.to.write(&a.subtract(b)?),
+        ("mul", Some(b)) => opts.to.write(&a.times(b)?),
+        ("solve", Some(b)) => {
+            let (x, null_space) = Matrix::general_solution(a, b)?;
+            let mut out = opts.to.write(&x);
+            if let Some(n) = null_space {
+                if opts.to != Format::Market {
+                    out.push('\n');
+                }
+                out.push_str(&opts.to.write(&n));
+            }
+            out
+        }
+        (s, _) => return Err(CliError::Usage(format!("unknown command '{}'", s))),
+    };
+    Ok(out)
+}
+
+fn run_eigen(opts: &Options) -> Result<String, CliError> {
+    if opts.complex {
+        return Err(CliError::Usage(
+            "eigen only supports real matrices".to_owned(),
+        ));
+    }
+    let inputs = opts.read_inputs::<f64>()?;
+    let ev = inputs[0].eigenvalues()?;
+    if ev.iter().all(|v| v.im() == 0.0) {
+        let re = ev.iter().map(|v| v.re()).collect::<Vec<_>>();
+        Ok(opts.to.write(&Matrix::from_vec(re.len(), 1, re)?))
+    } else {
+        Ok(opts
+            .to
+            .write(&Matrix::<Complex>::from_vec(ev.len(), 1, ev)?))
+    }
+}
+
+fn repl() -> Result<String, CliError> {
+    const PROMPT: &str = ">> ";
+    let mut session = Session::new();
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+    let mut line = String::new();
+    loop {
+        write!(stdout, "{}", PROMPT)?;
+        stdout.flush()?;
+        line.clear();
+        if stdin.read_line(&mut line)? == 0 {
+            writeln!(stdout)?;
+            break;
+        }
+        let input = line.trim_end();
+        let mut words = input.split_whitespace();
+        match words.next() {
+            None => {}
+            Some("exit") | Some("quit") => break,
+            Some("help") => {
+                writeln!(stdout, "{}", REPL_HELP)?;
+                let functions = FUNCTIONS.iter().map(|(_, u)| *u).collect::<Vec<_>>();
+                writeln!(stdout, "functions: {}", functions.join(", "))?;
+            }
+            Some("vars") | Some("who") => {
+                for (name, value) in session.variables() {
+                    let (r, c) = match value {
+                        rmatrix_ks::expr::Value::Scalar(_) => (1, 1),
+                        rmatrix_ks::expr::Value::Matrix(m) => m.dimensions(),
+                    };
+                    writeln!(stdout, "{:<12} {}x{}", name, r, c)?;
+                }
+            }
+            Some("clear") => {
+                let names = words.collect::<Vec<_>>();
+                if names.is_empty() {
+                    session.clear();
+                }
+                for name in names {
+                    if session.remove(name).is_none() {
+                        writeln!(stdout, "no variable '{}'", name)?;
+                    }
+                }
+            }
+            Some(_) => match session.execute(input) {
+                Ok((name, value)) => writeln!(stdout, "{} = {}", name, value)?,
+                Err(e) => writeln!(stdout, "{}{}", " ".repeat(PROMPT.len()), e.underline(input))?,
+            },
+        }
+    }
+    Ok(String::new())
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let result = Options::parse(&args).and_then(|opts| match opts {
+        None => Ok(format!("{}\n", USAGE)),
+        Some(opts) if opts.command == "eigen" => run_eigen(&opts),
+        Some(opts) if opts.command == "repl" => repl(),
+        Some(opts) if opts.complex => run::<Complex>(&opts),
+        Some(opts) => run::<f64>(&opts),
+    });
+    match result {
+        Ok(out) => {
+            let mut stdout = std::io::stdout();
+            if stdout
+                .write_all(out.as_bytes())
+                .and_then(|_| stdout.flush())
+                .is_err()
+            {
+                return ExitCode::from(1);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("rmatrix: {}", e);
+            if let CliError::Usage(_) = e {
+                eprintln!("try 'rmatrix --help' for more information");
+            }
+            ExitCode::from(e.exit_code())
+        }
+    }
 }
@@ -1,8 +1,12 @@
 //! attribute of matrices
 
-use crate::{error::RMatrixError, matrix::Matrix, number::Number};
+use crate::{
+    error::RMatrixError,
+    matrix::Matrix,
+    number::{Number, Ring},
+};
 
-impl<N: Number> Matrix<N> {
+impl<N: Ring> Matrix<N> {
     /// shape of a matrix
     ///
     /// # Examples
@@ -61,21 +65,23 @@ impl<N: Number> Matrix<N> {
         }
         Ok(t)
     }
+}
 
+impl<N: Number> Matrix<N> {
     pub fn inverse(&self) -> Result<Self, RMatrixError> {
         if self.shape.row != self.shape.col {
             Err(RMatrixError::MatrixNotSquare)
         } else {
-            Ok(self.row_reduce()?.1)
+            let e = self.row_reduce()?;
+            if e.rank < self.shape.row {
+                Err(RMatrixError::MatrixSingular)
+            } else {
+                Ok(e.transform)
+            }
         }
     }
 
     pub fn rank(&self) -> Result<usize, RMatrixError> {
-        let (m, _, _) = self.row_eliminate()?;
-        Ok((1..=m.shape.row)
-            .map(|i| m.get_row(i).unwrap())
-            .map(|r| r.iter().any(|v| !v.is_zero()))
-            .filter(|&v| v)
-            .count())
+        Ok(self.row_eliminate()?.rank)
     }
 }
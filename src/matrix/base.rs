@@ -1,8 +1,8 @@
 //! basic operations of matrices
 
-use crate::{error::RMatrixError, matrix::Matrix, number::Number};
+use crate::{error::RMatrixError, matrix::Matrix, number::Ring};
 
-impl<N: Number> Matrix<N> {
+impl<N: Ring> Matrix<N> {
     pub fn transpose(&self) -> Result<Self, RMatrixError> {
         let mut m = Matrix::zeros(self.shape.col, self.shape.row)?;
         for i in 1..=self.shape.row {
@@ -22,7 +22,7 @@ impl<N: Number> Matrix<N> {
         } else {
             let mut m = Self::zeros(self.shape.row, rhs.shape.col)?;
             for i in 0..self.shape.row * rhs.shape.col {
-                m.data[i] = self.data[i] + rhs.data[i];
+                m.data[i] = self.data[i].clone() + rhs.data[i].clone();
             }
             Ok(m)
         }
@@ -46,7 +46,7 @@ impl<N: Number> Matrix<N> {
     pub fn smul(&self, k: N) -> Result<Self, RMatrixError> {
         let mut m = Self::zeros(self.shape.row, self.shape.col)?;
         for i in 0..self.data.len() {
-            m.data[i] = self.data[i] * k;
+            m.data[i] = self.data[i].clone() * k.clone();
         }
         Ok(m)
     }
@@ -60,7 +60,7 @@ impl<N: Number> Matrix<N> {
         } else {
             let mut m = Self::zeros(self.shape.row, rhs.shape.col)?;
             for i in 0..self.shape.row * rhs.shape.col {
-                m.data[i] = self.data[i] - rhs.data[i];
+                m.data[i] = self.data[i].clone() - rhs.data[i].clone();
             }
             Ok(m)
         }
@@ -0,0 +1,460 @@
+//! self-describing binary format of matrices
+//!
+//! the layout of a file is
+//!
+//! | field        | size        | note                                     |
+//! | ------------ | ----------- | ---------------------------------------- |
+//! | magic        | 4           | `RMAT`                                   |
+//! | version      | 1           | currently `1`                            |
+//! | element type | 1           | [`BinaryElement::TYPE_CODE`]             |
+//! | endianness   | 1           | `0` for little-endian, `1` for big-endian |
+//! | flags        | 1           | bit `0` set if a checksum is appended    |
+//! | row, column  | 8 + 8       | unsigned integers                        |
+//! | tag length   | 4           | unsigned integer                         |
+//! | tag          | tag length  | utf-8                                    |
+//! | payload      | by element  | elements by row, see [`BinaryElement`]   |
+//! | checksum     | 4           | CRC-32 of the payload, if flagged        |
+//!
+//! all integers of the header and the payload follow the endianness field
+
+use std::io::{Read, Write};
+
+use crate::{
+    bigint::BigInt,
+    complex::Complex,
+    error::RMatrixError,
+    finite::{Gf, Gf2k},
+    matrix::{shape::MatrixShape, Matrix},
+    number::Ring,
+    rational::{big::BigRational, Rational},
+};
+
+/// magic string at the beginning of every binary matrix file
+const BINARY_MAGIC: &[u8; 4] = b"RMAT";
+
+/// version of the binary format
+const BINARY_VERSION: u8 = 1;
+
+/// flag bit of an appended checksum
+const FLAG_CHECKSUM: u8 = 0b1;
+
+/// elements written at once, and the initial capacity when reading the payload
+const CHUNK_ELEMENTS: usize = 4096;
+
+/// element types which can be stored in the binary format
+///
+/// every element is encoded by the integers or floating point numbers it consists of,
+/// in the endianness of the file
+///
+/// | type           | code | encoding                                        |
+/// | -------------- | ---- | ----------------------------------------------- |
+/// | `f64`          | 1    | 8 bytes                                         |
+/// | [`Complex`]    | 2    | real and imaginary part as `f64`                |
+/// | `f32`          | 3    | 4 bytes                                         |
+/// | [`Rational`]   | 4    | numerator and positive denominator as `i64`     |
+/// | [`BigInt`]     | 5    | sign byte, `u32` limb count, `u32` limbs from the least significant |
+/// | [`BigRational`] | 6   | numerator and positive denominator as [`BigInt`] |
+/// | [`Gf`]         | 7    | residue as `u64`, the modulus is not stored     |
+/// | [`Gf2k`]       | 8    | bits as `u64`, the polynomial is not stored     |
+pub trait BinaryElement: Ring {
+    /// code identifying the element type in the header
+    const TYPE_CODE: u8;
+
+    /// append the bytes of the element to `out`
+    fn write_bytes(&self, big_endian: bool, out: &mut Vec<u8>);
+
+    /// read one element, corrupt data gives [`RMatrixError::ParseFailed`]
+    fn read_element<R: Read>(reader: &mut R, big_endian: bool) -> Result<Self, RMatrixError>;
+}
+
+fn write_u32(v: u32, big_endian: bool, out: &mut Vec<u8>) {
+    out.extend_from_slice(&if big_endian {
+        v.to_be_bytes()
+    } else {
+        v.to_le_bytes()
+    });
+}
+
+fn write_u64(v: u64, big_endian: bool, out: &mut Vec<u8>) {
+    out.extend_from_slice(&if big_endian {
+        v.to_be_bytes()
+    } else {
+        v.to_le_bytes()
+    });
+}
+
+fn corrupt(what: &str) -> RMatrixError {
+    RMatrixError::ParseFailed(format!("binary element, {}", what))
+}
+
+impl BinaryElement for f64 {
+    const TYPE_CODE: u8 = 1;
+
+    fn write_bytes(&self, big_endian: bool, out: &mut Vec<u8>) {
+        write_u64(self.to_bits(), big_endian, out);
+    }
+
+    fn read_element<R: Read>(reader: &mut R, big_endian: bool) -> Result<Self, RMatrixError> {
+        Ok(f64::from_bits(read_u64(reader, big_endian)?))
+    }
+}
+
+impl BinaryElement for Complex {
+    const TYPE_CODE: u8 = 2;
+
+    fn write_bytes(&self, big_endian: bool, out: &mut Vec<u8>) {
+        self.re().write_bytes(big_endian, out);
+        self.im().write_bytes(big_endian, out);
+    }
+
+    fn read_element<R: Read>(reader: &mut R, big_endian: bool) -> Result<Self, RMatrixError> {
+        let re = f64::read_element(reader, big_endian)?;
+        Ok(Complex::new(re, f64::read_element(reader, big_endian)?))
+    }
+}
+
+impl BinaryElement for f32 {
+    const TYPE_CODE: u8 = 3;
+
+    fn write_bytes(&self, big_endian: bool, out: &mut Vec<u8>) {
+        write_u32(self.to_bits(), big_endian, out);
+    }
+
+    fn read_element<R: Read>(reader: &mut R, big_endian: bool) -> Result<Self, RMatrixError> {
+        Ok(f32::from_bits(read_u32(reader, big_endian)?))
+    }
+}
+
+impl BinaryElement for Rational {
+    const TYPE_CODE: u8 = 4;
+
+    fn write_bytes(&self, big_endian: bool, out: &mut Vec<u8>) {
+        write_u64(self.numer() as u64, big_endian, out);
+        write_u64(self.denom() as u64, big_endian, out);
+    }
+
+    fn read_element<R: Read>(reader: &mut R, big_endian: bool) -> Result<Self, RMatrixError> {
+        let num = read_u64(reader, big_endian)? as i64;
+        let den = read_u64(reader, big_endian)? as i64;
+        if den <= 0 {
+            return Err(corrupt("denominator is not positive"));
+        }
+        Ok(Rational::new(num, den))
+    }
+}
+
+impl BinaryElement for BigInt {
+    const TYPE_CODE: u8 = 5;
+
+    fn write_bytes(&self, big_endian: bool, out: &mut Vec<u8>) {
+        out.push(self.is_negative() as u8);
+        write_u32(self.mag().len() as u32, big_endian, out);
+        self.mag()
+            .iter()
+            .for_each(|&limb| write_u32(limb, big_endian, out));
+    }
+
+    fn read_element<R: Read>(reader: &mut R, big_endian: bool) -> Result<Self, RMatrixError> {
+        let mut sign = [0u8; 1];
+        reader.read_exact(&mut sign)?;
+        if sign[0] > 1 {
+            return Err(corrupt("sign is neither 0 nor 1"));
+        }
+        // the limb count is untrusted, so the limbs are collected as they are read
+        let mut mag = Vec::new();
+        for _ in 0..read_u32(reader, big_endian)? {
+            mag.push(read_u32(reader, big_endian)?);
+        }
+        Ok(BigInt::from_parts(sign[0] == 1, mag))
+    }
+}
+
+impl BinaryElement for BigRational {
+    const TYPE_CODE: u8 = 6;
+
+    fn write_bytes(&self, big_endian: bool, out: &mut Vec<u8>) {
+        self.numer().write_bytes(big_endian, out);
+        self.denom().write_bytes(big_endian, out);
+    }
+
+    fn read_element<R: Read>(reader: &mut R, big_endian: bool) -> Result<Self, RMatrixError> {
+        let num = BigInt::read_element(reader, big_endian)?;
+        let den = BigInt::read_element(reader, big_endian)?;
+        if den.signum() <= 0 {
+            return Err(corrupt("denominator is not positive"));
+        }
+        Ok(BigRational::new(num, den))
+    }
+}
+
+impl<const P: u64> BinaryElement for Gf<P> {
+    const TYPE_CODE: u8 = 7;
+
+    fn write_bytes(&self, big_endian: bool, out: &mut Vec<u8>) {
+        write_u64(self.value(), big_endian, out);
+    }
+
+    fn read_element<R: Read>(reader: &mut R, big_endian: bool) -> Result<Self, RMatrixError> {
+        let v = read_u64(reader, big_endian)?;
+        if v >= P {
+            return Err(corrupt(&format!("{} is not a residue modulo {}", v, P)));
+        }
+        Ok(Gf::new(v as i64))
+    }
+}
+
+impl<const POLY: u64> BinaryElement for Gf2k<POLY> {
+    const TYPE_CODE: u8 = 8;
+
+    fn write_bytes(&self, big_endian: bool, out: &mut Vec<u8>) {
+        write_u64(self.bits(), big_endian, out);
+    }
+
+    fn read_element<R: Read>(reader: &mut R, big_endian: bool) -> Result<Self, RMatrixError> {
+        let v = read_u64(reader, big_endian)?;
+        if v >> Self::K != 0 {
+            return Err(corrupt(&format!(
+                "{:#x} is not reduced modulo {:#x}",
+                v, POLY
+            )));
+        }
+        Ok(Gf2k::new(v))
+    }
+}
+
+/// a reader which updates a checksum with every byte read
+struct ChecksumReader<'a, R> {
+    inner: &'a mut R,
+    crc: Crc32,
+}
+
+impl<R: Read> Read for ChecksumReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.crc.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// options of writing the binary format
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BinaryOptions {
+    /// write integers and elements in big-endian
+    pub big_endian: bool,
+    /// append a CRC-32 checksum of the payload
+    pub checksum: bool,
+}
+
+impl Default for BinaryOptions {
+    fn default() -> Self {
+        Self {
+            big_endian: false,
+            checksum: true,
+        }
+    }
+}
+
+/// lookup table of CRC-32 (IEEE 802.3)
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xEDB8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+};
+
+/// running CRC-32 over the payload
+#[derive(Debug, Clone, Copy)]
+struct Crc32(u32);
+
+impl Crc32 {
+    fn new() -> Self {
+        Crc32(0xFFFF_FFFF)
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = CRC32_TABLE[((self.0 ^ b as u32) & 0xFF) as usize] ^ (self.0 >> 8);
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        self.0 ^ 0xFFFF_FFFF
+    }
+}
+
+fn read_u32<R: Read>(reader: &mut R, big_endian: bool) -> Result<u32, RMatrixError> {
+    let mut b = [0u8; 4];
+    reader.read_exact(&mut b)?;
+    Ok(if big_endian {
+        u32::from_be_bytes(b)
+    } else {
+        u32::from_le_bytes(b)
+    })
+}
+
+fn read_u64<R: Read>(reader: &mut R, big_endian: bool) -> Result<u64, RMatrixError> {
+    let mut b = [0u8; 8];
+    reader.read_exact(&mut b)?;
+    Ok(if big_endian {
+        u64::from_be_bytes(b)
+    } else {
+        u64::from_le_bytes(b)
+    })
+}
+
+impl<N: BinaryElement> Matrix<N> {
+    /// save a matrix to a file in the binary format
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), RMatrixError> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// load a matrix from a file in the binary format
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self, RMatrixError> {
+        let mut reader = std::io::BufReader::new(std::fs::File::open(path)?);
+        Self::read_from(&mut reader)
+    }
+
+    /// write a matrix in the binary format with default options
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::complex::Complex;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(1, 2, vec![1.0, 2.0])?;
+    /// let mut buffer = Vec::new();
+    /// m.write_to(&mut buffer)?;
+    /// assert_eq!(Matrix::<f64>::read_from(&mut buffer.as_slice())?, m);
+    /// assert!(matches!(
+    ///     Matrix::<Complex>::read_from(&mut buffer.as_slice()),
+    ///     Err(RMatrixError::ElementTypeMismatch(2, 1))
+    /// ));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), RMatrixError> {
+        self.write_to_with(writer, BinaryOptions::default())
+    }
+
+    /// write a matrix in the binary format
+    pub fn write_to_with<W: Write>(
+        &self,
+        writer: &mut W,
+        options: BinaryOptions,
+    ) -> Result<(), RMatrixError> {
+        let be = options.big_endian;
+        let mut header = BINARY_MAGIC.to_vec();
+        header.extend_from_slice(&[
+            BINARY_VERSION,
+            N::TYPE_CODE,
+            be as u8,
+            if options.checksum { FLAG_CHECKSUM } else { 0 },
+        ]);
+        for v in [self.shape.row as u64, self.shape.col as u64] {
+            header.extend_from_slice(&if be { v.to_be_bytes() } else { v.to_le_bytes() });
+        }
+        let tag_len = self.tag.len() as u32;
+        header.extend_from_slice(&if be {
+            tag_len.to_be_bytes()
+        } else {
+            tag_len.to_le_bytes()
+        });
+        header.extend_from_slice(self.tag.as_bytes());
+        writer.write_all(&header)?;
+
+        let mut crc = Crc32::new();
+        let mut buffer = Vec::new();
+        for chunk in self.data.chunks(CHUNK_ELEMENTS) {
+            buffer.clear();
+            chunk.iter().for_each(|v| v.write_bytes(be, &mut buffer));
+            crc.update(&buffer);
+            writer.write_all(&buffer)?;
+        }
+        if options.checksum {
+            let sum = crc.finish();
+            writer.write_all(&if be {
+                sum.to_be_bytes()
+            } else {
+                sum.to_le_bytes()
+            })?;
+        }
+        Ok(())
+    }
+
+    /// read a matrix in the binary format
+    ///
+    /// the element type of the stream must be `N`,
+    /// otherwise [`RMatrixError::ElementTypeMismatch`] is returned
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, RMatrixError> {
+        let mut head = [0u8; 8];
+        reader.read_exact(&mut head)?;
+        if &head[..4] != BINARY_MAGIC {
+            return Err(RMatrixError::ParseFailed("binary magic string".to_owned()));
+        }
+        if head[4] != BINARY_VERSION {
+            return Err(RMatrixError::FormatUnsupported(format!(
+                "binary version {}",
+                head[4]
+            )));
+        }
+        if head[5] != N::TYPE_CODE {
+            return Err(RMatrixError::ElementTypeMismatch(N::TYPE_CODE, head[5]));
+        }
+        let be = match head[6] {
+            0 => false,
+            1 => true,
+            v => return Err(RMatrixError::FormatUnsupported(format!("endianness {}", v))),
+        };
+        let has_checksum = head[7] & FLAG_CHECKSUM != 0;
+
+        let row = read_u64(reader, be)? as usize;
+        let col = read_u64(reader, be)? as usize;
+        let shape = MatrixShape::new(row, col)?;
+        let len = row
+            .checked_mul(col)
+            .ok_or(RMatrixError::ShapeUnreasonable)?;
+        // lengths in the header are untrusted, so buffers grow only with the data read
+        let tag_len = read_u32(reader, be)? as u64;
+        let mut tag = Vec::new();
+        (&mut *reader).take(tag_len).read_to_end(&mut tag)?;
+        if tag.len() as u64 != tag_len {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        let tag = String::from_utf8(tag).map_err(|e| RMatrixError::ParseFailed(e.to_string()))?;
+
+        let mut payload = ChecksumReader {
+            inner: &mut *reader,
+            crc: Crc32::new(),
+        };
+        let mut data = Vec::with_capacity(len.min(CHUNK_ELEMENTS));
+        while data.len() < len {
+            data.push(N::read_element(&mut payload, be)?);
+        }
+        let crc = payload.crc;
+        if has_checksum {
+            let expected = read_u32(reader, be)?;
+            if expected != crc.finish() {
+                return Err(RMatrixError::ChecksumMismatch(expected, crc.finish()));
+            }
+        }
+        Ok(Matrix { data, shape, tag })
+    }
+}
@@ -0,0 +1,464 @@
+//! Jordan and rational canonical forms of exact matrices
+
+use crate::{
+    bigint::BigInt,
+    error::RMatrixError,
+    matrix::Matrix,
+    number::Ring,
+    polynomial::Polynomial,
+    rational::{big::BigRational, Rational},
+};
+
+/// a Jordan block, `eigenvalue` on the diagonal and ones on the superdiagonal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JordanBlock {
+    pub eigenvalue: Rational,
+    pub size: usize,
+}
+
+/// Jordan form with `A = P * J * P^(-1)`
+///
+/// the blocks are sorted by ascending eigenvalue and then by descending size,
+/// the columns of `P` are Jordan chains, each starting with an eigenvector
+#[derive(Debug, Clone)]
+pub struct JordanForm {
+    pub p: Matrix<Rational>,
+    pub j: Matrix<Rational>,
+    pub blocks: Vec<JordanBlock>,
+}
+
+/// rational canonical form with `A = P * F * P^(-1)`
+///
+/// `F` is block diagonal with the companion matrices of the invariant factors,
+/// every factor divides the next one, the last one is the minimal polynomial
+/// and their product is the characteristic polynomial
+#[derive(Debug, Clone)]
+pub struct FrobeniusForm {
+    pub p: Matrix<Rational>,
+    pub f: Matrix<Rational>,
+    /// monic invariant factors of degree at least one
+    pub invariant_factors: Vec<Polynomial<Rational>>,
+}
+
+/// a vector in the coordinates of the matrix
+type Vector = Vec<Rational>;
+
+/// roots with their multiplicities
+type Roots = Vec<(Rational, usize)>;
+
+/// a square matrix of polynomials, indexed from 0
+type PolynomialMatrix = Vec<Vec<Polynomial<Rational>>>;
+
+/// trial divisions allowed when searching the divisors of a coefficient
+const ROOT_SEARCH_LIMIT: u64 = 1 << 24;
+
+/// positive divisors of `n` up to `limit` in ascending order, `n` must not be zero
+///
+/// the trial division stops at the smaller of `limit` and the square root of `n`
+fn divisors(n: &BigInt, limit: u64) -> Result<Vec<u64>, RMatrixError> {
+    let n = n.abs();
+    let small = n.to_i64().map(|v| v as u64);
+    let end = limit.min(small.map_or(u64::MAX, |v| v.isqrt()));
+    if end > ROOT_SEARCH_LIMIT {
+        return Err(RMatrixError::FunctionUndefined(format!(
+            "the divisors of {} are too many to search for rational roots",
+            n
+        )));
+    }
+    let mut found = Vec::new();
+    for d in 1..=end {
+        let divides = match small {
+            Some(v) => v.is_multiple_of(d),
+            None => n.div_rem(&BigInt::from(d)).1.is_zero(),
+        };
+        if divides {
+            found.push(d);
+            if let Some(co) = small.map(|v| v / d).filter(|&co| co > end && co <= limit) {
+                found.push(co);
+            }
+        }
+    }
+    found.sort_unstable();
+    Ok(found)
+}
+
+/// rational roots with multiplicities in ascending order and the factor without rational roots
+///
+/// a root `p / q` of the polynomial with integer coefficients `a_i` has `p | a_0` and `q | a_n`,
+/// only the candidates within Fujiwara's bound are tried, exactly with big integers
+fn rational_roots(
+    p: &Polynomial<Rational>,
+) -> Result<(Roots, Polynomial<BigRational>), RMatrixError> {
+    let mut roots = Vec::new();
+    let zeros = p.coeffs().iter().take_while(|c| c.is_zero()).count();
+    if zeros > 0 {
+        roots.push((Rational::default(), zeros));
+    }
+    let lcm = p.coeffs().iter().fold(BigInt::one(), |l, c| {
+        let d = BigInt::from(c.denom());
+        l.clone() / l.gcd(&d) * d
+    });
+    let mut rest = Polynomial::new(
+        p.coeffs()[zeros..]
+            .iter()
+            .map(|c| {
+                BigRational::from(BigInt::from(c.numer()) * (lcm.clone() / BigInt::from(c.denom())))
+            })
+            .collect(),
+    );
+    let n = match rest.degree() {
+        Some(n) if n > 0 => n,
+        _ => return Ok((roots, rest)),
+    };
+    let (first, last) = (
+        rest.coeff(0).numer().clone(),
+        rest.leading().numer().clone(),
+    );
+    // every root has |r| <= 2 max |a_(n-k) / a_n|^(1/k), with a_0 / 2 for k = n
+    let bound = 2.0
+        * (1..=n)
+            .map(|k| {
+                let ratio = rest.coeff(n - k).numer().to_f64() / last.to_f64();
+                let ratio = if k == n { ratio / 2.0 } else { ratio };
+                if ratio.is_nan() {
+                    f64::INFINITY
+                } else {
+                    ratio.abs().powf(1.0 / k as f64)
+                }
+            })
+            .fold(0.0, f64::max);
+    let mut candidates = Vec::new();
+    for q in divisors(&last, u64::MAX)? {
+        // a margin for the rounding of the bound, the cast saturates
+        let limit = (bound * q as f64 * (1.0 + 1e-9)).ceil() as u64 + 1;
+        for p in divisors(&first, limit)? {
+            // divisors are below the search limit or divide an `i64`, so the casts are exact
+            for s in [1, -1] {
+                candidates.push(Rational::new(s * p as i64, q as i64));
+            }
+        }
+    }
+    candidates.sort();
+    candidates.dedup();
+    for r in candidates {
+        let x = BigRational::new(BigInt::from(r.numer()), BigInt::from(r.denom()));
+        let factor = Polynomial::new(vec![-x.clone(), BigRational::one()]);
+        let mut multiplicity = 0;
+        while rest.degree().is_some_and(|d| d > 0) && rest.eval(&x).is_zero() {
+            rest = rest.div_rem(&factor).map_or(rest, |(q, _)| q);
+            multiplicity += 1;
+        }
+        if multiplicity > 0 {
+            roots.push((r, multiplicity));
+        }
+    }
+    roots.sort();
+    Ok((roots, rest))
+}
+
+/// the rank of a list of vectors
+fn rank(vectors: &[Vector]) -> Result<usize, RMatrixError> {
+    match vectors.first() {
+        None => Ok(0),
+        Some(v) => Matrix::from_vec(vectors.len(), v.len(), vectors.concat())?.rank(),
+    }
+}
+
+/// the product of a matrix and a vector
+fn apply(m: &Matrix<Rational>, v: &[Rational]) -> Result<Vector, RMatrixError> {
+    Ok(m.times(&Matrix::from_vec(v.len(), 1, v.to_vec())?)?.data)
+}
+
+/// the matrix with the given columns
+fn from_columns(n: usize, columns: &[Vector]) -> Result<Matrix<Rational>, RMatrixError> {
+    Matrix::from_vec(columns.len(), n, columns.concat())?.transpose()
+}
+
+/// Jordan chains of `N = A - λ I` for an eigenvalue of algebraic multiplicity `m`,
+/// as the block sizes in descending order and the chains
+fn jordan_chains(
+    a: &Matrix<Rational>,
+    eigenvalue: Rational,
+    m: usize,
+) -> Result<Vec<(usize, Vec<Vector>)>, RMatrixError> {
+    let n = a.shape.row;
+    let nil = a.subtract(&Matrix::<Rational>::eyes(n, n)?.smul(eigenvalue)?)?;
+    // bases of the kernels of N^k until the generalized eigenspace is reached
+    let mut kernels: Vec<Vec<Vector>> = vec![Vec::new()];
+    let mut power = Matrix::<Rational>::eyes(n, n)?;
+    while kernels.last().map_or(0, |k| k.len()) < m {
+        power = power.times(&nil)?;
+        let basis = match power.null_space()? {
+            Some(b) => (1..=b.shape.col)
+                .map(|j| b.get_col(j))
+                .collect::<Result<_, _>>()?,
+            None => Vec::new(),
+        };
+        kernels.push(basis);
+    }
+    // from the longest chains down, extend the vectors forced by the longer chains
+    // and the smaller kernel to a basis of the kernel on every level
+    let mut chains = Vec::new();
+    let mut forced: Vec<Vector> = Vec::new();
+    for k in (1..kernels.len()).rev() {
+        let mut span = kernels[k - 1].clone();
+        span.extend(forced.iter().cloned());
+        let mut r = rank(&span)?;
+        for v in &kernels[k] {
+            span.push(v.clone());
+            if rank(&span)? > r {
+                r += 1;
+                forced.push(v.clone());
+                let mut chain = vec![v.clone()];
+                for _ in 1..k {
+                    let next = apply(&nil, &chain[0])?;
+                    chain.insert(0, next);
+                }
+                chains.push((k, chain));
+            } else {
+                span.pop();
+            }
+        }
+        forced = forced
+            .iter()
+            .map(|v| apply(&nil, v))
+            .collect::<Result<_, _>>()?;
+    }
+    Ok(chains)
+}
+
+/// the largest numerator or denominator of the coefficients
+fn height(p: &Polynomial<Rational>) -> u64 {
+    p.coeffs()
+        .iter()
+        .map(|c| c.numer().unsigned_abs().max(c.denom() as u64))
+        .max()
+        .unwrap_or(0)
+}
+
+/// subtract `q` times the row `from` from the row `to`
+fn subtract_row(m: &mut PolynomialMatrix, to: usize, from: usize, q: &Polynomial<Rational>) {
+    let source = m[from].clone();
+    for (x, y) in m[to].iter_mut().zip(source) {
+        *x = x.clone() - q.clone() * y;
+    }
+}
+
+/// Smith form of `x I - A` over the rational polynomials,
+/// as the monic diagonal and the inverse `U` of the row transform
+fn characteristic_smith(
+    a: &Matrix<Rational>,
+) -> Result<(Vec<Polynomial<Rational>>, PolynomialMatrix), RMatrixError> {
+    let n = a.shape.row;
+    let mut m = (1..=n)
+        .map(|i| {
+            (1..=n)
+                .map(|j| Ok(Polynomial::constant(-a.get(i, j)?)))
+                .collect()
+        })
+        .collect::<Result<PolynomialMatrix, RMatrixError>>()?;
+    let mut u = vec![vec![Polynomial::zero(); n]; n];
+    for i in 0..n {
+        m[i][i] = m[i][i].clone() + Polynomial::x();
+        u[i][i] = Polynomial::constant(Rational::one());
+    }
+    // quotient and remainder by the pivot d, which is not zero
+    let rem = |p: &Polynomial<Rational>, d: &Polynomial<Rational>| p.div_rem(d).unwrap_or_default();
+    for t in 0..n {
+        loop {
+            // among the pivots of least degree the smallest coefficients limit their growth
+            let pivot = (t..n)
+                .flat_map(|i| (t..n).map(move |j| (i, j)))
+                .filter(|&(i, j)| !m[i][j].is_zero())
+                .min_by_key(|&(i, j)| (m[i][j].degree(), height(&m[i][j])));
+            let Some((pi, pj)) = pivot else { break };
+            // the row transform is tracked by the inverse column operations on U
+            m.swap(t, pi);
+            u.iter_mut().for_each(|row| row.swap(t, pi));
+            m.iter_mut().for_each(|row| row.swap(t, pj));
+            let mut clean = true;
+            for i in (t + 1)..n {
+                let (q, r) = rem(&m[i][t], &m[t][t]);
+                if !q.is_zero() {
+                    subtract_row(&mut m, i, t, &q);
+                    for row in u.iter_mut() {
+                        row[t] = row[t].clone() + q.clone() * row[i].clone();
+                    }
+                }
+                clean = clean && r.is_zero();
+            }
+            for j in (t + 1)..n {
+                let (q, r) = rem(&m[t][j], &m[t][t]);
+                if !q.is_zero() {
+                    for row in m.iter_mut().skip(t) {
+                        row[j] = row[j].clone() - q.clone() * row[t].clone();
+                    }
+                }
+                clean = clean && r.is_zero();
+            }
+            if !clean {
+                continue;
+            }
+            // the pivot must divide the rest, otherwise a row is added to bring in a remainder
+            let failing =
+                ((t + 1)..n).find(|&i| ((t + 1)..n).any(|j| !rem(&m[i][j], &m[t][t]).1.is_zero()));
+            match failing {
+                Some(i) => {
+                    subtract_row(&mut m, t, i, &Polynomial::constant(Rational::neg_one()));
+                    for row in u.iter_mut() {
+                        row[i] = row[i].clone() - row[t].clone();
+                    }
+                }
+                None => break,
+            }
+        }
+        let lead = m[t][t].leading();
+        if !lead.is_zero() {
+            m[t][t] = m[t][t].scale(Rational::one() / lead);
+            for row in u.iter_mut() {
+                row[t] = row[t].scale(lead);
+            }
+        }
+    }
+    Ok(((0..n).map(|t| m[t][t].clone()).collect(), u))
+}
+
+impl Matrix<Rational> {
+    /// Jordan form `(P, J)` with `A = P * J * P^(-1)` and the block structure
+    ///
+    /// the eigenvalues are the rational roots of the characteristic polynomial,
+    /// an error is returned if some eigenvalue is not rational,
+    /// use [`Matrix::frobenius_form`] which always exists instead
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::{canonical::JordanBlock, Matrix};
+    /// # use rmatrix_ks::rational::Rational;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let a = Matrix::from_vec(
+    ///     4,
+    ///     4,
+    ///     [5, 4, 2, 1, 0, 1, -1, -1, -1, -1, 3, 0, 1, 1, -1, 2].map(Rational::from).to_vec(),
+    /// )?;
+    /// let jordan = a.jordan_form()?;
+    /// let block = |v, size| JordanBlock { eigenvalue: Rational::from(v), size };
+    /// assert_eq!(jordan.blocks, vec![block(1, 1), block(2, 1), block(4, 2)]);
+    /// assert_eq!(jordan.j.get(3, 4)?, Rational::from(1));
+    /// assert_eq!(a.times(&jordan.p)?, jordan.p.times(&jordan.j)?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn jordan_form(&self) -> Result<JordanForm, RMatrixError> {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        let n = self.shape.row;
+        let (roots, rest) = rational_roots(&self.char_poly()?)?;
+        if rest.degree().is_some_and(|d| d > 0) {
+            return Err(RMatrixError::FunctionUndefined(format!(
+                "eigenvalues are the roots of {} which are not rational",
+                rest.monic().unwrap_or(rest)
+            )));
+        }
+        let mut columns = Vec::with_capacity(n);
+        let mut blocks = Vec::new();
+        for (eigenvalue, m) in roots {
+            for (size, chain) in jordan_chains(self, eigenvalue, m)? {
+                columns.extend(chain);
+                blocks.push(JordanBlock { eigenvalue, size });
+            }
+        }
+        let mut j = Matrix::zeros(n, n)?;
+        let mut start = 1;
+        for block in &blocks {
+            for k in start..(start + block.size) {
+                j.set(block.eigenvalue, k, k)?;
+                if k > start {
+                    j.set(Rational::one(), k - 1, k)?;
+                }
+            }
+            start += block.size;
+        }
+        Ok(JordanForm {
+            p: from_columns(n, &columns)?,
+            j,
+            blocks,
+        })
+    }
+
+    /// rational canonical form `(P, F)` with `A = P * F * P^(-1)` and the invariant factors
+    ///
+    /// the invariant factors are the diagonal of the Smith form of `x I - A`,
+    /// the columns of `P` are `v, A v, A^2 v, ...` for a generator `v` of every cyclic subspace
+    ///
+    /// # Panics
+    ///
+    /// the elimination on polynomials panics like [`Rational`] arithmetic
+    /// if an intermediate coefficient does not fit into `i64`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::rational::Rational;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// // a rotation by a quarter turn and a scaling by 2
+    /// let a = Matrix::from_vec(3, 3, [0, -1, 0, 1, 0, 0, 0, 0, 2].map(Rational::from).to_vec())?;
+    /// assert!(a.jordan_form().is_err());
+    /// let frobenius = a.frobenius_form()?;
+    /// assert_eq!(frobenius.invariant_factors.len(), 1);
+    /// assert_eq!(frobenius.invariant_factors[0].to_string(), "x^3 - 2x^2 + x - 2");
+    /// assert_eq!(a.times(&frobenius.p)?, frobenius.p.times(&frobenius.f)?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn frobenius_form(&self) -> Result<FrobeniusForm, RMatrixError> {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        let n = self.shape.row;
+        let (diagonal, u) = characteristic_smith(self)?;
+        let mut columns = Vec::with_capacity(n);
+        let mut f = Matrix::zeros(n, n)?;
+        let mut invariant_factors = Vec::new();
+        for (t, factor) in diagonal.into_iter().enumerate() {
+            let degree = factor.degree().unwrap_or(0);
+            if degree == 0 {
+                continue;
+            }
+            // the column of U as a polynomial in A applied to the unit vectors
+            let top = u
+                .iter()
+                .filter_map(|row| row[t].degree())
+                .max()
+                .unwrap_or(0);
+            let mut v = vec![Rational::default(); n];
+            for k in (0..=top).rev() {
+                v = apply(self, &v)?;
+                v.iter_mut()
+                    .zip(&u)
+                    .for_each(|(x, row)| *x = *x + row[t].coeff(k));
+            }
+            let start = columns.len();
+            for _ in 0..degree {
+                let next = apply(self, &v)?;
+                columns.push(std::mem::replace(&mut v, next));
+            }
+            let block = Matrix::companion(factor.coeffs())?;
+            for i in 1..=degree {
+                for k in 1..=degree {
+                    f.set(block.get(i, k)?, start + i, start + k)?;
+                }
+            }
+            invariant_factors.push(factor);
+        }
+        Ok(FrobeniusForm {
+            p: from_columns(n, &columns)?,
+            f,
+            invariant_factors,
+        })
+    }
+}
@@ -0,0 +1,65 @@
+//! echelon form of matrices
+
+use crate::{error::RMatrixError, matrix::Matrix, number::Number};
+
+/// result of a row elimination
+///
+/// columns are counted from 1 like [`Matrix::get`]
+#[derive(Debug, Clone)]
+pub struct Echelon<N> {
+    /// the row echelon form, reduced if from [`Matrix::row_reduce`]
+    pub matrix: Matrix<N>,
+    /// `E` with `E * A = matrix`
+    pub transform: Matrix<N>,
+    /// columns of the pivots, the pivot of row `i` is in column `pivots[i - 1]`
+    pub pivots: Vec<usize>,
+    /// columns without a pivot
+    pub free: Vec<usize>,
+    pub rank: usize,
+    /// `-1` if an odd number of rows are exchanged, otherwise `1`
+    pub sign: N,
+}
+
+impl<N: Number> Echelon<N> {
+    /// row of the pivot in column `col`
+    pub fn pivot_row(&self, col: usize) -> Option<usize> {
+        self.pivots.iter().position(|&p| p == col).map(|i| i + 1)
+    }
+
+    /// whether the columns of the matrix are linearly independent
+    pub fn is_full_column_rank(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    /// solve `A * X = B` by back substitution
+    ///
+    /// free variables are set to zero,
+    /// [`RMatrixError::LinearEquationsNoSolution`] is returned if the system is inconsistent
+    pub fn particular_solution(&self, b: &Matrix<N>) -> Result<Matrix<N>, RMatrixError> {
+        let (r, c) = self.matrix.dimensions();
+        if b.shape.row != r {
+            return Err(RMatrixError::ShapeInconsistent(
+                self.matrix.dimensions(),
+                b.dimensions(),
+            ));
+        }
+        let eb = self.transform.times(b)?;
+        for i in (self.rank + 1)..=r {
+            if eb.get_row(i)?.iter().any(|v| !v.is_zero()) {
+                return Err(RMatrixError::LinearEquationsNoSolution((r, c)));
+            }
+        }
+        let mut x = Matrix::zeros(c, b.shape.col)?;
+        for (i, &p) in self.pivots.iter().enumerate().rev() {
+            let pivot = self.matrix.get(i + 1, p)?;
+            for j in 1..=b.shape.col {
+                let mut v = eb.get(i + 1, j)?;
+                for &q in self.pivots[i + 1..].iter() {
+                    v = v - self.matrix.get(i + 1, q)? * x.get(q, j)?;
+                }
+                x.set(v / pivot.clone(), p, j)?;
+            }
+        }
+        Ok(x)
+    }
+}
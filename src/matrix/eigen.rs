@@ -0,0 +1,62 @@
+//! eigenvalues of real matrices
+
+use crate::{complex::Complex, error::RMatrixError, matrix::Matrix};
+
+impl Matrix<f64> {
+    /// eigenvalues of a square matrix
+    ///
+    /// they are the diagonal of the complex [`schur`](Matrix::schur) form,
+    /// which rounding leaves only approximately symmetric to the real axis,
+    /// so an eigenvalue and the nearest one to its conjugate are made an exact conjugate pair
+    /// if they are closer than it is to the real axis, otherwise it is made real,
+    /// complex conjugate pairs are adjacent with the positive imaginary part last
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// // rotation by a right angle
+    /// let m = Matrix::from_vec(2, 2, vec![0.0, -1.0, 1.0, 0.0])?;
+    /// let ev = m.eigenvalues()?;
+    /// assert!((ev[0].im() + 1.0).abs() < 1e-12 && (ev[1].im() - 1.0).abs() < 1e-12);
+    /// assert_eq!(ev[0].re(), ev[1].re());
+    /// let ev = Matrix::from_vec(2, 2, vec![2.0, 1.0, 1.0, 2.0])?.eigenvalues()?;
+    /// assert!(ev.iter().all(|v| v.im() == 0.0));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn eigenvalues(&self) -> Result<Vec<Complex>, RMatrixError> {
+        let (_, t) = self.to_complex().schur()?;
+        let n = self.shape.row;
+        let diagonal = (1..=n)
+            .map(|i| t.get(i, i))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut used = vec![false; n];
+        let mut ev = Vec::with_capacity(n);
+        for i in 0..n {
+            if used[i] {
+                continue;
+            }
+            used[i] = true;
+            let u = diagonal[i];
+            let partner = (0..n)
+                .filter(|&j| !used[j] && diagonal[j].im() * u.im() < 0.0)
+                .map(|j| (j, (diagonal[j] - u.conj()).norm()))
+                .filter(|&(_, d)| d <= u.im().abs())
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            match partner {
+                Some((j, _)) => {
+                    used[j] = true;
+                    let re = (u.re() + diagonal[j].re()) / 2.0;
+                    let im = (u.im().abs() + diagonal[j].im().abs()) / 2.0;
+                    ev.push(Complex::new(re, -im));
+                    ev.push(Complex::new(re, im));
+                }
+                None => ev.push(Complex::new(u.re(), 0.0)),
+            }
+        }
+        Ok(ev)
+    }
+}
@@ -0,0 +1,815 @@
+//! functions of square matrices
+//!
+//! norms are 1-norms, the largest column sum of the moduli
+
+use crate::{complex::Complex, error::RMatrixError, matrix::Matrix, number::Number};
+
+/// unit roundoff of double precision
+const UNIT_ROUNDOFF: f64 = f64::EPSILON / 2.0;
+
+/// maximum iterations of the square root and the square roots taken by the logarithm
+const MAX_ITERATIONS: usize = 100;
+
+/// largest norms for which the Padé approximants of degree 3, 5, 7 and 9 are accurate
+const PADE_THETA: [(usize, f64); 4] = [
+    (3, 1.495585217958292e-2),
+    (5, 2.53939833006323e-1),
+    (7, 9.504178996162932e-1),
+    (9, 2.097847961257068e0),
+];
+
+/// largest norm for which the Padé approximant of degree 13 is accurate
+const PADE_THETA_13: f64 = 5.371920351148152e0;
+
+/// coefficients of the Padé approximants of the exponential
+fn pade_coefficients(m: usize) -> &'static [f64] {
+    match m {
+        3 => &[120.0, 60.0, 12.0, 1.0],
+        5 => &[30240.0, 15120.0, 3360.0, 420.0, 30.0, 1.0],
+        7 => &[
+            17297280.0, 8648640.0, 1995840.0, 277200.0, 25200.0, 1512.0, 56.0, 1.0,
+        ],
+        9 => &[
+            17643225600.0,
+            8821612800.0,
+            2075673600.0,
+            302702400.0,
+            30270240.0,
+            2162160.0,
+            110880.0,
+            3960.0,
+            90.0,
+            1.0,
+        ],
+        _ => &[
+            64764752532480000.0,
+            32382376266240000.0,
+            7771770303897600.0,
+            1187353796428800.0,
+            129060195264000.0,
+            10559470521600.0,
+            670442572800.0,
+            33522128640.0,
+            1323241920.0,
+            40840800.0,
+            960960.0,
+            16380.0,
+            182.0,
+            1.0,
+        ],
+    }
+}
+
+/// nodes and weights of the `m` point Gauss–Legendre rule on `[0, 1]`
+fn gauss_legendre(m: usize) -> Vec<(f64, f64)> {
+    (1..=m)
+        .map(|i| {
+            // newton iteration on the legendre polynomial from the Chebyshev guess
+            let mut x = (std::f64::consts::PI * (i as f64 - 0.25) / (m as f64 + 0.5)).cos();
+            let mut dp = 1.0;
+            for _ in 0..100 {
+                let (mut p0, mut p1) = (1.0, x);
+                for k in 2..=m {
+                    let k = k as f64;
+                    (p0, p1) = (p1, ((2.0 * k - 1.0) * x * p1 - (k - 1.0) * p0) / k);
+                }
+                dp = m as f64 * (x * p1 - p0) / (x * x - 1.0);
+                let dx = p1 / dp;
+                x -= dx;
+                if dx.abs() < 1e-16 {
+                    break;
+                }
+            }
+            ((1.0 - x) / 2.0, 1.0 / ((1.0 - x * x) * dp * dp))
+        })
+        .collect()
+}
+
+/// element types which matrix functions are defined for
+pub trait FunmElement: Number {
+    fn from_f64(v: f64) -> Self;
+
+    /// the absolute value
+    fn modulus(&self) -> f64;
+
+    /// check that the principal value of `name` exists in the element type,
+    /// which needs no eigenvalue on the negative real axis and none at zero unless `zero` is set
+    fn check_principal(m: &Matrix<Self>, name: &str, zero: bool) -> Result<(), RMatrixError>;
+
+    fn to_complex(&self) -> Complex;
+
+    /// convert a complex result back, real types reject non-negligible imaginary parts
+    fn from_complex(m: Matrix<Complex>) -> Result<Matrix<Self>, RMatrixError>;
+}
+
+impl FunmElement for f64 {
+    fn from_f64(v: f64) -> Self {
+        v
+    }
+
+    fn modulus(&self) -> f64 {
+        self.abs()
+    }
+
+    fn check_principal(m: &Matrix<Self>, name: &str, zero: bool) -> Result<(), RMatrixError> {
+        let scale = m.norm_1().max(f64::MIN_POSITIVE);
+        // the eigenvalues are only accurate to the backward error of the Schur form
+        let tol = m.shape.row as f64 * UNIT_ROUNDOFF * scale;
+        match m
+            .eigenvalues()?
+            .into_iter()
+            .find(|v| (v.im() / scale).is_zero() && (v.re() < -tol || (!zero && v.re() <= tol)))
+        {
+            Some(v) => Err(RMatrixError::FunctionUndefined(format!(
+                "{} has no real principal value with the eigenvalue {:.3}",
+                name, v
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn to_complex(&self) -> Complex {
+        Complex::from(*self)
+    }
+
+    fn from_complex(m: Matrix<Complex>) -> Result<Matrix<Self>, RMatrixError> {
+        let scale = m.norm_1().max(f64::MIN_POSITIVE);
+        match m.data.iter().find(|v| !(v.im() / scale).is_zero()) {
+            Some(v) => Err(RMatrixError::FunctionUndefined(format!(
+                "the function of the real matrix has the complex element {:.3}",
+                v
+            ))),
+            None => Matrix::from_vec(
+                m.shape.row,
+                m.shape.col,
+                m.data.iter().map(|v| v.re()).collect(),
+            ),
+        }
+    }
+}
+
+impl FunmElement for Complex {
+    fn from_f64(v: f64) -> Self {
+        Complex::from(v)
+    }
+
+    fn modulus(&self) -> f64 {
+        self.norm()
+    }
+
+    /// eigenvalues on the negative real axis only choose a branch,
+    /// a singular matrix makes the logarithm fail with [`RMatrixError::MatrixSingular`]
+    fn check_principal(_: &Matrix<Self>, _: &str, _: bool) -> Result<(), RMatrixError> {
+        Ok(())
+    }
+
+    fn to_complex(&self) -> Complex {
+        *self
+    }
+
+    fn from_complex(m: Matrix<Complex>) -> Result<Matrix<Self>, RMatrixError> {
+        Ok(m)
+    }
+}
+
+impl<N: FunmElement> Matrix<N> {
+    /// the 1-norm, the largest column sum of the moduli
+    pub fn norm_1(&self) -> f64 {
+        (0..self.shape.col)
+            .map(|j| {
+                (0..self.shape.row)
+                    .map(|i| self.data[i * self.shape.col + j].modulus())
+                    .sum::<f64>()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// `||self - other|| / ||other||`
+    fn relative_distance(&self, other: &Self) -> Result<f64, RMatrixError> {
+        let d = self.subtract(other)?.norm_1();
+        let n = other.norm_1();
+        Ok(if n == 0.0 { d } else { d / n })
+    }
+
+    fn scale(&self, k: f64) -> Result<Self, RMatrixError> {
+        self.smul(N::from_f64(k))
+    }
+
+    /// LU decomposition with partial pivoting, `L` below the diagonal and `U` packed
+    /// into one matrix and the row of `A` for every row of `L U`
+    ///
+    /// a pivot not above `n u ||A||` is taken as zero, so the test is relative to the norm
+    fn lu(&self) -> Result<(Self, Vec<usize>), RMatrixError> {
+        let n = self.shape.row;
+        let tol = n as f64 * UNIT_ROUNDOFF * self.norm_1();
+        let mut a = self.data.clone();
+        let mut rows = (0..n).collect::<Vec<_>>();
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&i, &j| a[i * n + k].modulus().total_cmp(&a[j * n + k].modulus()))
+                .unwrap_or(k);
+            // also rejects a NaN pivot
+            if a[p * n + k].modulus().partial_cmp(&tol) != Some(std::cmp::Ordering::Greater) {
+                return Err(RMatrixError::MatrixSingular);
+            }
+            if p != k {
+                for j in 0..n {
+                    a.swap(p * n + j, k * n + j);
+                }
+                rows.swap(p, k);
+            }
+            let pivot = a[k * n + k].clone();
+            for i in (k + 1)..n {
+                let l = a[i * n + k].clone() / pivot.clone();
+                for j in (k + 1)..n {
+                    a[i * n + j] = a[i * n + j].clone() - l.clone() * a[k * n + j].clone();
+                }
+                a[i * n + k] = l;
+            }
+        }
+        Ok((Matrix::from_vec(n, n, a)?, rows))
+    }
+
+    /// solve `A X = B` by the LU decomposition
+    fn lu_solve(&self, b: &Self) -> Result<Self, RMatrixError> {
+        let (lu, rows) = self.lu()?;
+        let (n, m) = (self.shape.row, b.shape.col);
+        if b.shape.row != n {
+            return Err(RMatrixError::ShapeInconsistent(
+                self.dimensions(),
+                b.dimensions(),
+            ));
+        }
+        let mut x = Self::zeros(n, m)?;
+        for j in 0..m {
+            // forward substitution with the unit lower triangle, then back substitution
+            for (i, &r) in rows.iter().enumerate() {
+                let s = (0..i)
+                    .map(|k| lu.data[i * n + k].clone() * x.data[k * m + j].clone())
+                    .sum::<N>();
+                x.data[i * m + j] = b.data[r * m + j].clone() - s;
+            }
+            for i in (0..n).rev() {
+                let s = ((i + 1)..n)
+                    .map(|k| lu.data[i * n + k].clone() * x.data[k * m + j].clone())
+                    .sum::<N>();
+                x.data[i * m + j] = (x.data[i * m + j].clone() - s) / lu.data[i * n + i].clone();
+            }
+        }
+        Ok(x)
+    }
+
+    /// the inverse by the LU decomposition
+    fn lu_inverse(&self) -> Result<Self, RMatrixError> {
+        let n = self.shape.row;
+        self.lu_solve(&Matrix::eyes(n, n)?)
+    }
+
+    /// `ln |det A|` by the LU decomposition, which neither overflows nor underflows
+    fn ln_abs_det(&self) -> Result<f64, RMatrixError> {
+        let n = self.shape.row;
+        let (lu, _) = self.lu()?;
+        Ok((0..n).map(|i| lu.data[i * n + i].modulus().ln()).sum())
+    }
+
+    /// exponential of `A / 2^s` by the Padé approximant of degree `m`, squared `s` times
+    fn expm_pade(&self, m: usize, s: u32) -> Result<Self, RMatrixError> {
+        let n = self.shape.row;
+        let a = self.scale(0.5f64.powi(s as i32))?;
+        let b = pade_coefficients(m);
+        let eye = Matrix::eyes(n, n)?;
+        let a2 = a.times(&a)?;
+        let (u, v) = if m == 13 {
+            let a4 = a2.times(&a2)?;
+            let a6 = a4.times(&a2)?;
+            let sum = |k: [f64; 4], high: bool| -> Result<Self, RMatrixError> {
+                let low = a6
+                    .scale(k[0])?
+                    .plus(&a4.scale(k[1])?)?
+                    .plus(&a2.scale(k[2])?)?;
+                if high {
+                    a6.times(&low)
+                } else {
+                    low.plus(&eye.scale(k[3])?)
+                }
+            };
+            let u = a.times(
+                &sum([b[13], b[11], b[9], 0.0], true)?
+                    .plus(&sum([b[7], b[5], b[3], b[1]], false)?)?,
+            )?;
+            let v = sum([b[12], b[10], b[8], 0.0], true)?
+                .plus(&sum([b[6], b[4], b[2], b[0]], false)?)?;
+            (u, v)
+        } else {
+            let mut u = eye.scale(b[1])?;
+            let mut v = eye.scale(b[0])?;
+            let mut power = eye.clone();
+            for k in (2..=m).step_by(2) {
+                power = power.times(&a2)?;
+                u = u.plus(&power.scale(b[k + 1])?)?;
+                v = v.plus(&power.scale(b[k])?)?;
+            }
+            (a.times(&u)?, v)
+        };
+        let mut x = v.subtract(&u)?.lu_solve(&v.plus(&u)?)?;
+        for _ in 0..s {
+            x = x.times(&x)?;
+        }
+        Ok(x)
+    }
+
+    /// degree and scaling of the Padé approximant for the exponential,
+    /// an infinite or NaN element gives [`RMatrixError::FunctionUndefined`]
+    fn expm_parameters(&self) -> Result<(usize, u32), RMatrixError> {
+        // the norm skips NaN, so the elements are checked
+        if self.data.iter().any(|v| !v.modulus().is_finite()) {
+            return Err(RMatrixError::FunctionUndefined(
+                "expm of a matrix with an infinite or NaN element".to_string(),
+            ));
+        }
+        let norm = self.norm_1();
+        Ok(match PADE_THETA.iter().find(|(_, theta)| norm <= *theta) {
+            Some(&(m, _)) => (m, 0),
+            None => (13, (norm / PADE_THETA_13).log2().ceil().max(0.0) as u32),
+        })
+    }
+
+    /// matrix exponential by scaling and squaring with Padé approximants
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// // rotation by one radian
+    /// let m = Matrix::from_vec(2, 2, vec![0.0, -1.0, 1.0, 0.0])?;
+    /// let r = m.expm()?;
+    /// assert!((r.get(1, 1)? - 1f64.cos()).abs() < 1e-14);
+    /// assert!((r.get(2, 1)? - 1f64.sin()).abs() < 1e-14);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn expm(&self) -> Result<Self, RMatrixError> {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        let (m, s) = self.expm_parameters()?;
+        self.expm_pade(m, s)
+    }
+
+    /// matrix exponential with an estimate of its relative error
+    ///
+    /// the estimate is the difference to the result with one more squaring
+    pub fn expm_with_error(&self) -> Result<(Self, f64), RMatrixError> {
+        let x = self.expm()?;
+        let (_, s) = self.expm_parameters()?;
+        let y = self.expm_pade(13, s + 1)?;
+        let error = y.relative_distance(&x)?.max(UNIT_ROUNDOFF);
+        Ok((x, error))
+    }
+
+    /// principal square root by the scaled Denman–Beavers iteration
+    ///
+    /// the iteration inverts the matrix, so singular matrices use the recurrence
+    /// of Björck and Hammarling on the Schur form instead,
+    /// the square root exists if no eigenvalue is on the negative real axis
+    /// and zero eigenvalues are semisimple,
+    /// otherwise real matrices give [`RMatrixError::FunctionUndefined`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(2, 2, vec![4.0, 1.0, 0.0, 9.0])?;
+    /// let r = m.sqrtm()?;
+    /// assert!((r.get(1, 1)? - 2.0).abs() < 1e-12 && (r.get(2, 2)? - 3.0).abs() < 1e-12);
+    /// assert!((r.get(1, 2)? - 0.2).abs() < 1e-12);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn sqrtm(&self) -> Result<Self, RMatrixError> {
+        Ok(self.sqrtm_with_error()?.0)
+    }
+
+    /// principal square root with the relative residual `||X^2 - A|| / ||A||`
+    pub fn sqrtm_with_error(&self) -> Result<(Self, f64), RMatrixError> {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        N::check_principal(self, "sqrtm", true)?;
+        let x = match self.denman_beavers() {
+            Err(RMatrixError::MatrixSingular | RMatrixError::NotConverged(_)) => {
+                self.sqrtm_schur()?
+            }
+            x => x?,
+        };
+        let error = x.times(&x)?.relative_distance(self)?;
+        Ok((x, error))
+    }
+
+    /// square root of the triangular Schur form `T`, column by column from
+    /// `R_ii^2 = T_ii` and `(R_ii + R_jj) R_ij = T_ij - sum of R_ik R_kj for i < k < j`
+    fn sqrtm_schur(&self) -> Result<Self, RMatrixError> {
+        let n = self.shape.row;
+        let a = Matrix::from_vec(n, n, self.data.iter().map(|v| v.to_complex()).collect())?;
+        let (q, t) = a.schur()?;
+        let tol = n as f64 * UNIT_ROUNDOFF * t.norm_1();
+        let mut r = Matrix::<Complex>::zeros(n, n)?;
+        for j in 0..n {
+            // eigenvalues within the backward error of the Schur form are taken as zero
+            let d = t.data[j * n + j];
+            r.data[j * n + j] = if d.norm() <= tol {
+                Complex::default()
+            } else {
+                d.sqrt()
+            };
+            for i in (0..j).rev() {
+                let s = t.data[i * n + j]
+                    - ((i + 1)..j)
+                        .map(|k| r.data[i * n + k] * r.data[k * n + j])
+                        .sum::<Complex>();
+                let d = r.data[i * n + i] + r.data[j * n + j];
+                r.data[i * n + j] = if d.norm() > 0.0 {
+                    s / d
+                } else if s.norm() <= tol {
+                    Complex::default()
+                } else {
+                    // like the nilpotent [[0, 1], [0, 0]]
+                    return Err(RMatrixError::FunctionUndefined(
+                        "sqrtm has no value with a zero eigenvalue which is not semisimple"
+                            .to_owned(),
+                    ));
+                };
+            }
+        }
+        N::from_complex(q.times(&r)?.times(&q.adjoint()?)?)
+    }
+
+    fn denman_beavers(&self) -> Result<Self, RMatrixError> {
+        let n = self.shape.row;
+        let mut y = self.clone();
+        let mut z: Matrix<N> = Matrix::eyes(n, n)?;
+        let tol = (n as f64 * UNIT_ROUNDOFF).sqrt();
+        let (mut scaling, mut last) = (true, false);
+        for _ in 0..MAX_ITERATIONS {
+            // determinant scaling speeds up the early iterations
+            let mu = if scaling {
+                (-(y.ln_abs_det()? + z.ln_abs_det()?) / (2.0 * n as f64)).exp()
+            } else {
+                1.0
+            };
+            let next = y
+                .scale(mu)?
+                .plus(&z.lu_inverse()?.scale(1.0 / mu)?)?
+                .scale(0.5)?;
+            z = z
+                .scale(mu)?
+                .plus(&y.lu_inverse()?.scale(1.0 / mu)?)?
+                .scale(0.5)?;
+            let change = next.relative_distance(&y)?;
+            y = next;
+            // the convergence is quadratic, one more step after a small change is enough
+            if last {
+                return Ok(y);
+            }
+            last = change <= tol;
+            scaling = change > 1e-2;
+        }
+        Err(RMatrixError::NotConverged(MAX_ITERATIONS))
+    }
+
+    /// principal logarithm by inverse scaling and squaring
+    ///
+    /// square roots are taken until the matrix is close to the identity,
+    /// then the logarithm is a Padé approximant evaluated by Gauss–Legendre quadrature
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(2, 2, vec![0.0, -1.0, 1.0, 0.0])?;
+    /// let back = m.expm()?.logm()?;
+    /// assert!(back.subtract(&m)?.norm_1() < 1e-12);
+    /// let reflection = Matrix::from_vec(2, 2, vec![-1.0, 0.0, 0.0, 1.0])?;
+    /// assert!(reflection.logm().is_err());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn logm(&self) -> Result<Self, RMatrixError> {
+        Ok(self.logm_with_error()?.0)
+    }
+
+    /// principal logarithm with the relative residual `||exp(L) - A|| / ||A||`
+    pub fn logm_with_error(&self) -> Result<(Self, f64), RMatrixError> {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        N::check_principal(self, "logm", false)?;
+        let n = self.shape.row;
+        let eye = Matrix::eyes(n, n)?;
+        let mut x = self.clone();
+        let mut k = 0;
+        while x.subtract(&eye)?.norm_1() > 0.25 {
+            if k == MAX_ITERATIONS {
+                return Err(RMatrixError::NotConverged(MAX_ITERATIONS));
+            }
+            x = x.denman_beavers()?;
+            k += 1;
+        }
+        // log(I + X) is the integral of X (I + tX)^-1 over [0, 1]
+        let d = x.subtract(&eye)?;
+        let mut l = Matrix::zeros(n, n)?;
+        for (t, w) in gauss_legendre(8) {
+            let r = eye.plus(&d.scale(t)?)?.lu_inverse()?;
+            l = l.plus(&d.times(&r)?.scale(w)?)?;
+        }
+        let l = l.scale(2f64.powi(k as i32))?;
+        let error = l.expm()?.relative_distance(self)?;
+        Ok((l, error))
+    }
+}
+
+/// eigenvalues closer than this are evaluated together in one block
+const CLUSTER_DISTANCE: f64 = 0.1;
+
+/// points on the contour around a block of close eigenvalues
+const CONTOUR_POINTS: usize = 64;
+
+/// preferred radius of the contour around a block of close eigenvalues
+const CONTOUR_RADIUS: f64 = 0.5;
+
+/// `f(T)` of a triangular block with close eigenvalues by the Cauchy integral
+/// `f(T) = 1 / (2 pi i) ∮ f(z) (zI - T)^-1 dz` on a circle around their mean
+///
+/// the circle keeps twice the spread of the eigenvalues from the mean
+/// and at most half the `distance` to a singularity of `f`
+fn funm_block<F: Fn(Complex) -> Complex, D: Fn(Complex) -> f64>(
+    t: &Matrix<Complex>,
+    f: &F,
+    distance: &D,
+) -> Result<Matrix<Complex>, RMatrixError> {
+    let m = t.shape.row;
+    if m == 1 {
+        return Matrix::from_vec(1, 1, vec![f(t.data[0])]);
+    }
+    let diag = (0..m).map(|i| t.data[i * m + i]).collect::<Vec<_>>();
+    let center = diag.iter().copied().sum::<Complex>() / m as f64;
+    let spread = diag
+        .iter()
+        .map(|v| (*v - center).norm())
+        .fold(0.0, f64::max);
+    let singular = distance(center);
+    if 4.0 * spread >= singular {
+        return Err(RMatrixError::FunctionUndefined(format!(
+            "no contour around the eigenvalues near {:.3} avoids the singularities",
+            center
+        )));
+    }
+    let radius = (2.0 * spread).max(CONTOUR_RADIUS.min(singular / 2.0));
+    let eye = Matrix::eyes(m, m)?;
+    let mut sum = Matrix::zeros(m, m)?;
+    for k in 0..CONTOUR_POINTS {
+        let theta = 2.0 * std::f64::consts::PI * k as f64 / CONTOUR_POINTS as f64;
+        let w = Complex::from_polar(radius, theta);
+        let z = center + w;
+        let resolvent = eye.smul(z)?.subtract(t)?.lu_inverse()?;
+        sum = sum.plus(&resolvent.smul(f(z) * w)?)?;
+    }
+    sum.smul(Complex::from(1.0 / CONTOUR_POINTS as f64))
+}
+
+/// sub-block of a square matrix with 0-based half-open ranges
+fn block(
+    a: &Matrix<Complex>,
+    rows: &std::ops::Range<usize>,
+    cols: &std::ops::Range<usize>,
+) -> Result<Matrix<Complex>, RMatrixError> {
+    let n = a.shape.col;
+    let data = rows
+        .clone()
+        .flat_map(|i| cols.clone().map(move |j| a.data[i * n + j]))
+        .collect();
+    Matrix::from_vec(rows.len(), cols.len(), data)
+}
+
+/// solve `A X - X B = C` for upper triangular `A` and `B` without common eigenvalues
+fn solve_sylvester(
+    a: &Matrix<Complex>,
+    b: &Matrix<Complex>,
+    c: &Matrix<Complex>,
+) -> Result<Matrix<Complex>, RMatrixError> {
+    let (p, q) = c.dimensions();
+    let mut x = Matrix::<Complex>::zeros(p, q)?;
+    for j in 0..q {
+        // (A - b_jj I) x_j = c_j + sum of x_l b_lj for l < j
+        let rhs = (0..p)
+            .map(|i| {
+                c.data[i * q + j]
+                    + (0..j)
+                        .map(|l| x.data[i * q + l] * b.data[l * q + j])
+                        .sum::<Complex>()
+            })
+            .collect::<Vec<_>>();
+        for i in (0..p).rev() {
+            let s = (i + 1..p)
+                .map(|k| a.data[i * p + k] * x.data[k * q + j])
+                .sum::<Complex>();
+            x.data[i * q + j] = (rhs[i] - s) / (a.data[i * p + i] - b.data[j * q + j]);
+        }
+    }
+    Ok(x)
+}
+
+impl Matrix<Complex> {
+    /// `f(T)` for an upper triangular `T` by the block Parlett recurrence
+    fn funm_triangular<F: Fn(Complex) -> Complex, D: Fn(Complex) -> f64>(
+        t: &Self,
+        blocks: &[std::ops::Range<usize>],
+        f: &F,
+        distance: &D,
+    ) -> Result<Self, RMatrixError> {
+        let n = t.shape.row;
+        let nb = blocks.len();
+        let mut fb: Vec<Vec<Option<Self>>> = vec![vec![None; nb]; nb];
+        for (i, r) in blocks.iter().enumerate() {
+            fb[i][i] = Some(funm_block(&block(t, r, r)?, f, distance)?);
+        }
+        let get = |fb: &Vec<Vec<Option<Self>>>, i: usize, j: usize| {
+            fb[i][j].clone().ok_or(RMatrixError::ShapeUnreasonable)
+        };
+        for j in 1..nb {
+            for i in (0..j).rev() {
+                let (ri, rj) = (&blocks[i], &blocks[j]);
+                let tij = block(t, ri, rj)?;
+                // T_ii F_ij - F_ij T_jj = F_ii T_ij - T_ij F_jj + sum of F_ik T_kj - T_ik F_kj
+                let mut c = get(&fb, i, i)?
+                    .times(&tij)?
+                    .subtract(&tij.times(&get(&fb, j, j)?)?)?;
+                for (k, rk) in blocks.iter().enumerate().take(j).skip(i + 1) {
+                    c = c
+                        .plus(&get(&fb, i, k)?.times(&block(t, rk, rj)?)?)?
+                        .subtract(&block(t, ri, rk)?.times(&get(&fb, k, j)?)?)?;
+                }
+                fb[i][j] = Some(solve_sylvester(&block(t, ri, ri)?, &block(t, rj, rj)?, &c)?);
+            }
+        }
+        let mut out = Matrix::zeros(n, n)?;
+        for i in 0..nb {
+            for j in i..nb {
+                let b = get(&fb, i, j)?;
+                let cols = blocks[j].len();
+                for (k, r) in blocks[i].clone().enumerate() {
+                    for (l, c) in blocks[j].clone().enumerate() {
+                        out.data[r * n + c] = b.data[k * cols + l];
+                    }
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl<N: FunmElement> Matrix<N> {
+    /// `f(A)` for a function `f` analytic on the eigenvalues by the Schur–Parlett algorithm
+    ///
+    /// the Schur form is reordered so that eigenvalues closer than `0.1` form blocks,
+    /// which are evaluated by a contour integral on a circle of radius `0.5`
+    /// or twice their spread, so `f` must be analytic in that distance of close eigenvalues,
+    /// real matrices need `f(conj(z)) = conj(f(z))` for a real result,
+    /// use [`Matrix::funm_with_singularities`] for functions with poles or branch cuts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(2, 2, vec![1.0, 1.0, 0.0, 2.0])?;
+    /// let e = m.funm(|z| z.exp())?;
+    /// assert!(e.subtract(&m.expm()?)?.norm_1() < 1e-12);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn funm<F: Fn(Complex) -> Complex>(&self, f: F) -> Result<Self, RMatrixError> {
+        self.funm_with_singularities(f, |_| f64::INFINITY)
+    }
+
+    /// `f(A)` like [`Matrix::funm`] for a function `f` which is only analytic
+    /// at a positive `distance(z)` from `z`, like the distance to a pole or a branch cut
+    ///
+    /// the contour around a block of close eigenvalues shrinks to stay in that distance,
+    /// if the eigenvalues are too close to a singularity
+    /// [`RMatrixError::FunctionUndefined`] is returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// // 1 / z has a pole at zero
+    /// let m = Matrix::from_vec(2, 2, vec![0.2, 1.0, 0.0, 0.2])?;
+    /// let r = m.funm_with_singularities(|z| z.powf(-1.0), |z| z.norm())?;
+    /// assert!(r.subtract(&m.inverse()?)?.norm_1() < 1e-10);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn funm_with_singularities<F, D>(&self, f: F, distance: D) -> Result<Self, RMatrixError>
+    where
+        F: Fn(Complex) -> Complex,
+        D: Fn(Complex) -> f64,
+    {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        let n = self.shape.row;
+        let a = Matrix::from_vec(n, n, self.data.iter().map(|v| v.to_complex()).collect())?;
+        let (q, t) = a.schur()?;
+        // group eigenvalues transitively closer than the cluster distance
+        let eig = (0..n).map(|i| t.data[i * n + i]).collect::<Vec<_>>();
+        let mut cluster = (0..n).collect::<Vec<_>>();
+        for i in 0..n {
+            for j in 0..i {
+                if (eig[i] - eig[j]).norm() <= CLUSTER_DISTANCE && cluster[i] != cluster[j] {
+                    let (from, to) = (cluster[i].max(cluster[j]), cluster[i].min(cluster[j]));
+                    cluster
+                        .iter_mut()
+                        .filter(|c| **c == from)
+                        .for_each(|c| *c = to);
+                }
+            }
+        }
+        // the cluster of the original eigenvalue nearest to a reordered one
+        let cluster_of = |z: Complex| {
+            let nearest = (0..n)
+                .min_by(|&i, &j| (eig[i] - z).norm().total_cmp(&(eig[j] - z).norm()))
+                .unwrap_or(0);
+            cluster[nearest]
+        };
+        let (q, t) = Matrix::schur_reorder(&q, &t, cluster_of)?;
+        let mut blocks: Vec<std::ops::Range<usize>> = Vec::new();
+        for i in 0..n {
+            match blocks.last_mut() {
+                Some(b)
+                    if cluster_of(t.data[b.start * n + b.start])
+                        == cluster_of(t.data[i * n + i]) =>
+                {
+                    b.end = i + 1
+                }
+                _ => blocks.push(i..i + 1),
+            }
+        }
+        let ft = Matrix::funm_triangular(&t, &blocks, &f, &distance)?;
+        N::from_complex(q.times(&ft)?.times(&q.adjoint()?)?)
+    }
+
+    pub fn sinm(&self) -> Result<Self, RMatrixError> {
+        self.funm(|z| z.sin())
+    }
+
+    pub fn cosm(&self) -> Result<Self, RMatrixError> {
+        self.funm(|z| z.cos())
+    }
+
+    pub fn sinhm(&self) -> Result<Self, RMatrixError> {
+        self.funm(|z| z.sinh())
+    }
+
+    pub fn coshm(&self) -> Result<Self, RMatrixError> {
+        self.funm(|z| z.cosh())
+    }
+
+    /// principal power `A^p`, like `A^(1/3)` for `p = 1.0 / 3.0`
+    ///
+    /// real matrices whose power is not real give [`RMatrixError::FunctionUndefined`],
+    /// like close eigenvalues next to the branch cut on the negative real axis
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(2, 2, vec![0.2, 1.0, 0.0, 0.2])?;
+    /// let r = m.powf(0.5)?;
+    /// assert!(r.times(&r)?.subtract(&m)?.norm_1() < 1e-10);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn powf(&self, p: f64) -> Result<Self, RMatrixError> {
+        // integer powers have at most a pole at zero, others the branch cut
+        // on the closed negative real axis
+        let distance = |z: Complex| match (p.fract() == 0.0, p >= 0.0) {
+            (true, true) => f64::INFINITY,
+            (true, false) => z.norm(),
+            _ if z.re() >= 0.0 => z.norm(),
+            _ => z.im().abs(),
+        };
+        self.funm_with_singularities(|z| z.powf(p), distance)
+    }
+}
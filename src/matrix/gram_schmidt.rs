@@ -0,0 +1,136 @@
+//! Gram–Schmidt orthogonalization of the columns of matrices
+
+use crate::{error::RMatrixError, matrix::Matrix, number::Ring};
+
+/// variant of the Gram–Schmidt process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GramSchmidtMode {
+    /// project the original column onto every previous vector,
+    /// which loses orthogonality for ill conditioned matrices
+    Classical,
+    /// project the partially orthogonalized column onto the previous vectors one by one
+    Modified,
+    /// classical projection twice, which keeps orthogonality near the rounding error
+    Reorthogonalized,
+}
+
+/// result of a Gram–Schmidt process with `A = Q * R`
+///
+/// columns are counted from 1 like [`Matrix::get`]
+#[derive(Debug, Clone)]
+pub struct GramSchmidt {
+    /// orthonormal columns, a dependent column gives a zero column
+    pub q: Matrix<f64>,
+    /// upper triangular coefficients, the diagonal element of a dependent column is zero
+    pub r: Matrix<f64>,
+    /// columns which are linear combinations of the previous ones
+    pub dependent: Vec<usize>,
+    pub rank: usize,
+}
+
+impl GramSchmidt {
+    /// `|Q_k^T * Q_k - I|` for the non-zero columns `Q_k` of `Q`, by the 1-norm,
+    /// zero for exactly orthonormal columns
+    pub fn orthogonality_loss(&self) -> Result<f64, RMatrixError> {
+        let (_, n) = self.q.dimensions();
+        let cols = (1..=n)
+            .filter(|j| !self.dependent.contains(j))
+            .map(|j| self.q.get_col(j))
+            .collect::<Result<Vec<_>, _>>()?;
+        let k = cols.len();
+        let mut loss = 0.0f64;
+        for j in 0..k {
+            let mut sum = 0.0;
+            for i in 0..k {
+                let e = if i == j { 1.0 } else { 0.0 };
+                sum += (Matrix::dot(&cols[i], &cols[j])? - e).abs();
+            }
+            loss = loss.max(sum);
+        }
+        Ok(loss)
+    }
+}
+
+fn project(q: &[Vec<f64>], v: &[f64], r: &mut [f64]) -> Vec<f64> {
+    let mut w = v.to_vec();
+    for (i, qi) in q.iter().enumerate() {
+        let c = qi.iter().zip(v).map(|(x, y)| x * y).sum::<f64>();
+        r[i] += c;
+        w.iter_mut().zip(qi).for_each(|(x, y)| *x -= c * y);
+    }
+    w
+}
+
+impl Matrix<f64> {
+    /// orthonormalize the columns by the Gram–Schmidt process
+    ///
+    /// a column is dependent if its orthogonal part is zero relative to its length,
+    /// it is skipped by the following columns
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::{gram_schmidt::GramSchmidtMode, Matrix};
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let a = Matrix::from_vec(3, 3, vec![1.0, 2.0, 1.0, 1.0, 2.0, 0.0, 0.0, 0.0, 1.0])?;
+    /// let g = a.gram_schmidt(GramSchmidtMode::Modified)?;
+    /// assert_eq!(g.dependent, vec![2]);
+    /// assert_eq!(g.rank, 2);
+    /// assert!(g.q.times(&g.r)?.subtract(&a)?.norm_1() < 1e-12);
+    /// assert!(g.orthogonality_loss()? < 1e-12);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn gram_schmidt(&self, mode: GramSchmidtMode) -> Result<GramSchmidt, RMatrixError> {
+        let (m, n) = self.dimensions();
+        let mut q: Vec<Vec<f64>> = Vec::new();
+        // column of the previous vector in q
+        let mut basis = Vec::new();
+        let mut r = Matrix::<f64>::zeros(n, n)?;
+        let mut qm = Matrix::<f64>::zeros(m, n)?;
+        let mut dependent = Vec::new();
+        for j in 1..=n {
+            let a = self.get_col(j)?;
+            let mut coeffs = vec![0.0; q.len()];
+            let v = match mode {
+                GramSchmidtMode::Classical => project(&q, &a, &mut coeffs),
+                GramSchmidtMode::Reorthogonalized => {
+                    let v = project(&q, &a, &mut coeffs);
+                    project(&q, &v, &mut coeffs)
+                }
+                GramSchmidtMode::Modified => {
+                    let mut v = a.clone();
+                    for (i, qi) in q.iter().enumerate() {
+                        let c = Matrix::dot(qi, &v)?;
+                        coeffs[i] = c;
+                        v.iter_mut().zip(qi).for_each(|(x, y)| *x -= c * y);
+                    }
+                    v
+                }
+            };
+            for (&i, c) in basis.iter().zip(coeffs) {
+                r.set(c, i, j)?;
+            }
+            let length = Matrix::dot(&a, &a)?.sqrt();
+            let norm = Matrix::dot(&v, &v)?.sqrt();
+            if length == 0.0 || (norm / length).is_zero() {
+                dependent.push(j);
+                continue;
+            }
+            r.set(norm, j, j)?;
+            let qj = v.iter().map(|x| x / norm).collect::<Vec<_>>();
+            for (i, x) in qj.iter().enumerate() {
+                qm.set(*x, i + 1, j)?;
+            }
+            q.push(qj);
+            basis.push(j);
+        }
+        Ok(GramSchmidt {
+            q: qm,
+            r,
+            rank: basis.len(),
+            dependent,
+        })
+    }
+}
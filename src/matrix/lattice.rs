@@ -0,0 +1,163 @@
+//! lattice basis reduction
+//!
+//! a lattice basis is given by the columns of a matrix like the bases of subspaces,
+//! which must be linearly independent
+
+use crate::{
+    bigint::BigInt,
+    error::RMatrixError,
+    matrix::Matrix,
+    number::Real,
+    rational::{big::BigRational, Rational},
+};
+
+/// numbers which can be rounded to the nearest integer
+///
+/// the reduction is exact for [`Rational`] and [`BigRational`],
+/// the Gram–Schmidt data grows quickly, so only [`BigRational`] is safe from overflow,
+/// [`f64`] is faster but loses exactness for large or badly conditioned bases
+pub trait LatticeElement: Real {
+    /// the nearest integer, halves are rounded up
+    fn round_integer(&self) -> Self;
+}
+
+impl LatticeElement for f64 {
+    fn round_integer(&self) -> Self {
+        (self + 0.5).floor()
+    }
+}
+
+impl LatticeElement for Rational {
+    fn round_integer(&self) -> Self {
+        // floor((2n + d) / 2d) with a positive denominator
+        let (n, d) = (self.numer() as i128, self.denom() as i128);
+        let r = (2 * n + d).div_euclid(2 * d);
+        Rational::new(
+            i64::try_from(r).expect("rounded rational number overflows"),
+            1,
+        )
+    }
+}
+
+impl LatticeElement for BigRational {
+    fn round_integer(&self) -> Self {
+        let two = BigInt::from(2);
+        let n = two.clone() * self.numer().clone() + self.denom().clone();
+        let d = two * self.denom().clone();
+        let (q, r) = n.div_rem(&d);
+        // truncation rounds negative quotients up
+        let q = if r.is_negative() {
+            q - BigInt::from(1)
+        } else {
+            q
+        };
+        BigRational::from_integer(q)
+    }
+}
+
+fn dot<N: Real>(a: &[N], b: &[N]) -> N {
+    a.iter().zip(b).map(|(x, y)| x.clone() * y.clone()).sum()
+}
+
+impl<N: LatticeElement> Matrix<N> {
+    /// LLL reduction `(B', T)` of the lattice basis in the columns, with `B * T = B'`
+    ///
+    /// `T` is unimodular with integer elements, the basis `B'` is size reduced,
+    /// `|mu_kj| <= 1/2`, and fulfills the Lovász condition
+    /// `|b*_k|^2 >= (delta - mu_k,k-1^2) |b*_k-1|^2` for `1/4 < delta <= 1`,
+    /// the Gram–Schmidt coefficients are updated exactly for exact element types
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::rational::Rational;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let b = Matrix::from_vec(3, 3, [1, -1, 3, 1, 0, 5, 1, 2, 6].map(Rational::from).to_vec())?;
+    /// let (r, t) = b.lll_reduce(Rational::new(3, 4))?;
+    /// assert_eq!(b.times(&t)?, r);
+    /// assert_eq!(r, Matrix::from_vec(3, 3, [0, 1, -1, 1, 0, 0, 0, 1, 2].map(Rational::from).to_vec())?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn lll_reduce(&self, delta: N) -> Result<(Self, Self), RMatrixError> {
+        let quarter = N::one() / (N::one() + N::one() + N::one() + N::one());
+        if delta <= quarter || delta > N::one() {
+            return Err(RMatrixError::FunctionUndefined(format!(
+                "LLL needs 1/4 < delta <= 1, not {}",
+                delta
+            )));
+        }
+        let (m, n) = self.dimensions();
+        let mut b = (1..=n)
+            .map(|j| self.get_col(j))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut t = (1..=n)
+            .map(|j| Matrix::<N>::eyes(n, n)?.get_col(j))
+            .collect::<Result<Vec<_>, _>>()?;
+        // Gram–Schmidt coefficients mu and squared lengths of the orthogonal vectors
+        let mut mu = vec![vec![N::default(); n]; n];
+        let mut len = vec![N::default(); n];
+        let mut star: Vec<Vec<N>> = Vec::with_capacity(n);
+        for i in 0..n {
+            let mut v = b[i].clone();
+            for j in 0..i {
+                mu[i][j] = dot(&b[i], &star[j]) / len[j].clone();
+                for (x, s) in v.iter_mut().zip(&star[j]) {
+                    *x = x.clone() - mu[i][j].clone() * s.clone();
+                }
+            }
+            len[i] = dot(&v, &v);
+            // relative to the column itself, so the test does not depend on the scale
+            let original = dot(&b[i], &b[i]);
+            if original == N::default() || (len[i].clone() / original).is_zero() {
+                return Err(RMatrixError::MatrixSingular);
+            }
+            star.push(v);
+        }
+        let half = N::one() / (N::one() + N::one());
+        let mut k = 1;
+        while k < n {
+            // size reduction of b_k
+            for j in (0..k).rev() {
+                if mu[k][j].abs_value() <= half {
+                    continue;
+                }
+                let q = mu[k][j].round_integer();
+                for v in [&mut b, &mut t, &mut mu] {
+                    let (lower, upper) = v.split_at_mut(k);
+                    // mu_jl is zero for l >= j, the implicit mu_jj = 1 is subtracted below
+                    for (x, y) in upper[0].iter_mut().zip(&lower[j]) {
+                        *x = x.clone() - q.clone() * y.clone();
+                    }
+                }
+                mu[k][j] = mu[k][j].clone() - q;
+            }
+            let c = mu[k][k - 1].clone();
+            if len[k] >= (delta.clone() - c.clone() * c.clone()) * len[k - 1].clone() {
+                k += 1;
+                continue;
+            }
+            // exchange b_k and b_k-1 and update the Gram–Schmidt data
+            b.swap(k, k - 1);
+            t.swap(k, k - 1);
+            let new_len = len[k].clone() + c.clone() * c.clone() * len[k - 1].clone();
+            mu[k][k - 1] = c.clone() * len[k - 1].clone() / new_len.clone();
+            len[k] = len[k - 1].clone() * len[k].clone() / new_len.clone();
+            len[k - 1] = new_len;
+            let (lower, upper) = mu.split_at_mut(k);
+            lower[k - 1][..(k - 1)].swap_with_slice(&mut upper[0][..(k - 1)]);
+            for i in (k + 1)..n {
+                let tmp = mu[i][k].clone();
+                mu[i][k] = mu[i][k - 1].clone() - c.clone() * tmp.clone();
+                mu[i][k - 1] = tmp + mu[k][k - 1].clone() * mu[i][k].clone();
+            }
+            k = (k - 1).max(1);
+        }
+        let columns = |v: Vec<Vec<N>>, r: usize| {
+            Matrix::from_vec(n, r, v.into_iter().flatten().collect())?.transpose()
+        };
+        Ok((columns(b, m)?, columns(t, n)?))
+    }
+}
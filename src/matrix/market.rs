@@ -0,0 +1,219 @@
+//! Matrix Market exchange format
+//!
+//! both `array` and `coordinate` files are read,
+//! matrices are always written as `array` with `general` symmetry
+
+use crate::{complex::Complex, error::RMatrixError, matrix::Matrix, number::Number};
+
+/// element types which can be stored in a Matrix Market file
+pub trait MarketElement: Number {
+    /// field written to the header
+    const FIELD: &'static str;
+
+    /// convert the numbers of one entry, `None` if it can not be represented
+    fn from_market(parts: &[f64]) -> Option<Self>;
+
+    /// the numbers of one entry separated by spaces
+    fn to_market(&self) -> String;
+}
+
+impl MarketElement for f64 {
+    const FIELD: &'static str = "real";
+
+    fn from_market(parts: &[f64]) -> Option<Self> {
+        match parts {
+            [v] => Some(*v),
+            _ => None,
+        }
+    }
+
+    fn to_market(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl MarketElement for Complex {
+    const FIELD: &'static str = "complex";
+
+    fn from_market(parts: &[f64]) -> Option<Self> {
+        match parts {
+            [re] => Some(Complex::new(*re, f64::default())),
+            [re, im] => Some(Complex::new(*re, *im)),
+            _ => None,
+        }
+    }
+
+    fn to_market(&self) -> String {
+        format!("{} {}", self.re(), self.im())
+    }
+}
+
+fn parse_all<T: std::str::FromStr>(parts: &[&str]) -> Result<Vec<T>, RMatrixError> {
+    parts
+        .iter()
+        .map(|e| {
+            e.parse::<T>()
+                .map_err(|_| RMatrixError::ParseFailed(e.to_string()))
+        })
+        .collect()
+}
+
+impl<N: MarketElement> Matrix<N> {
+    /// parse a matrix in Matrix Market format
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let s = "%%MatrixMarket matrix coordinate real symmetric\n2 2 2\n1 1 4\n2 1 -1\n";
+    /// let m = Matrix::<f64>::from_matrix_market(s)?;
+    /// assert_eq!(m, Matrix::from_vec(2, 2, vec![4.0, -1.0, -1.0, 0.0])?);
+    /// assert_eq!(Matrix::<f64>::from_matrix_market(&m.to_matrix_market())?, m);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_matrix_market(s: &str) -> Result<Self, RMatrixError> {
+        let mut lines = s.lines();
+        let banner = lines
+            .next()
+            .ok_or_else(|| RMatrixError::ParseFailed("empty Matrix Market".to_owned()))?
+            .to_lowercase();
+        let fields = banner.split_whitespace().collect::<Vec<_>>();
+        if fields.len() != 5 || fields[0] != "%%matrixmarket" || fields[1] != "matrix" {
+            return Err(RMatrixError::ParseFailed(banner));
+        }
+        let (coordinate, field, symmetry) = (fields[2] == "coordinate", fields[3], fields[4]);
+        let width = match field {
+            "real" | "integer" => 1,
+            "complex" => 2,
+            "pattern" if coordinate => 0,
+            _ => return Err(RMatrixError::FormatUnsupported(format!("field {}", field))),
+        };
+        if !matches!(
+            symmetry,
+            "general" | "symmetric" | "skew-symmetric" | "hermitian"
+        ) {
+            return Err(RMatrixError::FormatUnsupported(format!(
+                "symmetry {}",
+                symmetry
+            )));
+        }
+
+        let mut lines = lines
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() && !l.starts_with('%'));
+        let size_line = lines
+            .next()
+            .ok_or_else(|| RMatrixError::ParseFailed("Matrix Market size".to_owned()))?;
+        let size: Vec<usize> = parse_all(&size_line.split_whitespace().collect::<Vec<_>>())?;
+        let (row, col, count) = match size[..] {
+            [r, c] if !coordinate => (r, c, None),
+            [r, c, nnz] if coordinate => (r, c, Some(nnz)),
+            _ => return Err(RMatrixError::ParseFailed(format!("size {}", size_line))),
+        };
+        row.checked_mul(col)
+            .ok_or(RMatrixError::ShapeUnreasonable)?;
+
+        // array entries are stored by column, only the lower triangle if symmetric
+        let stored = |&(r, c): &(usize, usize)| match symmetry {
+            "general" => true,
+            "skew-symmetric" => r > c,
+            _ => r >= c,
+        };
+        let mut positions = (1..=col)
+            .flat_map(|c| (1..=row).map(move |r| (r, c)))
+            .filter(stored);
+        let mut entries: Vec<(usize, usize, Vec<f64>)> = Vec::new();
+        for line in lines {
+            let tokens = line.split_whitespace().collect::<Vec<_>>();
+            let (r, c, parts) = if coordinate {
+                if tokens.len() != width + 2 {
+                    return Err(RMatrixError::ParseFailed(line.to_owned()));
+                }
+                let index: Vec<usize> = parse_all(&tokens[..2])?;
+                let (r, c) = (index[0], index[1]);
+                if r < 1 || r > row || c < 1 || c > col {
+                    return Err(RMatrixError::OutOfBoundary(r, c));
+                }
+                let parts = if width == 0 {
+                    vec![1.0]
+                } else {
+                    parse_all(&tokens[2..])?
+                };
+                (r, c, parts)
+            } else {
+                if tokens.len() != width {
+                    return Err(RMatrixError::ParseFailed(line.to_owned()));
+                }
+                let (r, c) = positions.next().ok_or_else(|| {
+                    RMatrixError::ParseFailed(format!("more than {} entries", entries.len()))
+                })?;
+                (r, c, parse_all(&tokens)?)
+            };
+            entries.push((r, c, parts));
+        }
+        match count {
+            Some(nnz) if entries.len() != nnz => {
+                return Err(RMatrixError::ParseFailed(format!(
+                    "{} entries, {} declared",
+                    entries.len(),
+                    nnz
+                )))
+            }
+            None if positions.next().is_some() => {
+                return Err(RMatrixError::ParseFailed(format!(
+                    "only {} entries",
+                    entries.len()
+                )))
+            }
+            _ => {}
+        }
+
+        let convert = |parts: &[f64]| {
+            N::from_market(parts).ok_or_else(|| {
+                RMatrixError::FormatUnsupported(format!("field {} for real elements", field))
+            })
+        };
+        let mut m = Self::zeros(row, col)?;
+        for (r, c, parts) in entries {
+            m.set(convert(&parts)?, r, c)?;
+            if r != c {
+                let mirror = match symmetry {
+                    "symmetric" => Some(parts),
+                    "skew-symmetric" => Some(parts.iter().map(|v| -v).collect()),
+                    "hermitian" => Some(
+                        parts
+                            .iter()
+                            .enumerate()
+                            .map(|(i, &v)| if i == 1 { -v } else { v })
+                            .collect(),
+                    ),
+                    _ => None,
+                };
+                if let Some(mirror) = mirror {
+                    m.set(convert(&mirror)?, c, r)?;
+                }
+            }
+        }
+        Ok(m)
+    }
+
+    /// format a matrix in Matrix Market `array` format
+    pub fn to_matrix_market(&self) -> String {
+        let mut s = format!(
+            "%%MatrixMarket matrix array {} general\n{} {}\n",
+            N::FIELD,
+            self.shape.row,
+            self.shape.col
+        );
+        for c in 0..self.shape.col {
+            for r in 0..self.shape.row {
+                s.push_str(&self.data[r * self.shape.col + c].to_market());
+                s.push('\n');
+            }
+        }
+        s
+    }
+}
@@ -0,0 +1,256 @@
+//! reading and writing of MATLAB level 4 MAT-files
+//!
+//! every variable of the file is stored as
+//!
+//! - a header of five 32-bit integers: type, rows, columns, imaginary flag and name length
+//! - the name of the variable, terminated by a null byte
+//! - the real part by column
+//! - the imaginary part by column, if the imaginary flag is set
+//!
+//! variables are read keyed by their names, which are also stored in the `tag` of the matrices
+
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+};
+
+use crate::{complex::Complex, error::RMatrixError, matrix::Matrix, number::Number};
+
+/// element types which can be stored in a MAT-file
+pub trait MatElement: Number {
+    /// whether the imaginary part is written
+    const IMAGINARY: bool;
+
+    /// convert the real and imaginary part, `None` if it can not be represented
+    fn from_mat(re: f64, im: Option<f64>) -> Option<Self>;
+
+    /// the real and imaginary part of the element
+    fn to_mat(&self) -> (f64, f64);
+}
+
+impl MatElement for f64 {
+    const IMAGINARY: bool = false;
+
+    fn from_mat(re: f64, im: Option<f64>) -> Option<Self> {
+        match im {
+            None => Some(re),
+            Some(_) => None,
+        }
+    }
+
+    fn to_mat(&self) -> (f64, f64) {
+        (*self, f64::default())
+    }
+}
+
+impl MatElement for Complex {
+    const IMAGINARY: bool = true;
+
+    fn from_mat(re: f64, im: Option<f64>) -> Option<Self> {
+        Some(Complex::new(re, im.unwrap_or_default()))
+    }
+
+    fn to_mat(&self) -> (f64, f64) {
+        (self.re(), self.im())
+    }
+}
+
+/// check whether `name` is a valid MATLAB variable name
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// read one number of precision `p` from the stream
+fn read_number<R: Read>(reader: &mut R, p: i32, big_endian: bool) -> Result<f64, RMatrixError> {
+    let size = match p {
+        0 => 8,
+        1 | 2 => 4,
+        3 | 4 => 2,
+        _ => 1,
+    };
+    let mut b = [0u8; 8];
+    reader.read_exact(&mut b[..size])?;
+    if big_endian {
+        b[..size].reverse();
+    }
+    Ok(match p {
+        0 => f64::from_le_bytes(b),
+        1 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+        2 => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+        3 => i16::from_le_bytes([b[0], b[1]]) as f64,
+        4 => u16::from_le_bytes([b[0], b[1]]) as f64,
+        _ => b[0] as f64,
+    })
+}
+
+impl<N: MatElement> Matrix<N> {
+    /// read all variables of a MAT-file
+    pub fn read_mat<P: AsRef<std::path::Path>>(
+        path: P,
+    ) -> Result<BTreeMap<String, Self>, RMatrixError> {
+        let mut reader = std::io::BufReader::new(std::fs::File::open(path)?);
+        Self::read_mat_from(&mut reader)
+    }
+
+    /// read all variables of a MAT-file from a reader, keyed by their names
+    ///
+    /// the name is also stored in the `tag` of the matrix,
+    /// a later variable replaces an earlier one of the same name,
+    /// empty variables like `[]` are skipped because a matrix has at least one element,
+    /// complex variables can only be read as `Matrix<Complex>`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let mut a = Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0])?;
+    /// a.tag = "A".to_owned();
+    /// let mut b = Matrix::from_vec(1, 3, vec![5.0, 6.0, 7.0])?;
+    /// b.tag = "b".to_owned();
+    /// let mut buffer = Vec::new();
+    /// Matrix::write_mat_to(&mut buffer, &[&a, &b])?;
+    /// let vars = Matrix::<f64>::read_mat_from(&mut buffer.as_slice())?;
+    /// assert_eq!(vars.len(), 2);
+    /// assert_eq!(vars["A"], a);
+    /// assert_eq!(vars["b"].tag, "b");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn read_mat_from<R: Read>(reader: &mut R) -> Result<BTreeMap<String, Self>, RMatrixError> {
+        let mut vars = BTreeMap::new();
+        loop {
+            let mut header = [0u8; 20];
+            // stop at the end of the stream between two variables
+            let n = reader.read(&mut header[..1])?;
+            if n == 0 {
+                break;
+            }
+            reader.read_exact(&mut header[1..])?;
+            let le = |i: usize| i32::from_le_bytes(header[4 * i..4 * i + 4].try_into().unwrap());
+            let be = |i: usize| i32::from_be_bytes(header[4 * i..4 * i + 4].try_into().unwrap());
+            // the type is at most 4999 in either byte order
+            let big_endian = !(0..5000).contains(&le(0));
+            let field = |i: usize| if big_endian { be(i) } else { le(i) };
+            let (mopt, row, col, imagf, namlen) =
+                (field(0), field(1), field(2), field(3), field(4));
+            let (m, o, p, t) = (mopt / 1000, mopt / 100 % 10, mopt / 10 % 10, mopt % 10);
+            if !(0..5000).contains(&mopt) || m > 1 || o != 0 || p > 5 || t > 1 {
+                return Err(RMatrixError::FormatUnsupported(format!(
+                    "MAT-file type {}",
+                    mopt
+                )));
+            }
+            if row < 0 || col < 0 || namlen < 1 {
+                return Err(RMatrixError::ParseFailed("MAT-file header".to_owned()));
+            }
+
+            // lengths in the header are untrusted, so buffers grow only with the data read
+            let mut name = Vec::new();
+            (&mut *reader).take(namlen as u64).read_to_end(&mut name)?;
+            if name.len() != namlen as usize {
+                return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+            }
+            let name = String::from_utf8_lossy(&name)
+                .trim_end_matches('\0')
+                .to_owned();
+            let (row, col) = (row as usize, col as usize);
+            let len = row
+                .checked_mul(col)
+                .ok_or(RMatrixError::ShapeUnreasonable)?;
+            let mut re = Vec::new();
+            for _ in 0..len {
+                re.push(read_number(reader, p, big_endian)?);
+            }
+            let mut im = Vec::new();
+            if imagf != 0 {
+                for _ in 0..len {
+                    im.push(read_number(reader, p, big_endian)?);
+                }
+            }
+            if len == 0 {
+                continue;
+            }
+            let data = re
+                .iter()
+                .enumerate()
+                .map(|(k, &v)| {
+                    N::from_mat(v, im.get(k).copied()).ok_or_else(|| {
+                        RMatrixError::FormatUnsupported(format!(
+                            "complex variable '{}' for real elements",
+                            name
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            // the data is stored by column, which is the transpose by row
+            let mut m = Self::from_vec(col, row, data)?.transpose()?;
+            m.tag = name.clone();
+            vars.insert(name, m);
+        }
+        Ok(vars)
+    }
+
+    /// write matrices as variables of a MAT-file
+    pub fn write_mat<P: AsRef<std::path::Path>>(
+        path: P,
+        vars: &[&Self],
+    ) -> Result<(), RMatrixError> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        Self::write_mat_to(&mut writer, vars)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// write matrices as variables of a MAT-file to a writer
+    ///
+    /// the `tag` of every matrix is used as its variable name,
+    /// so it must start with a letter and only contain letters, digits and `_`
+    pub fn write_mat_to<W: Write>(writer: &mut W, vars: &[&Self]) -> Result<(), RMatrixError> {
+        for m in vars {
+            if !is_variable_name(&m.tag) {
+                return Err(RMatrixError::FormatUnsupported(format!(
+                    "variable name '{}'",
+                    m.tag
+                )));
+            }
+            let dimension = |v: usize| {
+                i32::try_from(v).map_err(|_| {
+                    RMatrixError::FormatUnsupported(format!(
+                        "variable '{}' of shape {}x{}",
+                        m.tag, m.shape.row, m.shape.col
+                    ))
+                })
+            };
+            // little-endian full double matrix
+            let header = [
+                0i32,
+                dimension(m.shape.row)?,
+                dimension(m.shape.col)?,
+                N::IMAGINARY as i32,
+                dimension(m.tag.len() + 1)?,
+            ];
+            for v in header {
+                writer.write_all(&v.to_le_bytes())?;
+            }
+            writer.write_all(m.tag.as_bytes())?;
+            writer.write_all(&[0u8])?;
+            let parts = (1..=m.shape.col)
+                .flat_map(|c| (1..=m.shape.row).map(move |r| (r, c)))
+                .map(|(r, c)| m.get(r, c).map(|v| v.to_mat()))
+                .collect::<Result<Vec<_>, _>>()?;
+            for (re, _) in parts.iter() {
+                writer.write_all(&re.to_le_bytes())?;
+            }
+            if N::IMAGINARY {
+                for (_, im) in parts.iter() {
+                    writer.write_all(&im.to_le_bytes())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
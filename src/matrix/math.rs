@@ -1,86 +1,326 @@
 //! some math operation of matrices
 
-use crate::{error::RMatrixError, matrix::Matrix, number::Number};
+use crate::{
+    error::RMatrixError,
+    matrix::{echelon::Echelon, normal::EuclideanElement, trace::RowOp, Matrix},
+    number::{Number, Ring},
+};
 
 impl<N: Number> Matrix<N> {
-    pub fn row_eliminate(&self) -> Result<(Self, Self, N), RMatrixError> {
+    /// gaussian elimination, every row operation is also passed to `record`
+    ///
+    /// the pivot of a column is its first non-zero element below the previous pivots,
+    /// if `reduce` is set pivots are scaled to one and eliminated from all other rows,
+    /// otherwise only from the rows below
+    pub(crate) fn eliminate<F>(
+        &self,
+        reduce: bool,
+        mut record: F,
+    ) -> Result<Echelon<N>, RMatrixError>
+    where
+        F: FnMut(&RowOp<N>) -> Result<(), RMatrixError>,
+    {
+        let (r, c) = self.dimensions();
         let mut m = self.clone();
-        let mut factor = N::one();
-        let size = m.shape.row.min(m.shape.col);
-        let mut np = Matrix::eyes(size, size)?;
-        let mut bias = 0;
-        let mut i = 1;
-        while i + bias < size {
-            // check pivot
-            while i + bias <= m.shape.col && m.get(i, i + bias)?.is_zero() {
-                // find non-zero row
-                let mut changed = false;
-                for k in (i + 1)..=m.shape.row {
-                    if !m.get(k, i + bias)?.is_zero() {
-                        // do row exchange
-                        let p_change = Matrix::p_change(self.shape.row, i, k)?;
-                        np = p_change.times(&np)?;
-                        m = p_change.times(&m)?;
-                        factor = factor * N::neg_one();
-                        changed = true;
-                    }
-                }
-                if !changed {
-                    // skip this column
-                    bias += 1;
+        let mut transform = Matrix::eyes(r, r)?;
+        let mut sign = N::one();
+        let mut pivots = Vec::new();
+        let mut free = Vec::new();
+        let mut apply = |op: RowOp<N>, m: &mut Matrix<N>, t: &mut Matrix<N>| {
+            op.apply(m)?;
+            op.apply(t)?;
+            record(&op)
+        };
+        for col in 1..=c {
+            let i = pivots.len() + 1;
+            let found = (i..=r).find(|&k| m.get(k, col).is_ok_and(|v| !v.is_zero()));
+            let k = match found {
+                Some(k) => k,
+                None => {
+                    free.push(col);
+                    continue;
                 }
+            };
+            if k != i {
+                // do row exchange
+                apply(RowOp::Swap(i, k), &mut m, &mut transform)?;
+                sign = -sign;
+            }
+            let mut pivot = m.get(i, col)?;
+            if reduce && !(pivot.clone() - N::one()).is_zero() {
+                apply(
+                    RowOp::Scale {
+                        k: N::one() / pivot,
+                        row: i,
+                    },
+                    &mut m,
+                    &mut transform,
+                )?;
+                pivot = m.get(i, col)?;
             }
             // do eliminate
-            let pivot = m.get(i, i + bias)?;
-            for j in (i + 1)..=m.shape.row {
-                let p = m.get(j, i + bias)? / pivot;
-                let p_add = Matrix::p_add(self.shape.row, -p, i, j)?;
-                np = p_add.times(&np)?;
-                m = p_add.times(&m)?;
+            let rows = if reduce { 1..=r } else { (i + 1)..=r };
+            for j in rows.filter(|&j| j != i) {
+                let v = m.get(j, col)?;
+                if !v.is_zero() {
+                    apply(
+                        RowOp::AddMultiple {
+                            k: -(v / pivot.clone()),
+                            from: i,
+                            to: j,
+                        },
+                        &mut m,
+                        &mut transform,
+                    )?;
+                }
             }
-            // do next row
-            i += 1;
+            pivots.push(col);
         }
-        Ok((m, np, factor))
+        Ok(Echelon {
+            matrix: m,
+            transform,
+            rank: pivots.len(),
+            pivots,
+            free,
+            sign,
+        })
     }
 
-    pub fn row_reduce(&self) -> Result<(Self, Self), RMatrixError> {
-        let (mut m, mut np, _) = self.row_eliminate()?;
-        let mut bias = 0;
-        let size = m.shape.row.min(m.shape.col);
-        let mut i = 1;
-        while i <= size && i + bias <= m.shape.col {
-            if !m.get(i, i + bias)?.is_zero() {
-                let p = m.get(i, i + bias)?;
-                let p_smul = Matrix::p_smul(m.shape.row, N::one() / p, i)?;
-                np = p_smul.times(&np)?;
-                m = p_smul.times(&m)?;
-                for j in 1..i {
-                    if !m.get(j, i + bias)?.is_zero() {
-                        let p = m.get(j, i + bias)? / m.get(i, i + bias)?;
-                        let p_add = Matrix::p_add(m.shape.row, -p, i, j)?;
-                        np = p_add.times(&np)?;
-                        m = p_add.times(&m)?;
-                    }
-                }
-                i += 1;
-            } else {
-                bias += 1;
-            }
-        }
-        Ok((m, np))
+    /// row echelon form, the pivots are not scaled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(3, 3, vec![0.0, 1.0, 2.0, 0.0, 2.0, 4.0, 1.0, 0.0, 1.0])?;
+    /// let e = m.row_eliminate()?;
+    /// assert_eq!(e.pivots, vec![1, 2]);
+    /// assert_eq!(e.free, vec![3]);
+    /// assert_eq!(e.rank, 2);
+    /// assert_eq!(e.sign, -1.0);
+    /// assert_eq!(e.transform.times(&m)?, e.matrix);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn row_eliminate(&self) -> Result<Echelon<N>, RMatrixError> {
+        self.eliminate(false, |_| Ok(()))
+    }
+
+    /// reduced row echelon form
+    pub fn row_reduce(&self) -> Result<Echelon<N>, RMatrixError> {
+        self.eliminate(true, |_| Ok(()))
     }
 
     pub fn det(&self) -> Result<N, RMatrixError> {
         if self.shape.row != self.shape.col {
             Err(RMatrixError::MatrixNotSquare)
         } else {
-            let (m, _, a) = self.row_eliminate()?;
+            let e = self.row_eliminate()?;
             let mut res = N::one();
-            for i in 1..=m.shape.row {
-                res = res * m.get(i, i)?;
+            for i in 1..=e.matrix.shape.row {
+                res = res * e.matrix.get(i, i)?;
             }
-            Ok(res * a)
+            Ok(res * e.sign)
+        }
+    }
+
+    /// integer power `A^n` by repeated squaring, negative powers are powers of the inverse
+    ///
+    /// it takes about `2 log2(n)` multiplications instead of `n`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let fib = Matrix::from_vec(2, 2, vec![1.0, 1.0, 1.0, 0.0])?;
+    /// assert_eq!(fib.pow(10)?.get(1, 2)?, 55.0);
+    /// assert_eq!(fib.pow(-1)?, Matrix::from_vec(2, 2, vec![0.0, 1.0, 1.0, -1.0])?);
+    /// assert_eq!(fib.pow(0)?, Matrix::eyes(2, 2)?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn pow(&self, n: i64) -> Result<Self, RMatrixError> {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
         }
+        let base = if n < 0 { self.inverse()? } else { self.clone() };
+        base.pow_unsigned(n.unsigned_abs())
+    }
+}
+
+impl<N: Ring> Matrix<N> {
+    /// non-negative integer power `A^n` by repeated squaring
+    ///
+    /// unlike [`pow`](Matrix::pow) it does not divide,
+    /// so it is exact for integers like [`BigInt`](crate::bigint::BigInt)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::bigint::BigInt;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let fib = Matrix::from_vec(2, 2, [1, 1, 1, 0].map(BigInt::from).to_vec())?;
+    /// assert_eq!(fib.pow_unsigned(90)?.get(1, 2)?, BigInt::from(2_880_067_194_370_816_120i64));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn pow_unsigned(&self, n: u64) -> Result<Self, RMatrixError> {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        Self::power_by_squaring(
+            Matrix::eyes(self.shape.row, self.shape.col)?,
+            self.clone(),
+            n,
+            Self::times,
+        )
+    }
+
+    /// `identity * base^n` by repeated squaring with the product `mul`
+    fn power_by_squaring<F>(
+        mut result: Self,
+        mut base: Self,
+        mut n: u64,
+        mul: F,
+    ) -> Result<Self, RMatrixError>
+    where
+        F: Fn(&Self, &Self) -> Result<Self, RMatrixError>,
+    {
+        while n > 0 {
+            if n & 1 == 1 {
+                result = mul(&result, &base)?;
+            }
+            n >>= 1;
+            if n > 0 {
+                base = mul(&base, &base)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl<N: Ring + std::ops::Div<Output = N>> Matrix<N> {
+    /// determinant by fraction-free Bareiss elimination
+    ///
+    /// every division is exact in an integral domain,
+    /// so it is correct for integers like [`BigInt`](crate::bigint::BigInt)
+    /// and the intermediate values are minors of the matrix, which bounds their growth
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::bigint::BigInt;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(3, 3, [2, 0, 1, 1, 3, 2, 1, 1, 2].map(BigInt::from).to_vec())?;
+    /// assert_eq!(m.det_bareiss()?, BigInt::from(6));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn det_bareiss(&self) -> Result<N, RMatrixError> {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        let n = self.shape.row;
+        let mut a = self.data.clone();
+        let mut sign = N::one();
+        let mut prev = N::one();
+        for k in 0..n {
+            match (k..n).find(|&i| !a[i * n + k].is_zero()) {
+                None => return Ok(N::default()),
+                Some(p) if p != k => {
+                    for j in k..n {
+                        a.swap(p * n + j, k * n + j);
+                    }
+                    sign = -sign;
+                }
+                Some(_) => {}
+            }
+            for i in (k + 1)..n {
+                for j in (k + 1)..n {
+                    a[i * n + j] = (a[i * n + j].clone() * a[k * n + k].clone()
+                        - a[i * n + k].clone() * a[k * n + j].clone())
+                        / prev.clone();
+                }
+            }
+            prev = a[k * n + k].clone();
+        }
+        Ok(sign * prev)
+    }
+}
+
+impl<N: EuclideanElement + std::ops::Rem<Output = N>> Matrix<N> {
+    /// `A^n` with every element reduced to the residues `0..m` of a positive modulus
+    ///
+    /// every product of two residues is reduced before it is summed,
+    /// so the elements stay below `m * m + m` during the computation,
+    /// which allows huge exponents for integers like [`BigInt`](crate::bigint::BigInt)
+    /// and for `i64` as long as `m * m + m` fits
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::bigint::BigInt;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let fib = Matrix::from_vec(2, 2, [1, 1, 1, 0].map(BigInt::from).to_vec())?;
+    /// // F(10^18) mod 10^9 + 7
+    /// let p = fib.pow_mod(1_000_000_000_000_000_000, &BigInt::from(1_000_000_007))?;
+    /// assert_eq!(p.get(1, 2)?, BigInt::from(209_783_453));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// it is only defined for integers
+    ///
+    /// ```compile_fail
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// let m = Matrix::from_vec(2, 2, vec![1.0, 1.0, 1.0, 0.0]).unwrap();
+    /// let p = m.pow_mod(10, &7.0);
+    /// ```
+    pub fn pow_mod(&self, n: u64, m: &N) -> Result<Self, RMatrixError> {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        if *m <= N::default() {
+            return Err(RMatrixError::FunctionUndefined(format!(
+                "the modulus {} is not positive",
+                m
+            )));
+        }
+        // truncating remainders may be negative, shift them into 0..m
+        let residues = |mut a: Self| {
+            a.data.iter_mut().for_each(|v| {
+                *v = ((v.clone() % m.clone()) + m.clone()) % m.clone();
+            });
+            a
+        };
+        let size = self.shape.row;
+        let times_mod = |a: &Self, b: &Self| {
+            let mut c = Self::zeros(size, size)?;
+            for i in 0..size {
+                for j in 0..size {
+                    c.data[i * size + j] = (0..size).fold(N::default(), |acc, k| {
+                        (acc + a.data[i * size + k].clone() * b.data[k * size + j].clone())
+                            % m.clone()
+                    });
+                }
+            }
+            Ok(c)
+        };
+        Self::power_by_squaring(
+            residues(Matrix::eyes(size, size)?),
+            residues(self.clone()),
+            n,
+            times_mod,
+        )
     }
 }
@@ -1,7 +1,23 @@
 pub mod attr;
 pub mod base;
+pub mod binary;
+pub mod canonical;
+pub mod echelon;
+pub mod eigen;
+pub mod funm;
+pub mod gram_schmidt;
+pub mod lattice;
+pub mod market;
+pub mod mat;
 pub mod math;
+pub mod normal;
+pub mod npy;
+pub mod poly;
+pub mod schur;
 mod shape;
+pub mod subspace;
+pub mod text;
+pub mod trace;
 pub mod utils;
 
 use crate::matrix::shape::MatrixShape;
@@ -10,6 +26,8 @@ use crate::matrix::shape::MatrixShape;
 const TAG_LEANGTH: usize = 8;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawMatrix<N>"))]
 /// numeric matrix
 pub struct Matrix<N> {
     /// matrix data
@@ -19,3 +37,37 @@ pub struct Matrix<N> {
     /// matrix tag
     pub tag: String,
 }
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+/// unchecked matrix used for deserializing
+struct RawMatrix<N> {
+    data: Vec<N>,
+    shape: MatrixShape,
+    tag: String,
+}
+
+#[cfg(feature = "serde")]
+impl<N> TryFrom<RawMatrix<N>> for Matrix<N> {
+    type Error = crate::error::RMatrixError;
+
+    fn try_from(raw: RawMatrix<N>) -> Result<Self, Self::Error> {
+        let len = raw
+            .shape
+            .row
+            .checked_mul(raw.shape.col)
+            .ok_or(crate::error::RMatrixError::ShapeUnreasonable)?;
+        if raw.data.len() != len {
+            Err(crate::error::RMatrixError::LengthInconsistent(
+                raw.data.len(),
+                len,
+            ))
+        } else {
+            Ok(Matrix {
+                data: raw.data,
+                shape: raw.shape,
+                tag: raw.tag,
+            })
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Hermite and Smith normal forms of integer matrices
+//!
+//! only unimodular operations are used: exchanges, adding integer multiples of a row or column,
+//! and negation, so the transforms have integer inverses
+
+use crate::{
+    bigint::BigInt,
+    error::RMatrixError,
+    matrix::{trace::RowOp, Matrix},
+    number::Ring,
+};
+
+/// integers with a division which truncates towards zero,
+/// so `a - (a / b) * b` is smaller than `b` in absolute value
+///
+/// they are only a [`Ring`], eliminations which divide are not available
+///
+/// ```compile_fail
+/// # use rmatrix_ks::matrix::Matrix;
+/// let m = Matrix::from_vec(2, 2, vec![2i64, 1, 1, 2]).unwrap();
+/// let i = m.inverse();
+/// ```
+pub trait EuclideanElement: Ring + PartialOrd + std::ops::Div<Output = Self> {
+    /// absolute value
+    fn abs_value(&self) -> Self {
+        if *self < Self::default() {
+            -self.clone()
+        } else {
+            self.clone()
+        }
+    }
+}
+
+impl EuclideanElement for i64 {}
+impl EuclideanElement for BigInt {}
+
+/// the largest `q` with `q * b <= a` for a positive `b`
+fn floor_div<N: EuclideanElement>(a: N, b: N) -> N {
+    let q = a.clone() / b.clone();
+    if q.clone() * b > a {
+        q - N::one()
+    } else {
+        q
+    }
+}
+
+/// position of the non-zero element with the smallest absolute value in a submatrix
+fn smallest<N: EuclideanElement>(
+    m: &Matrix<N>,
+    rows: std::ops::RangeInclusive<usize>,
+    cols: std::ops::RangeInclusive<usize>,
+) -> Result<Option<(usize, usize)>, RMatrixError> {
+    let mut best: Option<(usize, usize, N)> = None;
+    for i in rows {
+        for j in cols.clone() {
+            let v = m.get(i, j)?.abs_value();
+            if !v.is_zero() && best.as_ref().is_none_or(|b| v < b.2) {
+                best = Some((i, j, v));
+            }
+        }
+    }
+    Ok(best.map(|(i, j, _)| (i, j)))
+}
+
+impl<N: EuclideanElement> Matrix<N> {
+    /// row style Hermite normal form `(H, U)` with `U * A = H` and unimodular `U`
+    ///
+    /// `H` is in row echelon form with positive pivots,
+    /// the elements above a pivot lie in `0..pivot`, it is unique for every matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let a = Matrix::from_vec(2, 3, vec![2i64, 3, 6, 4, 1, 0])?;
+    /// let (h, u) = a.hermite_normal_form()?;
+    /// assert_eq!(h, Matrix::from_vec(2, 3, vec![2, 3, 6, 0, 5, 12])?);
+    /// assert_eq!(u.times(&a)?, h);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn hermite_normal_form(&self) -> Result<(Self, Self), RMatrixError> {
+        let (m, n) = self.dimensions();
+        let mut h = self.clone();
+        let mut u = Matrix::eyes(m, m)?;
+        let apply = |op: RowOp<N>, h: &mut Self, u: &mut Self| {
+            op.apply(h)?;
+            op.apply(u)
+        };
+        let mut row = 1;
+        for col in 1..=n {
+            if row > m {
+                break;
+            }
+            // Euclid on the column until only the pivot row is non-zero
+            while let Some((p, _)) = smallest(&h, row..=m, col..=col)? {
+                if p != row {
+                    apply(RowOp::Swap(row, p), &mut h, &mut u)?;
+                }
+                let pivot = h.get(row, col)?;
+                let mut clean = true;
+                for i in (row + 1)..=m {
+                    let q = h.get(i, col)? / pivot.clone();
+                    if !q.is_zero() {
+                        let k = -q;
+                        apply(
+                            RowOp::AddMultiple {
+                                k,
+                                from: row,
+                                to: i,
+                            },
+                            &mut h,
+                            &mut u,
+                        )?;
+                    }
+                    clean = clean && h.get(i, col)?.is_zero();
+                }
+                if clean {
+                    break;
+                }
+            }
+            let mut pivot = h.get(row, col)?;
+            if pivot.is_zero() {
+                continue;
+            }
+            if pivot < N::default() {
+                let k = N::neg_one();
+                apply(RowOp::Scale { k, row }, &mut h, &mut u)?;
+                pivot = -pivot;
+            }
+            for i in 1..row {
+                let q = floor_div(h.get(i, col)?, pivot.clone());
+                if !q.is_zero() {
+                    let k = -q;
+                    apply(
+                        RowOp::AddMultiple {
+                            k,
+                            from: row,
+                            to: i,
+                        },
+                        &mut h,
+                        &mut u,
+                    )?;
+                }
+            }
+            row += 1;
+        }
+        Ok((h, u))
+    }
+
+    /// Smith normal form `(U, D, V)` with `U * A * V = D` and unimodular `U` and `V`
+    ///
+    /// `D` is diagonal with non-negative elements, each dividing the next,
+    /// the non-zero ones are the invariant factors, like the torsion of the abelian group
+    /// with the rows of `A` as relations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let a = Matrix::from_vec(2, 2, vec![2i64, 4, 6, 8])?;
+    /// let (u, d, v) = a.smith_normal_form()?;
+    /// assert_eq!(d, Matrix::from_vec(2, 2, vec![2, 0, 0, 4])?);
+    /// assert_eq!(u.times(&a)?.times(&v)?, d);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn smith_normal_form(&self) -> Result<(Self, Self, Self), RMatrixError> {
+        let (m, n) = self.dimensions();
+        let mut d = self.clone();
+        let mut u = Matrix::eyes(m, m)?;
+        let mut v = Matrix::eyes(n, n)?;
+        let row_op = |op: RowOp<N>, d: &mut Self, u: &mut Self| {
+            op.apply(d)?;
+            op.apply(u)
+        };
+        let col_op = |op: RowOp<N>, d: &mut Self, v: &mut Self| {
+            op.apply_columns(d)?;
+            op.apply_columns(v)
+        };
+        for t in 1..=m.min(n) {
+            loop {
+                // move the smallest element of the rest to the pivot position
+                let (i, j) = match smallest(&d, t..=m, t..=n)? {
+                    Some(p) => p,
+                    None => return Ok((u, d, v)),
+                };
+                if i != t {
+                    row_op(RowOp::Swap(t, i), &mut d, &mut u)?;
+                }
+                if j != t {
+                    col_op(RowOp::Swap(t, j), &mut d, &mut v)?;
+                }
+                let pivot = d.get(t, t)?;
+                let mut clean = true;
+                for i in (t + 1)..=m {
+                    let q = d.get(i, t)? / pivot.clone();
+                    if !q.is_zero() {
+                        let k = -q;
+                        row_op(RowOp::AddMultiple { k, from: t, to: i }, &mut d, &mut u)?;
+                    }
+                    clean = clean && d.get(i, t)?.is_zero();
+                }
+                for j in (t + 1)..=n {
+                    let q = d.get(t, j)? / pivot.clone();
+                    if !q.is_zero() {
+                        let k = -q;
+                        col_op(RowOp::AddMultiple { k, from: t, to: j }, &mut d, &mut v)?;
+                    }
+                    clean = clean && d.get(t, j)?.is_zero();
+                }
+                if !clean {
+                    continue;
+                }
+                // a remainder in the rest goes into the pivot row, giving a smaller element
+                let mut indivisible = None;
+                'search: for i in (t + 1)..=m {
+                    for j in (t + 1)..=n {
+                        let e = d.get(i, j)?;
+                        if !(e.clone() - (e / pivot.clone()) * pivot.clone()).is_zero() {
+                            indivisible = Some(i);
+                            break 'search;
+                        }
+                    }
+                }
+                match indivisible {
+                    Some(i) => {
+                        let k = N::one();
+                        row_op(RowOp::AddMultiple { k, from: i, to: t }, &mut d, &mut u)?;
+                    }
+                    None => break,
+                }
+            }
+            if d.get(t, t)? < N::default() {
+                let k = N::neg_one();
+                row_op(RowOp::Scale { k, row: t }, &mut d, &mut u)?;
+            }
+        }
+        Ok((u, d, v))
+    }
+}
@@ -0,0 +1,293 @@
+//! reading and writing of the NumPy `.npy` format
+
+use std::io::{Read, Write};
+
+use crate::{complex::Complex, error::RMatrixError, matrix::Matrix, number::Number};
+
+/// magic string at the beginning of every `.npy` file
+const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// a scalar decoded from a `.npy` payload
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NpyScalar {
+    Real(f64),
+    Complex(f64, f64),
+}
+
+/// element types which can be stored in a `.npy` file
+pub trait NpyElement: Number {
+    /// dtype written to the header, always little-endian
+    const DESCR: &'static str;
+
+    /// convert a decoded scalar, `None` if it can not be represented
+    fn from_npy(v: NpyScalar) -> Option<Self>;
+
+    /// little-endian bytes of the element
+    fn to_npy_bytes(&self) -> Vec<u8>;
+}
+
+impl NpyElement for f64 {
+    const DESCR: &'static str = "<f8";
+
+    fn from_npy(v: NpyScalar) -> Option<Self> {
+        match v {
+            NpyScalar::Real(r) => Some(r),
+            NpyScalar::Complex(_, _) => None,
+        }
+    }
+
+    fn to_npy_bytes(&self) -> Vec<u8> {
+        self.to_le_bytes().to_vec()
+    }
+}
+
+impl NpyElement for Complex {
+    const DESCR: &'static str = "<c16";
+
+    fn from_npy(v: NpyScalar) -> Option<Self> {
+        match v {
+            NpyScalar::Real(r) => Some(Complex::new(r, f64::default())),
+            NpyScalar::Complex(re, im) => Some(Complex::new(re, im)),
+        }
+    }
+
+    fn to_npy_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.re().to_le_bytes().to_vec();
+        bytes.extend_from_slice(&self.im().to_le_bytes());
+        bytes
+    }
+}
+
+/// element type described by the `descr` field of the header
+#[derive(Debug, Clone, Copy)]
+struct Dtype {
+    /// numpy kind character, `f`, `i`, `u`, `c` or `b`
+    kind: char,
+    /// size of one element in bytes
+    size: usize,
+    big_endian: bool,
+}
+
+impl Dtype {
+    fn parse(descr: &str) -> Result<Self, RMatrixError> {
+        let unsupported = || RMatrixError::FormatUnsupported(format!("dtype '{}'", descr));
+        let mut chars = descr.chars();
+        let big_endian = match chars.next() {
+            Some('<') | Some('|') | Some('=') => false,
+            Some('>') => true,
+            _ => return Err(unsupported()),
+        };
+        let kind = chars.next().ok_or_else(unsupported)?;
+        let size = chars.as_str().parse::<usize>().map_err(|_| unsupported())?;
+        match (kind, size) {
+            ('f', 4 | 8) | ('c', 8 | 16) | ('i' | 'u', 1 | 2 | 4 | 8) | ('b', 1) => Ok(Dtype {
+                kind,
+                size,
+                big_endian,
+            }),
+            _ => Err(unsupported()),
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> NpyScalar {
+        let word = |b: &[u8]| -> [u8; 8] {
+            let mut w = [0u8; 8];
+            if self.big_endian {
+                b.iter().rev().enumerate().for_each(|(i, &v)| w[i] = v);
+            } else {
+                w[..b.len()].copy_from_slice(b);
+            }
+            w
+        };
+        let float = |b: &[u8]| -> f64 {
+            let w = word(b);
+            if b.len() == 4 {
+                f32::from_le_bytes([w[0], w[1], w[2], w[3]]) as f64
+            } else {
+                f64::from_le_bytes(w)
+            }
+        };
+        match self.kind {
+            'f' => NpyScalar::Real(float(bytes)),
+            'c' => {
+                let half = self.size / 2;
+                NpyScalar::Complex(float(&bytes[..half]), float(&bytes[half..]))
+            }
+            'u' | 'b' => NpyScalar::Real(u64::from_le_bytes(word(bytes)) as f64),
+            _ => {
+                // sign extend the integer to 64 bits
+                let shift = 64 - 8 * self.size as u32;
+                NpyScalar::Real(((i64::from_le_bytes(word(bytes)) << shift) >> shift) as f64)
+            }
+        }
+    }
+}
+
+/// find the raw text of the value of `key` in the header dictionary
+fn header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let start = header
+        .find(&format!("'{}'", key))
+        .or_else(|| header.find(&format!("\"{}\"", key)))?;
+    let rest = header[start + key.len() + 2..].trim_start();
+    let rest = rest.strip_prefix(':')?.trim_start();
+    let end = match rest.chars().next()? {
+        '\'' | '"' => rest[1..].find(['\'', '"'])? + 2,
+        '(' => rest.find(')')? + 1,
+        _ => rest.find([',', '}']).unwrap_or(rest.len()),
+    };
+    Some(rest[..end].trim())
+}
+
+/// parse the header dictionary into dtype, fortran order and shape
+fn parse_header(header: &str) -> Result<(Dtype, bool, Vec<usize>), RMatrixError> {
+    let missing = |key: &str| RMatrixError::ParseFailed(format!("npy header without '{}'", key));
+    let descr = header_value(header, "descr").ok_or_else(|| missing("descr"))?;
+    let dtype = Dtype::parse(descr.trim_matches(['\'', '"']))?;
+    let fortran_order = match header_value(header, "fortran_order") {
+        Some("True") => true,
+        Some("False") => false,
+        Some(v) => return Err(RMatrixError::ParseFailed(v.to_owned())),
+        None => return Err(missing("fortran_order")),
+    };
+    let shape = header_value(header, "shape")
+        .ok_or_else(|| missing("shape"))?
+        .trim_matches(['(', ')'])
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<usize>()
+                .map_err(|_| RMatrixError::ParseFailed(s.to_owned()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((dtype, fortran_order, shape))
+}
+
+impl<N: NpyElement> Matrix<N> {
+    /// read a matrix from a `.npy` file
+    ///
+    /// 1-D arrays are loaded as column vectors,
+    /// arrays of any other dimension than 1 or 2 are rejected
+    pub fn read_npy<P: AsRef<std::path::Path>>(path: P) -> Result<Self, RMatrixError> {
+        let mut reader = std::io::BufReader::new(std::fs::File::open(path)?);
+        Self::read_npy_from(&mut reader)
+    }
+
+    /// read a matrix in `.npy` format from a reader
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0])?;
+    /// let mut buffer = Vec::new();
+    /// m.write_npy_to(&mut buffer)?;
+    /// assert_eq!(Matrix::<f64>::read_npy_from(&mut buffer.as_slice())?, m);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn read_npy_from<R: Read>(reader: &mut R) -> Result<Self, RMatrixError> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic[..6] != NPY_MAGIC {
+            return Err(RMatrixError::ParseFailed("npy magic string".to_owned()));
+        }
+        let header_len = match magic[6] {
+            1 => {
+                let mut len = [0u8; 2];
+                reader.read_exact(&mut len)?;
+                u16::from_le_bytes(len) as usize
+            }
+            2 | 3 => {
+                let mut len = [0u8; 4];
+                reader.read_exact(&mut len)?;
+                u32::from_le_bytes(len) as usize
+            }
+            v => {
+                return Err(RMatrixError::FormatUnsupported(format!(
+                    "npy version {}.{}",
+                    v, magic[7]
+                )))
+            }
+        };
+        let mut header = vec![0u8; header_len];
+        reader.read_exact(&mut header)?;
+        let header = String::from_utf8_lossy(&header);
+        let (dtype, fortran_order, shape) = parse_header(&header)?;
+
+        let (row, col) = match shape[..] {
+            [r] => (r, 1),
+            [r, c] => (r, c),
+            _ => return Err(RMatrixError::DimensionUnsupported(shape)),
+        };
+        // the header is untrusted, so the payload is read element by element
+        // instead of allocating its claimed size up front
+        let count = row
+            .checked_mul(col)
+            .filter(|n| n.checked_mul(dtype.size).is_some())
+            .ok_or_else(|| RMatrixError::DimensionUnsupported(shape.clone()))?;
+        let mut data = Vec::new();
+        let mut bytes = vec![0u8; dtype.size];
+        for _ in 0..count {
+            reader.read_exact(&mut bytes)?;
+            data.push(N::from_npy(dtype.decode(&bytes)).ok_or_else(|| {
+                RMatrixError::FormatUnsupported(format!(
+                    "dtype '{}' for elements of '{}'",
+                    header_value(&header, "descr").unwrap_or_default(),
+                    N::DESCR
+                ))
+            })?);
+        }
+        if fortran_order {
+            // column major data is the transpose in row major order
+            Self::from_vec(col, row, data)?.transpose()
+        } else {
+            Self::from_vec(row, col, data)
+        }
+    }
+
+    /// write a matrix to a `.npy` file
+    pub fn write_npy<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), RMatrixError> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write_npy_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// write a matrix in `.npy` format to a writer
+    ///
+    /// the data is always written in C order
+    pub fn write_npy_to<W: Write>(&self, writer: &mut W) -> Result<(), RMatrixError> {
+        let mut header = format!(
+            "{{'descr': '{}', 'fortran_order': False, 'shape': ({}, {}), }}",
+            N::DESCR,
+            self.shape.row,
+            self.shape.col
+        );
+        // version 1 stores the header length in 2 bytes
+        let (version, prefix) = if header.len() + 11 <= u16::MAX as usize {
+            (1u8, 10)
+        } else {
+            (2u8, 12)
+        };
+        // the whole header is aligned to 64 bytes and ends with a newline
+        let padding = (64 - (prefix + header.len() + 1) % 64) % 64;
+        header.push_str(&" ".repeat(padding));
+        header.push('\n');
+
+        writer.write_all(NPY_MAGIC)?;
+        writer.write_all(&[version, 0])?;
+        if version == 1 {
+            writer.write_all(&(header.len() as u16).to_le_bytes())?;
+        } else {
+            writer.write_all(&(header.len() as u32).to_le_bytes())?;
+        }
+        writer.write_all(header.as_bytes())?;
+        for v in self.data.iter() {
+            writer.write_all(&v.to_npy_bytes())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+//! characteristic and minimal polynomials of matrices
+
+use crate::{
+    error::RMatrixError,
+    matrix::Matrix,
+    number::{Number, Ring},
+    polynomial::Polynomial,
+};
+
+impl<N: Ring> Matrix<N> {
+    /// the characteristic polynomial `det(x I - A)` by the Berkowitz algorithm
+    ///
+    /// the polynomial of every leading principal submatrix follows from the previous one
+    /// by a product with a Toeplitz matrix, so only additions and multiplications are used
+    /// and the result is exact in every characteristic for integers like
+    /// [`BigInt`](crate::bigint::BigInt), [`Rational`](crate::rational::Rational)
+    /// and the [finite fields](crate::finite),
+    /// rounding errors grow with the size for floating point numbers
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::finite::Gf;
+    /// # use rmatrix_ks::rational::Rational;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(2, 2, [1, 2, 3, 4].map(Rational::from).to_vec())?;
+    /// assert_eq!(m.char_poly()?.to_string(), "x^2 - 5x - 2");
+    /// let m = Matrix::from_vec(2, 2, [1, 1, 1, 0].map(Gf::<2>::new).to_vec())?;
+    /// assert_eq!(m.char_poly()?.to_string(), "x^2 + x + 1");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn char_poly(&self) -> Result<Polynomial<N>, RMatrixError> {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        let n = self.shape.row;
+        let a = |i: usize, j: usize| self.data[i * n + j].clone();
+        // coefficients of the leading k x k submatrix in descending order
+        let mut p = vec![N::one()];
+        for k in 1..=n {
+            // the first column of the Toeplitz matrix is
+            // 1, -a_kk, -r c, -r A c, ..., -r A^(k-2) c
+            // with the leading submatrix A, the column c above and the row r left of a_kk
+            let r = (0..(k - 1)).map(|j| a(k - 1, j)).collect::<Vec<_>>();
+            let mut v = (0..(k - 1)).map(|i| a(i, k - 1)).collect::<Vec<_>>();
+            let mut t = vec![N::one(), -a(k - 1, k - 1)];
+            for _ in 0..(k - 1) {
+                t.push(
+                    -r.iter()
+                        .zip(&v)
+                        .map(|(x, y)| x.clone() * y.clone())
+                        .sum::<N>(),
+                );
+                v = (0..(k - 1))
+                    .map(|i| (0..(k - 1)).map(|j| a(i, j) * v[j].clone()).sum())
+                    .collect();
+            }
+            p = (0..=k)
+                .map(|i| {
+                    (0..=i.min(k - 1))
+                        .map(|j| t[i - j].clone() * p[j].clone())
+                        .sum()
+                })
+                .collect();
+        }
+        p.reverse();
+        Ok(Polynomial::new(p))
+    }
+}
+
+impl<N: Number> Matrix<N> {
+    /// the minimal polynomial, the monic polynomial of least degree with `p(A) = 0`
+    ///
+    /// it is the first linear dependence of `I, A, A^2, ...`,
+    /// found by elimination on the flattened powers,
+    /// so it is exact for [`Rational`](crate::rational::Rational)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::rational::Rational;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(3, 3, [2, 0, 0, 0, 2, 0, 0, 0, 3].map(Rational::from).to_vec())?;
+    /// assert_eq!(m.char_poly()?.to_string(), "x^3 - 7x^2 + 16x - 12");
+    /// assert_eq!(m.min_poly()?.to_string(), "x^2 - 5x + 6");
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn min_poly(&self) -> Result<Polynomial<N>, RMatrixError> {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        let n = self.shape.row;
+        let mut powers = Matrix::<N>::eyes(n, n)?.data;
+        let mut last = Matrix::<N>::eyes(n, n)?;
+        // by Cayley–Hamilton the powers up to A^n are dependent
+        for k in 1..=n {
+            last = last.times(self)?;
+            powers.extend(last.data.iter().cloned());
+            let columns = Matrix::from_vec(k + 1, n * n, powers.clone())?.transpose()?;
+            // I, ..., A^(k-1) are independent, so A^k is the only free column
+            if let Some(dependence) = columns.echelon_null_space()? {
+                return Ok(Polynomial::new(dependence.get_col(1)?));
+            }
+        }
+        self.char_poly()
+    }
+}
@@ -0,0 +1,263 @@
+//! complex Schur decomposition
+
+use crate::{complex::Complex, error::RMatrixError, matrix::Matrix, number::Ring};
+
+/// maximum QR iterations spent on one eigenvalue
+const MAX_QR_ITERATIONS: usize = 60;
+
+/// a square array with 0-based indices, modified in place by the decomposition
+struct Square {
+    n: usize,
+    a: Vec<Complex>,
+}
+
+impl Square {
+    fn at(&self, i: usize, j: usize) -> Complex {
+        self.a[i * self.n + j]
+    }
+
+    fn set(&mut self, i: usize, j: usize, v: Complex) {
+        self.a[i * self.n + j] = v;
+    }
+
+    /// apply the rotation `[[conj(c), conj(s)], [-s, c]]` to rows `i` and `i + 1`
+    fn rotate_rows(&mut self, i: usize, c: Complex, s: Complex, cols: std::ops::Range<usize>) {
+        for j in cols {
+            let (x, y) = (self.at(i, j), self.at(i + 1, j));
+            self.set(i, j, c.conj() * x + s.conj() * y);
+            self.set(i + 1, j, c * y - s * x);
+        }
+    }
+
+    /// multiply columns `j` and `j + 1` by the adjoint of the rotation
+    fn rotate_cols(&mut self, j: usize, c: Complex, s: Complex, rows: std::ops::Range<usize>) {
+        for i in rows {
+            let (x, y) = (self.at(i, j), self.at(i, j + 1));
+            self.set(i, j, x * c + y * s);
+            self.set(i, j + 1, y * c.conj() - x * s.conj());
+        }
+    }
+}
+
+/// rotation which maps `(x, y)` to `(r, 0)`
+fn givens(x: Complex, y: Complex) -> (Complex, Complex) {
+    let r = x.norm().hypot(y.norm());
+    if r == 0.0 {
+        (Complex::one(), Complex::default())
+    } else {
+        (x / r, y / r)
+    }
+}
+
+impl Matrix<Complex> {
+    /// Schur decomposition `A = Q * T * Q^H` with unitary `Q` and upper triangular `T`
+    ///
+    /// the eigenvalues are on the diagonal of `T`,
+    /// computed by the shifted QR algorithm on the Hessenberg form
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::complex::Complex;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(2, 2, vec![0.0, -1.0, 1.0, 0.0])?.to_complex();
+    /// let (q, t) = m.schur()?;
+    /// assert!(t.get(2, 1)?.norm() < 1e-14);
+    /// let back = q.times(&t)?.times(&q.adjoint()?)?;
+    /// assert!(back.subtract(&m)?.norm_1() < 1e-14);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn schur(&self) -> Result<(Self, Self), RMatrixError> {
+        if self.shape.row != self.shape.col {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        let n = self.shape.row;
+        let mut t = Square {
+            n,
+            a: self.data.clone(),
+        };
+        let mut q = Square {
+            n,
+            a: Matrix::<Complex>::eyes(n, n)?.data,
+        };
+        hessenberg(&mut t, &mut q);
+        shifted_qr(&mut t, &mut q)?;
+        Ok((Matrix::from_vec(n, n, q.a)?, Matrix::from_vec(n, n, t.a)?))
+    }
+
+    /// reorder a Schur decomposition so that the diagonal of `T` is sorted by `key`
+    ///
+    /// adjacent eigenvalues are exchanged by rotations, equal keys keep their order
+    pub fn schur_reorder<K: Ord, F: Fn(Complex) -> K>(
+        q: &Self,
+        t: &Self,
+        key: F,
+    ) -> Result<(Self, Self), RMatrixError> {
+        let n = t.shape.row;
+        let mut tt = Square {
+            n,
+            a: t.data.clone(),
+        };
+        let mut qq = Square {
+            n,
+            a: q.data.clone(),
+        };
+        // bubble sort, every exchange is a similarity transform
+        for pass in 0..n {
+            let mut swapped = false;
+            for k in 0..(n - 1 - pass.min(n - 1)) {
+                if key(tt.at(k, k)) > key(tt.at(k + 1, k + 1)) {
+                    swap_diagonal(&mut tt, &mut qq, k);
+                    swapped = true;
+                }
+            }
+            if !swapped {
+                break;
+            }
+        }
+        Ok((Matrix::from_vec(n, n, qq.a)?, Matrix::from_vec(n, n, tt.a)?))
+    }
+
+    /// the conjugate transpose
+    pub fn adjoint(&self) -> Result<Self, RMatrixError> {
+        let mut m = self.transpose()?;
+        m.data.iter_mut().for_each(|v| *v = v.conj());
+        Ok(m)
+    }
+}
+
+impl Matrix<f64> {
+    /// the same matrix with complex elements
+    pub fn to_complex(&self) -> Matrix<Complex> {
+        Matrix {
+            data: self.data.iter().map(|&v| Complex::from(v)).collect(),
+            shape: self.shape,
+            tag: self.tag.clone(),
+        }
+    }
+}
+
+/// exchange the diagonal elements `k` and `k + 1` of the triangular `t`
+fn swap_diagonal(t: &mut Square, q: &mut Square, k: usize) {
+    let n = t.n;
+    let (c, s) = givens(t.at(k, k + 1), t.at(k + 1, k + 1) - t.at(k, k));
+    t.rotate_rows(k, c, s, k..n);
+    t.rotate_cols(k, c, s, 0..(k + 2));
+    q.rotate_cols(k, c, s, 0..n);
+    t.set(k + 1, k, Complex::default());
+}
+
+/// reduce to upper Hessenberg form by Householder reflections
+fn hessenberg(h: &mut Square, q: &mut Square) {
+    let n = h.n;
+    for k in 0..n.saturating_sub(2) {
+        let x = ((k + 1)..n).map(|i| h.at(i, k)).collect::<Vec<_>>();
+        let norm = x.iter().map(|v| v.norm_sqr()).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            continue;
+        }
+        // v = x - alpha e_1 with alpha of the opposite phase of x_1 to avoid cancellation
+        let phase = if x[0].norm() == 0.0 {
+            Complex::one()
+        } else {
+            x[0] / x[0].norm()
+        };
+        let mut v = x;
+        v[0] = v[0] + phase * norm;
+        let vn = v.iter().map(|v| v.norm_sqr()).sum::<f64>().sqrt();
+        v.iter_mut().for_each(|e| *e = *e / vn);
+        // H = P H P and Q = Q P with P = I - 2 v v^H
+        for j in 0..n {
+            let d: Complex = (0..v.len()).map(|i| v[i].conj() * h.at(k + 1 + i, j)).sum();
+            for (i, vi) in v.iter().enumerate() {
+                let e = h.at(k + 1 + i, j) - *vi * d * 2.0;
+                h.set(k + 1 + i, j, e);
+            }
+        }
+        for m in [&mut *h, &mut *q] {
+            for i in 0..n {
+                let d: Complex = (0..v.len()).map(|l| m.at(i, k + 1 + l) * v[l]).sum();
+                for (l, vl) in v.iter().enumerate() {
+                    let e = m.at(i, k + 1 + l) - d * vl.conj() * 2.0;
+                    m.set(i, k + 1 + l, e);
+                }
+            }
+        }
+        for i in (k + 2)..n {
+            h.set(i, k, Complex::default());
+        }
+    }
+}
+
+/// single shift QR iterations with Wilkinson shifts on a Hessenberg matrix
+fn shifted_qr(h: &mut Square, q: &mut Square) -> Result<(), RMatrixError> {
+    let n = h.n;
+    let scale = h.a.iter().map(|v| v.norm_sqr()).sum::<f64>().sqrt();
+    let mut hi = n.saturating_sub(1);
+    let mut iterations = 0;
+    while hi > 0 {
+        // find the active block lo..=hi with no negligible subdiagonal
+        let mut lo = hi;
+        while lo > 0 {
+            let mut s = h.at(lo - 1, lo - 1).norm() + h.at(lo, lo).norm();
+            if s == 0.0 {
+                s = scale;
+            }
+            if h.at(lo, lo - 1).norm() <= f64::EPSILON * s {
+                h.set(lo, lo - 1, Complex::default());
+                break;
+            }
+            lo -= 1;
+        }
+        if lo == hi {
+            hi -= 1;
+            iterations = 0;
+            continue;
+        }
+        iterations += 1;
+        if iterations > MAX_QR_ITERATIONS {
+            return Err(RMatrixError::NotConverged(MAX_QR_ITERATIONS));
+        }
+        let (a, b, c, d) = (
+            h.at(hi - 1, hi - 1),
+            h.at(hi - 1, hi),
+            h.at(hi, hi - 1),
+            h.at(hi, hi),
+        );
+        let mu = if iterations % 10 == 0 {
+            // exceptional shift to break cycles
+            d + Complex::from(c.norm() * 0.75)
+        } else {
+            // the eigenvalue of the trailing 2x2 block closer to d
+            let half = (a - d) * 0.5;
+            let disc = (half * half + b * c).sqrt();
+            let (m1, m2) = ((a + d) * 0.5 + disc, (a + d) * 0.5 - disc);
+            if (m1 - d).norm() <= (m2 - d).norm() {
+                m1
+            } else {
+                m2
+            }
+        };
+        for k in lo..=hi {
+            h.set(k, k, h.at(k, k) - mu);
+        }
+        let mut rotations = Vec::with_capacity(hi - lo);
+        for k in lo..hi {
+            let (c, s) = givens(h.at(k, k), h.at(k + 1, k));
+            h.rotate_rows(k, c, s, k..n);
+            h.set(k + 1, k, Complex::default());
+            rotations.push((k, c, s));
+        }
+        for (k, c, s) in rotations {
+            h.rotate_cols(k, c, s, 0..(k + 2).min(hi + 1));
+            q.rotate_cols(k, c, s, 0..n);
+        }
+        for k in lo..=hi {
+            h.set(k, k, h.at(k, k) + mu);
+        }
+    }
+    Ok(())
+}
@@ -3,6 +3,8 @@
 use crate::error::RMatrixError;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawMatrixShape"))]
 /// shape of a matrix
 pub struct MatrixShape {
     /// row size
@@ -11,6 +13,23 @@ pub struct MatrixShape {
     pub col: usize,
 }
 
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+/// unchecked shape used for deserializing
+struct RawMatrixShape {
+    row: usize,
+    col: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawMatrixShape> for MatrixShape {
+    type Error = RMatrixError;
+
+    fn try_from(raw: RawMatrixShape) -> Result<Self, Self::Error> {
+        Self::new(raw.row, raw.col)
+    }
+}
+
 impl MatrixShape {
     pub fn new(row: usize, col: usize) -> Result<Self, RMatrixError> {
         if row == 0 || col == 0 {
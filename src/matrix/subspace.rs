@@ -0,0 +1,415 @@
+//! fundamental subspaces of matrices
+//!
+//! a basis is returned as the columns of a matrix,
+//! `None` stands for the zero subspace which has no basis vector
+//!
+//! the bases are exact and read off the reduced row echelon form for exact types,
+//! and orthonormal from a pivoted QR decomposition for `f64`
+
+use crate::{
+    error::RMatrixError,
+    finite::{Gf, Gf2k},
+    matrix::Matrix,
+    number::Number,
+    rational::{big::BigRational, Rational},
+};
+
+/// default relative tolerance of the numerical rank of `f64` matrices
+///
+/// a column is dependent if its remaining norm is at most the tolerance
+/// times the norm of the largest column
+pub const SUBSPACE_TOLERANCE: f64 = 1e-8;
+
+/// element types with a preferred kind of subspace bases
+///
+/// exact types use the echelon bases like [`Matrix::echelon_null_space`],
+/// `f64` uses the orthonormal bases like [`Matrix::null_space_with_tolerance`]
+/// with [`SUBSPACE_TOLERANCE`]
+pub trait SubspaceElement: Number {
+    /// the dimension of the column space, consistent with the bases
+    fn rank_of(a: &Matrix<Self>) -> Result<usize, RMatrixError> {
+        a.rank()
+    }
+
+    /// whether every column of `v` lies in the column space, consistent with the bases
+    fn in_span_of(a: &Matrix<Self>, v: &Matrix<Self>) -> Result<bool, RMatrixError> {
+        Ok(Self::rank_of(&Matrix::hcat(a, v)?)? == Self::rank_of(a)?)
+    }
+
+    fn null_space_of(a: &Matrix<Self>) -> Result<Option<Matrix<Self>>, RMatrixError> {
+        a.echelon_null_space()
+    }
+
+    fn column_space_of(a: &Matrix<Self>) -> Result<Option<Matrix<Self>>, RMatrixError> {
+        a.echelon_column_space()
+    }
+
+    fn row_space_of(a: &Matrix<Self>) -> Result<Option<Matrix<Self>>, RMatrixError> {
+        a.echelon_row_space()
+    }
+
+    fn left_null_space_of(a: &Matrix<Self>) -> Result<Option<Matrix<Self>>, RMatrixError> {
+        a.echelon_left_null_space()
+    }
+}
+
+impl SubspaceElement for Rational {}
+impl SubspaceElement for BigRational {}
+impl<const P: u64> SubspaceElement for Gf<P> {}
+impl<const POLY: u64> SubspaceElement for Gf2k<POLY> {}
+
+impl SubspaceElement for f64 {
+    fn rank_of(a: &Matrix<Self>) -> Result<usize, RMatrixError> {
+        a.rank_with_tolerance(SUBSPACE_TOLERANCE)
+    }
+
+    fn in_span_of(a: &Matrix<Self>, v: &Matrix<Self>) -> Result<bool, RMatrixError> {
+        a.is_in_span_with_tolerance(v, SUBSPACE_TOLERANCE)
+    }
+
+    fn null_space_of(a: &Matrix<Self>) -> Result<Option<Matrix<Self>>, RMatrixError> {
+        a.null_space_with_tolerance(SUBSPACE_TOLERANCE)
+    }
+
+    fn column_space_of(a: &Matrix<Self>) -> Result<Option<Matrix<Self>>, RMatrixError> {
+        a.column_space_with_tolerance(SUBSPACE_TOLERANCE)
+    }
+
+    fn row_space_of(a: &Matrix<Self>) -> Result<Option<Matrix<Self>>, RMatrixError> {
+        a.row_space_with_tolerance(SUBSPACE_TOLERANCE)
+    }
+
+    fn left_null_space_of(a: &Matrix<Self>) -> Result<Option<Matrix<Self>>, RMatrixError> {
+        a.left_null_space_with_tolerance(SUBSPACE_TOLERANCE)
+    }
+}
+
+/// matrix with the given columns of length `rows`, `None` if there is no column
+fn from_columns<N: Number>(
+    rows: usize,
+    cols: Vec<Vec<N>>,
+) -> Result<Option<Matrix<N>>, RMatrixError> {
+    if cols.is_empty() {
+        return Ok(None);
+    }
+    let n = cols.len();
+    let m = Matrix::from_vec(n, rows, cols.into_iter().flatten().collect())?;
+    Ok(Some(m.transpose()?))
+}
+
+impl<N: Number> Matrix<N> {
+    /// basis of the solutions of `A * x = 0` from the reduced row echelon form
+    ///
+    /// there is one vector for every free column of the reduced row echelon form,
+    /// it is one at the free column and zero at the other free columns
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::rational::Rational;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(2, 3, [1, 2, 3, 2, 4, 6].map(Rational::from).to_vec())?;
+    /// let n = m.echelon_null_space()?.unwrap();
+    /// assert_eq!(n.dimensions(), (3, 2));
+    /// assert_eq!(n.get_col(1)?, [-2, 1, 0].map(Rational::from).to_vec());
+    /// assert_eq!(m.times(&n)?, Matrix::zeros(2, 2)?);
+    /// assert!(Matrix::<Rational>::eyes(2, 2)?.echelon_null_space()?.is_none());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn echelon_null_space(&self) -> Result<Option<Self>, RMatrixError> {
+        let e = self.row_reduce()?;
+        let mut cols = Vec::new();
+        for &f in e.free.iter() {
+            let mut v = vec![N::default(); self.shape.col];
+            v[f - 1] = N::one();
+            for (i, &p) in e.pivots.iter().enumerate() {
+                v[p - 1] = -(e.matrix.get(i + 1, f)? / e.matrix.get(i + 1, p)?);
+            }
+            cols.push(v);
+        }
+        from_columns(self.shape.col, cols)
+    }
+
+    /// basis of the span of the columns, the pivot columns of the matrix
+    pub fn echelon_column_space(&self) -> Result<Option<Self>, RMatrixError> {
+        let e = self.row_eliminate()?;
+        let cols = e
+            .pivots
+            .iter()
+            .map(|&p| self.get_col(p))
+            .collect::<Result<Vec<_>, _>>()?;
+        from_columns(self.shape.row, cols)
+    }
+
+    /// basis of the span of the rows as columns, the non-zero rows of the reduced row echelon form
+    pub fn echelon_row_space(&self) -> Result<Option<Self>, RMatrixError> {
+        let e = self.row_reduce()?;
+        let cols = (1..=e.rank)
+            .map(|i| e.matrix.get_row(i))
+            .collect::<Result<Vec<_>, _>>()?;
+        from_columns(self.shape.col, cols)
+    }
+
+    /// basis of the solutions of `y^T * A = 0` as columns
+    ///
+    /// these are the rows of the elimination transform which produce the zero rows
+    pub fn echelon_left_null_space(&self) -> Result<Option<Self>, RMatrixError> {
+        let e = self.row_eliminate()?;
+        let cols = ((e.rank + 1)..=self.shape.row)
+            .map(|i| e.transform.get_row(i))
+            .collect::<Result<Vec<_>, _>>()?;
+        from_columns(self.shape.row, cols)
+    }
+}
+
+impl<N: SubspaceElement> Matrix<N> {
+    /// basis of the solutions of `A * x = 0`, see [`SubspaceElement`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(2, 3, vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0])?;
+    /// let n = m.null_space()?.unwrap();
+    /// assert_eq!(n.dimensions(), (3, 2));
+    /// assert!(m.times(&n)?.norm_1() < 1e-12);
+    /// assert!(n.transpose()?.times(&n)?.subtract(&Matrix::eyes(2, 2)?)?.norm_1() < 1e-12);
+    /// assert!(Matrix::<f64>::eyes(2, 2)?.null_space()?.is_none());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn null_space(&self) -> Result<Option<Self>, RMatrixError> {
+        N::null_space_of(self)
+    }
+
+    /// basis of the span of the columns, see [`SubspaceElement`]
+    pub fn column_space(&self) -> Result<Option<Self>, RMatrixError> {
+        N::column_space_of(self)
+    }
+
+    /// basis of the span of the rows as columns, see [`SubspaceElement`]
+    pub fn row_space(&self) -> Result<Option<Self>, RMatrixError> {
+        N::row_space_of(self)
+    }
+
+    /// basis of the solutions of `y^T * A = 0` as columns, see [`SubspaceElement`]
+    pub fn left_null_space(&self) -> Result<Option<Self>, RMatrixError> {
+        N::left_null_space_of(self)
+    }
+
+    /// basis of the intersection of the column spaces of two matrices
+    ///
+    /// a vector `A * x = B * y` of the intersection comes from `[A, -B] * [x; y] = 0`
+    pub fn subspace_intersection(&self, other: &Self) -> Result<Option<Self>, RMatrixError> {
+        if other.shape.row != self.shape.row {
+            return Err(RMatrixError::ShapeInconsistent(
+                self.dimensions(),
+                other.dimensions(),
+            ));
+        }
+        let joined = Matrix::hcat(self, &other.smul(N::neg_one())?)?;
+        let n = match joined.null_space()? {
+            Some(n) => n,
+            None => return Ok(None),
+        };
+        let k = self.shape.col;
+        let x = Matrix::from_vec(k, n.shape.col, n.data[..k * n.shape.col].to_vec())?;
+        self.times(&x)?.column_space()
+    }
+
+    /// basis of the sum of the column spaces of two matrices
+    pub fn subspace_sum(&self, other: &Self) -> Result<Option<Self>, RMatrixError> {
+        Matrix::hcat(self, other)?.column_space()
+    }
+
+    /// dimension of the null space, see [`SubspaceElement`]
+    pub fn nullity(&self) -> Result<usize, RMatrixError> {
+        Ok(self.shape.col - N::rank_of(self)?)
+    }
+
+    /// dimension of the left null space, see [`SubspaceElement`]
+    pub fn left_nullity(&self) -> Result<usize, RMatrixError> {
+        Ok(self.shape.row - N::rank_of(self)?)
+    }
+
+    /// whether every column of `v` is a linear combination of the columns,
+    /// with the same tolerance as the bases, see [`SubspaceElement`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(3, 2, vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0])?;
+    /// assert!(m.is_in_span(&Matrix::from_vec(3, 1, vec![2.0, 3.0, 5.0])?)?);
+    /// assert!(!m.is_in_span(&Matrix::from_vec(3, 1, vec![0.0, 0.0, 1.0])?)?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_in_span(&self, v: &Self) -> Result<bool, RMatrixError> {
+        if v.shape.row != self.shape.row {
+            return Err(RMatrixError::ShapeInconsistent(
+                self.dimensions(),
+                v.dimensions(),
+            ));
+        }
+        N::in_span_of(self, v)
+    }
+}
+
+/// householder QR decomposition with column pivoting, `A * P = Q * R`
+///
+/// returns the full orthogonal `Q` and the numerical rank,
+/// a column is dependent if its remaining norm is at most `tolerance` times the largest column
+fn pivoted_qr(a: &Matrix<f64>, tolerance: f64) -> Result<(Matrix<f64>, usize), RMatrixError> {
+    let (m, n) = a.dimensions();
+    let mut r = a.data.clone();
+    let mut q = Matrix::<f64>::eyes(m, m)?;
+    let col_norm = |r: &[f64], j: usize, from: usize| {
+        (from..m).map(|i| r[i * n + j].powi(2)).sum::<f64>().sqrt()
+    };
+    let scale = (0..n).map(|j| col_norm(&r, j, 0)).fold(0.0, f64::max);
+    if scale == 0.0 {
+        return Ok((q, 0));
+    }
+    let mut rank = 0;
+    for k in 0..m.min(n) {
+        let (j, norm) = (k..n)
+            .map(|j| (j, col_norm(&r, j, k)))
+            .fold((k, -1.0), |best, c| if c.1 > best.1 { c } else { best });
+        if norm <= tolerance * scale {
+            break;
+        }
+        if j != k {
+            for i in 0..m {
+                r.swap(i * n + j, i * n + k);
+            }
+        }
+        // reflect x = r[k.., k] onto alpha * e_1
+        let alpha = if r[k * n + k] > 0.0 { -norm } else { norm };
+        let mut v = (k..m).map(|i| r[i * n + k]).collect::<Vec<_>>();
+        v[0] -= alpha;
+        let vn = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if vn > 0.0 {
+            v.iter_mut().for_each(|x| *x /= vn);
+            for j in k..n {
+                let d = (k..m).map(|i| v[i - k] * r[i * n + j]).sum::<f64>();
+                for i in k..m {
+                    r[i * n + j] -= 2.0 * d * v[i - k];
+                }
+            }
+            for i in 0..m {
+                let d = (k..m).map(|l| q.data[i * m + l] * v[l - k]).sum::<f64>();
+                for l in k..m {
+                    q.data[i * m + l] -= 2.0 * d * v[l - k];
+                }
+            }
+        }
+        rank += 1;
+    }
+    Ok((q, rank))
+}
+
+/// columns `from..to` of `q`, counted from 0
+fn columns(q: &Matrix<f64>, from: usize, to: usize) -> Result<Option<Matrix<f64>>, RMatrixError> {
+    let cols = ((from + 1)..=to)
+        .map(|j| q.get_col(j))
+        .collect::<Result<Vec<_>, _>>()?;
+    from_columns(q.shape.row, cols)
+}
+
+impl Matrix<f64> {
+    /// numerical rank by pivoted QR,
+    /// with the relative `tolerance` like [`SUBSPACE_TOLERANCE`]
+    pub fn rank_with_tolerance(&self, tolerance: f64) -> Result<usize, RMatrixError> {
+        Ok(pivoted_qr(self, tolerance)?.1)
+    }
+
+    /// whether every column of `v` lies in the column space,
+    /// its distance to the orthonormal basis of the column space is compared
+    /// with the relative `tolerance` times its own norm
+    pub fn is_in_span_with_tolerance(
+        &self,
+        v: &Self,
+        tolerance: f64,
+    ) -> Result<bool, RMatrixError> {
+        if v.shape.row != self.shape.row {
+            return Err(RMatrixError::ShapeInconsistent(
+                self.dimensions(),
+                v.dimensions(),
+            ));
+        }
+        let basis = self.column_space_with_tolerance(tolerance)?;
+        for j in 1..=v.shape.col {
+            let x = v.get_col(j)?;
+            let mut r = x.clone();
+            if let Some(q) = &basis {
+                for k in 1..=q.shape.col {
+                    let qk = q.get_col(k)?;
+                    let d = Matrix::dot(&qk, &x)?;
+                    r.iter_mut().zip(&qk).for_each(|(r, q)| *r -= d * q);
+                }
+            }
+            let norm = |x: &[f64]| x.iter().map(|e| e * e).sum::<f64>().sqrt();
+            if norm(&r) > tolerance * norm(&x) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// orthonormal basis of the column space by pivoted QR,
+    /// with the relative `tolerance` of the numerical rank like [`SUBSPACE_TOLERANCE`]
+    pub fn column_space_with_tolerance(
+        &self,
+        tolerance: f64,
+    ) -> Result<Option<Self>, RMatrixError> {
+        let (q, rank) = pivoted_qr(self, tolerance)?;
+        columns(&q, 0, rank)
+    }
+
+    /// orthonormal basis of the left null space by pivoted QR,
+    /// with the relative `tolerance` of the numerical rank like [`SUBSPACE_TOLERANCE`]
+    pub fn left_null_space_with_tolerance(
+        &self,
+        tolerance: f64,
+    ) -> Result<Option<Self>, RMatrixError> {
+        let (q, rank) = pivoted_qr(self, tolerance)?;
+        columns(&q, rank, self.shape.row)
+    }
+
+    /// orthonormal basis of the row space as columns by pivoted QR of the transpose,
+    /// with the relative `tolerance` of the numerical rank like [`SUBSPACE_TOLERANCE`]
+    pub fn row_space_with_tolerance(&self, tolerance: f64) -> Result<Option<Self>, RMatrixError> {
+        let (q, rank) = pivoted_qr(&self.transpose()?, tolerance)?;
+        columns(&q, 0, rank)
+    }
+
+    /// orthonormal basis of the null space by pivoted QR of the transpose,
+    /// with the relative `tolerance` of the numerical rank like [`SUBSPACE_TOLERANCE`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// // the second row differs from twice the first one by 1e-6
+    /// let m = Matrix::from_vec(2, 2, vec![1.0, 2.0, 2.0, 4.000001])?;
+    /// assert!(m.null_space_with_tolerance(1e-12)?.is_none());
+    /// let n = m.null_space_with_tolerance(1e-4)?.unwrap();
+    /// assert!(m.times(&n)?.norm_1() < 1e-6);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn null_space_with_tolerance(&self, tolerance: f64) -> Result<Option<Self>, RMatrixError> {
+        let (q, rank) = pivoted_qr(&self.transpose()?, tolerance)?;
+        columns(&q, rank, self.shape.col)
+    }
+}
@@ -0,0 +1,88 @@
+//! plain text and CSV formats of matrices
+//!
+//! every row of a matrix is in one line,
+//! lines which are empty or start with `#` are ignored
+
+use crate::{error::RMatrixError, matrix::Matrix, number::Ring};
+
+impl<N: Ring> Matrix<N> {
+    /// parse a matrix from lines of elements split by `split`
+    fn from_lines<F>(s: &str, split: F) -> Result<Self, RMatrixError>
+    where
+        F: for<'a> Fn(&'a str) -> Vec<&'a str>,
+    {
+        let mut rows: Vec<Vec<N>> = Vec::new();
+        for line in s.lines().map(|l| l.trim()) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let row = split(line)
+                .into_iter()
+                .map(|e| {
+                    e.parse::<N>()
+                        .map_err(|_| RMatrixError::ParseFailed(e.to_owned()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(first) = rows.first() {
+                if first.len() != row.len() {
+                    return Err(RMatrixError::LengthInconsistent(row.len(), first.len()));
+                }
+            }
+            rows.push(row);
+        }
+        let col = rows.first().map(|r| r.len()).unwrap_or_default();
+        Self::from_vec(rows.len(), col, rows.into_iter().flatten().collect())
+    }
+
+    /// parse a matrix from rows of whitespace separated elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::<f64>::from_text("1 2\n3 4\n")?;
+    /// assert_eq!(m, Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0])?);
+    /// assert_eq!(Matrix::<f64>::from_text(&m.to_text())?, m);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_text(s: &str) -> Result<Self, RMatrixError> {
+        Self::from_lines(s, |l| l.split_whitespace().collect())
+    }
+
+    /// parse a matrix from rows of comma separated elements
+    pub fn from_csv(s: &str) -> Result<Self, RMatrixError> {
+        Self::from_lines(s, |l| {
+            l.split(',')
+                .map(|e| e.trim().trim_matches('"').trim())
+                .collect()
+        })
+    }
+
+    /// format a matrix as rows of whitespace separated elements
+    pub fn to_text(&self) -> String {
+        self.join_rows(" ")
+    }
+
+    /// format a matrix as rows of comma separated elements
+    pub fn to_csv(&self) -> String {
+        self.join_rows(",")
+    }
+
+    fn join_rows(&self, sep: &str) -> String {
+        self.data
+            .chunks(self.shape.col)
+            .map(|r| {
+                let mut line = r
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<_>>()
+                    .join(sep);
+                line.push('\n');
+                line
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,275 @@
+//! recorded elementary row operations
+
+use crate::{
+    error::RMatrixError,
+    matrix::Matrix,
+    number::{Number, Ring},
+};
+
+/// an elementary row operation, rows start from 1
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowOp<N> {
+    /// exchange two rows, like [`Matrix::p_change`]
+    Swap(usize, usize),
+    /// add `k` times row `from` to row `to`, like [`Matrix::p_add`]
+    AddMultiple { k: N, from: usize, to: usize },
+    /// multiply row `row` by `k`, like [`Matrix::p_smul`]
+    Scale { k: N, row: usize },
+}
+
+impl<N: Ring> RowOp<N> {
+    /// elementary matrix of the operation for matrices with `n` rows
+    pub fn matrix(&self, n: usize) -> Result<Matrix<N>, RMatrixError> {
+        match self {
+            RowOp::Swap(i, j) => Matrix::p_change(n, *i, *j),
+            RowOp::AddMultiple { k, from, to } => Matrix::p_add(n, k.clone(), *from, *to),
+            RowOp::Scale { k, row } => Matrix::p_smul(n, k.clone(), *row),
+        }
+    }
+
+    /// apply the operation to the rows of `m`
+    pub fn apply(&self, m: &mut Matrix<N>) -> Result<(), RMatrixError> {
+        let (r, c) = m.dimensions();
+        let check = |i: usize| {
+            if i == 0 || i > r {
+                Err(RMatrixError::OutOfRowBoundary(i))
+            } else {
+                Ok(i - 1)
+            }
+        };
+        match self {
+            RowOp::Swap(i, j) => {
+                let (i, j) = (check(*i)?, check(*j)?);
+                for k in 0..c {
+                    m.data.swap(i * c + k, j * c + k);
+                }
+            }
+            RowOp::AddMultiple { k, from, to } => {
+                let (from, to) = (check(*from)?, check(*to)?);
+                for j in 0..c {
+                    m.data[to * c + j] =
+                        m.data[to * c + j].clone() + k.clone() * m.data[from * c + j].clone();
+                }
+            }
+            RowOp::Scale { k, row } => {
+                let row = check(*row)?;
+                for j in 0..c {
+                    m.data[row * c + j] = k.clone() * m.data[row * c + j].clone();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// apply the same operation to the columns of `m`, like multiplying by the transposed
+    /// elementary matrix from the right, so `AddMultiple` adds `k` times column `from` to column `to`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::{trace::RowOp, Matrix};
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let mut m = Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0])?;
+    /// let op = RowOp::AddMultiple { k: -2.0, from: 1, to: 2 };
+    /// op.apply_columns(&mut m)?;
+    /// assert_eq!(m, Matrix::from_vec(2, 2, vec![1.0, 0.0, 3.0, -2.0])?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn apply_columns(&self, m: &mut Matrix<N>) -> Result<(), RMatrixError> {
+        let (r, c) = m.dimensions();
+        let check = |j: usize| {
+            if j == 0 || j > c {
+                Err(RMatrixError::OutOfColumnBoundary(j))
+            } else {
+                Ok(j - 1)
+            }
+        };
+        match self {
+            RowOp::Swap(i, j) => {
+                let (i, j) = (check(*i)?, check(*j)?);
+                for k in 0..r {
+                    m.data.swap(k * c + i, k * c + j);
+                }
+            }
+            RowOp::AddMultiple { k, from, to } => {
+                let (from, to) = (check(*from)?, check(*to)?);
+                for i in 0..r {
+                    m.data[i * c + to] =
+                        m.data[i * c + to].clone() + k.clone() * m.data[i * c + from].clone();
+                }
+            }
+            RowOp::Scale { k, row } => {
+                let col = check(*row)?;
+                for i in 0..r {
+                    m.data[i * c + col] = k.clone() * m.data[i * c + col].clone();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// LaTeX notation of the operation
+    pub fn to_latex(&self) -> String {
+        match self {
+            RowOp::Swap(i, j) => format!("R_{{{}}} \\leftrightarrow R_{{{}}}", i, j),
+            RowOp::AddMultiple { k, from, to } => format!(
+                "R_{{{}}} \\leftarrow R_{{{}}} + ({:.3}) R_{{{}}}",
+                to, to, k, from
+            ),
+            RowOp::Scale { k, row } => {
+                format!("R_{{{}}} \\leftarrow ({:.3}) R_{{{}}}", row, k, row)
+            }
+        }
+    }
+}
+
+impl<N: Ring> std::fmt::Display for RowOp<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RowOp::Swap(i, j) => write!(f, "R{} <-> R{}", i, j),
+            RowOp::AddMultiple { k, from, to } => {
+                write!(f, "R{} <- R{} + ({:.3}) * R{}", to, to, k, from)
+            }
+            RowOp::Scale { k, row } => write!(f, "R{} <- ({:.3}) * R{}", row, k, row),
+        }
+    }
+}
+
+/// a matrix with the row operations applied to it, each followed by the intermediate matrix
+#[derive(Debug, Clone)]
+pub struct RowTrace<N> {
+    pub initial: Matrix<N>,
+    pub steps: Vec<(RowOp<N>, Matrix<N>)>,
+}
+
+impl<N: Ring> RowTrace<N> {
+    pub fn new(initial: Matrix<N>) -> Self {
+        RowTrace {
+            initial,
+            steps: Vec::new(),
+        }
+    }
+
+    /// apply an operation to the current matrix and record it
+    pub fn push(&mut self, op: RowOp<N>) -> Result<(), RMatrixError> {
+        let mut m = self.result().clone();
+        op.apply(&mut m)?;
+        self.steps.push((op, m));
+        Ok(())
+    }
+
+    /// the matrix after all operations
+    pub fn result(&self) -> &Matrix<N> {
+        self.steps.last().map_or(&self.initial, |(_, m)| m)
+    }
+
+    pub fn ops(&self) -> impl Iterator<Item = &RowOp<N>> {
+        self.steps.iter().map(|(op, _)| op)
+    }
+
+    /// apply the recorded operations to another matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let a = Matrix::from_vec(2, 2, vec![0.0, 2.0, 1.0, 1.0])?;
+    /// let trace = a.row_reduce_traced()?;
+    /// // replaying on the identity gives the inverse
+    /// let inv = trace.replay(&Matrix::eyes(2, 2)?)?;
+    /// assert_eq!(inv.times(&a)?, Matrix::eyes(2, 2)?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn replay(&self, m: &Matrix<N>) -> Result<Matrix<N>, RMatrixError> {
+        let mut m = m.clone();
+        for op in self.ops() {
+            op.apply(&mut m)?;
+        }
+        Ok(m)
+    }
+
+    /// the product of the elementary matrices, `E` with `E * initial = result`
+    pub fn transform(&self) -> Result<Matrix<N>, RMatrixError> {
+        let n = self.initial.shape.row;
+        self.replay(&Matrix::eyes(n, n)?)
+    }
+
+    /// worked solution in plain text
+    pub fn to_text(&self) -> String {
+        let mut s = format_rows(&self.initial);
+        for (op, m) in self.steps.iter() {
+            s.push_str(&format!("\n{}\n", op));
+            s.push_str(&format_rows(m));
+        }
+        s
+    }
+
+    /// worked solution as a LaTeX `align*` environment
+    pub fn to_latex(&self) -> String {
+        let mut s = format!("\\begin{{align*}}\n& {}", latex_matrix(&self.initial));
+        for (op, m) in self.steps.iter() {
+            s.push_str(&format!(
+                " \\\\\n\\xrightarrow{{{}}} & {}",
+                op.to_latex(),
+                latex_matrix(m)
+            ));
+        }
+        s.push_str("\n\\end{align*}\n");
+        s
+    }
+}
+
+fn format_rows<N: Ring>(m: &Matrix<N>) -> String {
+    m.data
+        .chunks(m.shape.col)
+        .map(|r| {
+            format!(
+                "[{}]\n",
+                r.iter()
+                    .map(|v| format!("{:>6.3}", v))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+        .collect()
+}
+
+fn latex_matrix<N: Ring>(m: &Matrix<N>) -> String {
+    format!(
+        "\\begin{{bmatrix}} {} \\end{{bmatrix}}",
+        m.data
+            .chunks(m.shape.col)
+            .map(|r| {
+                r.iter()
+                    .map(|v| format!("{:.3}", v))
+                    .collect::<Vec<_>>()
+                    .join(" & ")
+            })
+            .collect::<Vec<_>>()
+            .join(" \\\\ ")
+    )
+}
+
+impl<N: Number> Matrix<N> {
+    /// reduce a matrix to reduced row echelon form, recording every row operation
+    ///
+    /// each pivot is the first non-zero element of its column,
+    /// it is scaled to one and then eliminated from all other rows
+    pub fn row_reduce_traced(&self) -> Result<RowTrace<N>, RMatrixError> {
+        let mut ops = Vec::new();
+        self.eliminate(true, |op| {
+            ops.push(op.clone());
+            Ok(())
+        })?;
+        let mut trace = RowTrace::new(self.clone());
+        for op in ops {
+            trace.push(op)?;
+        }
+        Ok(trace)
+    }
+}
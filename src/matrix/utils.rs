@@ -1,11 +1,11 @@
 use crate::{
     error::RMatrixError,
     matrix::{shape::MatrixShape, Matrix, TAG_LEANGTH},
-    number::Number,
+    number::{Number, Ring},
 };
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 
-impl<N: Number> Matrix<N> {
+impl<N: Ring> Matrix<N> {
     /// return a zero matrix with specific size
     ///
     /// # Examples
@@ -70,126 +70,24 @@ impl<N: Number> Matrix<N> {
     }
 
     pub fn from_stdin() -> Result<Self, RMatrixError> {
-        //! read a matrix from stdin
-        //!
-        //! # Examples
+        //! read a matrix in the text format from stdin until the end of the input
         //!
-        //! the input must follow some rules
+        //! nothing is prompted or printed, the input is parsed by [`Matrix::from_text`],
+        //! so every row is in one line and the elements are separated by whitespace,
+        //! for interactive work use the `rmatrix repl` calculator instead
         //!
-        //! - the shape infos are separated by spaces or commas
-        //! - the shape info must in one line
-        //! - the data are separated by spaces or newlines
-        //! - can not use comma or other separator with data
+        //! # Examples
         //!
         //! ```no_run
         //! # use rmatrix_ks::matrix::Matrix;
+        //! // printf '1 2\n3 4.15\n' | program
         //! let m = Matrix::<f64>::from_stdin().unwrap();
-        //! println!("{}", m);
-        //!
-        //! // matrix shape (r, c): 2, 2    
-        //! // matrix data:
-        //! // 1 2
-        //! // 3 4.15
-        //! //
-        //! // [ 1.000,  2.000]
-        //! // [ 3.000,  4.150]
-        //! // <mat[c2i8szSj] 2x2>
-        //! ```
-        //!
-        //! # Warning
-        //!
-        //! for complex number of other type
-        //!
-        //! `from_stdin()` method is using whitespace to split data,
-        //! be sure to avoid whitespace when entering
-        //!
-        //! if the complex number has only an `Im`, the `Re` should be replaced by 0,
-        //! but there must be a `Re`.
-        //!
-        //! ## Examples
-        //!
-        //! ```no_run
-        //! # use rmatrix_ks::matrix::Matrix;
-        //! # use rmatrix_ks::complex::Complex;
-        //! let m = Matrix::<Complex>::from_stdin().unwrap();
-        //! println!("{}", m);
-        //!
-        //! // matrix shape (r, c): 2 2
-        //! // matrix data:
-        //! // 1+2I -3-3.14I
-        //! // 0-4I -3.14+2I
-        //! //
-        //! // [1.000+2.000I, -3.000-3.140I]
-        //! // [0.000-4.000I, -3.140+2.000I]
-        //! // <mat[nhQSomO8] 2x2>
+        //! assert_eq!(m.dimensions(), (2, 2));
         //! ```
 
-        print!("matrix shape (r, c): ");
-        std::io::Write::flush(&mut std::io::stdout()).expect("failed to flush stdout");
-        let mut rb: String = String::new();
-        let mut shape_info: Vec<usize>;
-        loop {
-            rb.clear();
-            if std::io::stdin().read_line(&mut rb).is_ok() {
-                shape_info = rb
-                    .split(|c: char| c.is_whitespace() || c == ',')
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.parse::<usize>().unwrap_or_default())
-                    .collect::<Vec<_>>();
-            } else {
-                loop {
-                    eprintln!("failed to get matrix shape info, please re-input!");
-                    if let Ok(_) = std::io::stdin().read_line(&mut rb) {
-                        shape_info = rb
-                            .split(|c: char| c.is_whitespace() || c == ',')
-                            .filter(|s| !s.is_empty())
-                            .map(|s| s.parse::<usize>().unwrap_or_default())
-                            .collect::<Vec<_>>();
-                        break;
-                    }
-                }
-            }
-
-            if shape_info.len() == 2 {
-                break;
-            } else {
-                eprintln!("failed to get matrix shape info, please re-input!");
-                print!("matrix shape (r, c): ");
-                std::io::Write::flush(&mut std::io::stdout()).expect("failed to flush stdout");
-            }
-        }
-        let mut m = Self::zeros(shape_info[0], shape_info[1])?;
-        let mut dcnt: usize = 0;
-        let mut mdata: Vec<Result<_, _>>;
-        println!("matrix data:");
-        while dcnt < m.data.len() {
-            rb.clear();
-            if std::io::stdin().read_line(&mut rb).is_ok() && {
-                mdata = rb
-                    .split(|c: char| c.is_whitespace())
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.trim().parse::<N>())
-                    .collect();
-                mdata
-                    .iter()
-                    .map(|v| v.is_ok())
-                    .fold(true, |acc, v| acc && v)
-            } {
-                mdata.iter().for_each(|v| {
-                    if dcnt < m.data.len() {
-                        if let Ok(v) = v {
-                            m.data[dcnt] = v.clone();
-                            dcnt += 1;
-                        }
-                    }
-                });
-            } else {
-                eprintln!("failed to get matrix data, please re-input!");
-                dcnt = 0;
-                println!("matrix data:");
-            }
-        }
-        Ok(m)
+        let mut s = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut s)?;
+        Self::from_text(&s)
     }
 
     pub fn p_change(n: usize, i: usize, j: usize) -> Result<Matrix<N>, RMatrixError> {
@@ -265,95 +163,142 @@ impl<N: Number> Matrix<N> {
         }
     }
 
-    pub fn dot(v1: &Vec<N>, v2: &Vec<N>) -> Result<N, RMatrixError> {
+    pub fn dot(v1: &[N], v2: &[N]) -> Result<N, RMatrixError> {
         if v1.len() == v2.len() {
-            Ok(std::iter::zip(v1, v2).map(|(&e1, &e2)| e1 * e2).sum())
+            Ok(std::iter::zip(v1, v2)
+                .map(|(e1, e2)| e1.clone() * e2.clone())
+                .sum())
         } else {
             Err(RMatrixError::LengthInconsistent(v1.len(), v2.len()))
         }
     }
 
-    pub fn outer(v1: &Vec<N>, v2: &Vec<N>) -> Result<Self, RMatrixError> {
+    pub fn outer(v1: &[N], v2: &[N]) -> Result<Self, RMatrixError> {
         let mut m = Self::zeros(v1.len(), v2.len())?;
-        for i in 0..v1.len() {
-            for j in 0..v2.len() {
-                m.set(v1[i] * v2[j], i + 1, j + 1)?;
+        for (i, e1) in v1.iter().enumerate() {
+            for (j, e2) in v2.iter().enumerate() {
+                m.set(e1.clone() * e2.clone(), i + 1, j + 1)?;
             }
         }
         Ok(m)
     }
+}
+
+impl<N: Number> Matrix<N> {
+    /// companion matrix of the polynomial with coefficients `c_0, ..., c_n` in ascending order
+    ///
+    /// ones are on the subdiagonal and the last column is `-c_i / c_n`,
+    /// so the characteristic polynomial is the monic polynomial and the eigenvalues are its roots,
+    /// exactly zero leading coefficients are ignored and the degree must be at least one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// // 2x^2 - 6x + 4 = 2 (x - 1) (x - 2)
+    /// let c = Matrix::companion(&[4.0, -6.0, 2.0])?;
+    /// assert_eq!(c, Matrix::from_vec(2, 2, vec![0.0, -2.0, 1.0, 3.0])?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn companion(coeffs: &[N]) -> Result<Self, RMatrixError> {
+        let n = match coeffs.iter().rposition(|c| *c != N::default()) {
+            Some(n) if n > 0 => n,
+            _ => return Err(RMatrixError::ShapeUnreasonable),
+        };
+        let mut m = Self::zeros(n, n)?;
+        for i in 2..=n {
+            m.set(N::one(), i, i - 1)?;
+        }
+        for (i, c) in coeffs[..n].iter().enumerate() {
+            m.set(-(c.clone() / coeffs[n].clone()), i + 1, n)?;
+        }
+        Ok(m)
+    }
+
+    /// general solution of `A * X = B`
+    ///
+    /// it is a particular solution with the free variables set to zero
+    /// and a basis of the null space of `A`, `None` if the solution is unique,
+    /// any combination of the basis can be added to a column of the particular solution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let a = Matrix::from_vec(1, 2, vec![1.0, 1.0])?;
+    /// let b = Matrix::from_vec(1, 1, vec![2.0])?;
+    /// let (x, n) = Matrix::general_solution(&a, &b)?;
+    /// assert_eq!(x, Matrix::from_vec(2, 1, vec![2.0, 0.0])?);
+    /// assert_eq!(n, Some(Matrix::from_vec(2, 1, vec![-1.0, 1.0])?));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn general_solution(
+        a: &Matrix<N>,
+        b: &Matrix<N>,
+    ) -> Result<(Matrix<N>, Option<Matrix<N>>), RMatrixError> {
+        if a.shape.row != b.shape.row {
+            return Err(RMatrixError::ShapeInconsistent(
+                a.dimensions(),
+                b.dimensions(),
+            ));
+        }
+        let x = a.row_reduce()?.particular_solution(b)?;
+        Ok((x, a.echelon_null_space()?))
+    }
 
     pub fn solve_linear_equations(a: &Matrix<N>, b: &Matrix<N>) -> Result<(), RMatrixError> {
-        fn format_vec_n<N: Number>(v: Vec<N>) -> String {
-            if v.len() == 0 {
+        fn format_vec_n<N: Ring>(v: Vec<N>) -> String {
+            if v.is_empty() {
                 "[]".to_owned()
             } else {
                 let mut s = String::from("[");
-                for i in 0..(v.len() - 1) {
-                    s.push_str(&format!("{}, ", v[i]));
+                for e in v.iter().take(v.len() - 1) {
+                    s.push_str(&format!("{}, ", e));
                 }
                 s.push_str(&format!("{}]", v[v.len() - 1]));
                 s
             }
         }
 
-        if a.shape.row > a.shape.col {
-            Err(RMatrixError::LinearEquationsNoSolution(a.dimensions()))
-        } else if a.shape.row != b.shape.row {
+        if a.shape.row != b.shape.row {
             Err(RMatrixError::ShapeInconsistent(
                 a.dimensions(),
                 b.dimensions(),
             ))
         } else {
-            let (m, pn) = a.row_reduce()?;
-            let mb = pn.times(&b)?;
-            let (r, c) = m.dimensions();
-
-            let mut bias = 0;
-            let mut has_soluion: Vec<bool> = Vec::with_capacity(c);
-            for _ in 0..c {
-                has_soluion.push(false);
-            }
-            let mut i = 1;
-            while i <= r && i + bias <= c {
-                if !m.get(i, i + bias)?.is_zero() {
-                    has_soluion[i + bias - 1] = true;
-                    i += 1;
-                } else {
-                    bias += 1;
-                };
-            }
-            let mut ans: Vec<String> = Vec::with_capacity(c);
-            bias = 0;
-            i = 0;
-            while i < r {
-                if has_soluion[i + bias] {
-                    ans.push(format_vec_n(mb.get_row(i + 1)?));
-                    for j in (i + bias)..c {
-                        if !has_soluion[j] {
-                            let v = m.get(i + 1, j + 1)?;
-                            ans[i + bias].push_str(&format!(
-                                " {} x{}",
-                                if (v - N::one()).is_zero() {
-                                    "-".to_owned()
-                                } else if (v + N::one()).is_zero() {
-                                    "+".to_owned()
-                                } else {
-                                    format!("{:+}", -v)
-                                },
-                                j + 1
-                            ))
+            let e = a.row_reduce()?;
+            let x = e.particular_solution(b)?;
+            for j in 1..=a.shape.col {
+                match e.pivot_row(j) {
+                    Some(i) => {
+                        let mut ans = format_vec_n(x.get_row(j)?);
+                        for &f in e.free.iter() {
+                            let v = e.matrix.get(i, f)?;
+                            if !v.is_zero() {
+                                ans.push_str(&format!(
+                                    " {} x{}",
+                                    if (v.clone() - N::one()).is_zero() {
+                                        "-".to_owned()
+                                    } else if (v.clone() + N::one()).is_zero() {
+                                        "+".to_owned()
+                                    } else {
+                                        format!("{:+}", -v)
+                                    },
+                                    f
+                                ))
+                            }
                         }
+                        println!("x{} = {}", j, ans);
                     }
-                    i += 1;
-                } else {
-                    ans.push(format!("x{}", i + bias + 1));
-                    bias += 1;
+                    None => println!("x{} = x{}", j, j),
                 }
             }
-            ans.iter().enumerate().for_each(|(i, v)| {
-                println!("x{} = {}", i + 1, v);
-            });
             Ok(())
         }
     }
@@ -374,9 +319,9 @@ impl Matrix<f64> {
     }
 }
 
-impl<N: Number> std::fmt::Display for Matrix<N> {
+impl<N: Ring> std::fmt::Display for Matrix<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "")?;
+        writeln!(f)?;
         for r in 1..=self.shape.row {
             write!(f, "[")?;
             for c in 1..=self.shape.col - 1 {
@@ -392,7 +337,7 @@ impl<N: Number> std::fmt::Display for Matrix<N> {
     }
 }
 
-impl<N: Number> PartialEq for Matrix<N> {
+impl<N: Ring> PartialEq for Matrix<N> {
     fn eq(&self, other: &Self) -> bool {
         self.data == other.data && self.shape == other.shape
     }
@@ -1,5 +1,45 @@
-use crate::complex::Complex;
+use crate::{
+    bigint::BigInt,
+    complex::Complex,
+    finite::{Gf, Gf2k},
+    rational::{big::BigRational, Rational},
+};
 
+/// commutative rings with a unit, which can be the elements of a matrix
+///
+/// integers like [`BigInt`] are rings, eliminations which divide need a [`Number`]
+///
+/// ```compile_fail
+/// # use rmatrix_ks::matrix::Matrix;
+/// # use rmatrix_ks::bigint::BigInt;
+/// let m = Matrix::from_vec(2, 2, [2, 1, 1, 2].map(BigInt::from).to_vec()).unwrap();
+/// // a truncating division would give 4, use `det_bareiss` instead
+/// let d = m.det();
+/// ```
+pub trait Ring:
+    std::ops::Add<Output = Self>
+    + std::ops::Sub<Output = Self>
+    + std::ops::Mul<Output = Self>
+    + std::ops::Neg<Output = Self>
+    + PartialEq
+    + Default
+    + Clone
+    + std::fmt::Debug
+    + std::fmt::Display
+    + std::str::FromStr
+    + std::iter::Sum
+{
+    fn one() -> Self;
+    fn neg_one() -> Self;
+    fn is_zero(&self) -> bool;
+}
+
+/// fields, every non-zero element can divide,
+/// so [`Matrix::det`](crate::matrix::Matrix::det) and
+/// [`Matrix::inverse`](crate::matrix::Matrix::inverse) are available
+///
+/// it has the same methods as [`Ring`], which it implies by a blanket implementation,
+/// so importing `Number` alone is enough to call them on numbers
 pub trait Number:
     std::ops::Add<Output = Self>
     + std::ops::Sub<Output = Self>
@@ -9,7 +49,6 @@ pub trait Number:
     + PartialEq
     + Default
     + Clone
-    + Copy
     + std::fmt::Debug
     + std::fmt::Display
     + std::str::FromStr
@@ -20,6 +59,123 @@ pub trait Number:
     fn is_zero(&self) -> bool;
 }
 
+impl<N: Number> Ring for N {
+    fn one() -> Self {
+        <N as Number>::one()
+    }
+
+    fn neg_one() -> Self {
+        <N as Number>::neg_one()
+    }
+
+    fn is_zero(&self) -> bool {
+        <N as Number>::is_zero(self)
+    }
+}
+
+/// ordered numbers, which can be the parts of a [`Complex`]
+pub trait Real: Number + PartialOrd {
+    /// absolute value
+    fn abs_value(&self) -> Self {
+        if *self < Self::default() {
+            -self.clone()
+        } else {
+            self.clone()
+        }
+    }
+}
+
+impl Real for f64 {}
+impl Real for f32 {}
+impl Real for Rational {}
+impl Real for BigRational {}
+
+/// floating point numbers, which have the elementary functions
+pub trait Float: Real + Copy {
+    const PI: Self;
+    const NAN: Self;
+
+    /// the nearest value to a double precision number
+    fn from_f64(v: f64) -> Self;
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn sinh(self) -> Self;
+    fn cosh(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn copysign(self, sign: Self) -> Self;
+}
+
+macro_rules! impl_float {
+    ($($t:ident),*) => {
+        $(
+            impl Float for $t {
+                const PI: Self = std::$t::consts::PI;
+                const NAN: Self = $t::NAN;
+
+                fn from_f64(v: f64) -> Self {
+                    v as $t
+                }
+
+                fn abs(self) -> Self {
+                    $t::abs(self)
+                }
+
+                fn sqrt(self) -> Self {
+                    $t::sqrt(self)
+                }
+
+                fn hypot(self, other: Self) -> Self {
+                    $t::hypot(self, other)
+                }
+
+                fn exp(self) -> Self {
+                    $t::exp(self)
+                }
+
+                fn ln(self) -> Self {
+                    $t::ln(self)
+                }
+
+                fn powf(self, n: Self) -> Self {
+                    $t::powf(self, n)
+                }
+
+                fn sin(self) -> Self {
+                    $t::sin(self)
+                }
+
+                fn cos(self) -> Self {
+                    $t::cos(self)
+                }
+
+                fn sinh(self) -> Self {
+                    $t::sinh(self)
+                }
+
+                fn cosh(self) -> Self {
+                    $t::cosh(self)
+                }
+
+                fn atan2(self, other: Self) -> Self {
+                    $t::atan2(self, other)
+                }
+
+                fn copysign(self, sign: Self) -> Self {
+                    $t::copysign(self, sign)
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(f32, f64);
+
 impl Number for f64 {
     fn one() -> Self {
         1.0f64
@@ -34,16 +190,126 @@ impl Number for f64 {
     }
 }
 
-impl Number for Complex {
+impl Number for f32 {
+    fn one() -> Self {
+        1.0f32
+    }
+
+    fn neg_one() -> Self {
+        -1.0f32
+    }
+
+    /// single precision keeps about seven digits, so the tolerance is wider than for `f64`
+    fn is_zero(&self) -> bool {
+        ((self.abs() * (10.0f32).powi(5)).trunc() as i32) == 0i32
+    }
+}
+
+impl<T: Real> Number for Complex<T> {
+    fn one() -> Self {
+        Complex::new(T::one(), T::default())
+    }
+
+    fn neg_one() -> Self {
+        Complex::new(T::neg_one(), T::default())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.re().is_zero() && self.im().is_zero()
+    }
+}
+
+impl Number for Rational {
+    fn one() -> Self {
+        Rational::from_integer(1)
+    }
+
+    fn neg_one() -> Self {
+        Rational::from_integer(-1)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.numer() == 0
+    }
+}
+
+impl<const P: u64> Number for Gf<P> {
+    fn one() -> Self {
+        Gf::new(1)
+    }
+
+    fn neg_one() -> Self {
+        Gf::new(-1)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value() == 0
+    }
+}
+
+impl<const POLY: u64> Number for Gf2k<POLY> {
     fn one() -> Self {
-        Complex::new(f64::one(), f64::default())
+        Gf2k::new(1)
     }
 
+    /// `-1 = 1` in characteristic two
     fn neg_one() -> Self {
-        Complex::new(f64::neg_one(), f64::default())
+        Gf2k::new(1)
     }
 
     fn is_zero(&self) -> bool {
-        self.norm().is_zero()
+        self.bits() == 0
+    }
+}
+
+/// machine integers, overflow panics in debug builds
+impl Ring for i64 {
+    fn one() -> Self {
+        1
+    }
+
+    fn neg_one() -> Self {
+        -1
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+}
+
+impl Ring for BigInt {
+    fn one() -> Self {
+        BigInt::from(1)
+    }
+
+    fn neg_one() -> Self {
+        BigInt::from(-1)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.signum() == 0
+    }
+}
+
+/// write `s` with the width and alignment flags of `f`, right aligned by default
+pub(crate) fn pad_width(f: &mut std::fmt::Formatter<'_>, s: &str) -> std::fmt::Result {
+    match (f.width(), f.align()) {
+        (Some(w), Some(std::fmt::Alignment::Left)) => write!(f, "{:<w$}", s),
+        (Some(w), Some(std::fmt::Alignment::Center)) => write!(f, "{:^w$}", s),
+        (Some(w), _) => write!(f, "{:>w$}", s),
+        (None, _) => write!(f, "{}", s),
+    }
+}
+
+/// write `s` with the sign and width flags of `f`, the precision is ignored
+pub(crate) fn pad_exact(
+    f: &mut std::fmt::Formatter<'_>,
+    negative: bool,
+    s: &str,
+) -> std::fmt::Result {
+    if f.sign_plus() && !negative {
+        pad_width(f, &format!("+{}", s))
+    } else {
+        pad_width(f, s)
     }
 }
@@ -0,0 +1,183 @@
+//! basic implementation of Polynomial
+
+use crate::{
+    error::RMatrixError,
+    matrix::Matrix,
+    number::{Number, Ring},
+    polynomial::Polynomial,
+};
+
+impl<N: Ring> Polynomial<N> {
+    /// generate a polynomial from its coefficients in ascending order
+    ///
+    /// only exactly zero leading coefficients are dropped,
+    /// small floating point coefficients are kept whatever their size
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, -2.0, 0.0, 1.0, 0.0]);
+    /// assert_eq!(p.degree(), Some(3));
+    /// assert_eq!(p.to_string(), "x^3 - 2x + 1");
+    /// assert_eq!(Polynomial::new(vec![1.0, 1e-9]).degree(), Some(1));
+    /// ```
+    pub fn new(mut coeffs: Vec<N>) -> Self {
+        while coeffs.last().is_some_and(|c| *c == N::default()) {
+            coeffs.pop();
+        }
+        Polynomial { coeffs }
+    }
+
+    /// the zero polynomial
+    pub fn zero() -> Self {
+        Polynomial { coeffs: Vec::new() }
+    }
+
+    /// the constant polynomial `c`
+    pub fn constant(c: N) -> Self {
+        Polynomial::new(vec![c])
+    }
+
+    /// the monomial `c x^n`
+    pub fn monomial(c: N, n: usize) -> Self {
+        let mut coeffs = vec![N::default(); n];
+        coeffs.push(c);
+        Polynomial::new(coeffs)
+    }
+
+    /// the polynomial `x`
+    pub fn x() -> Self {
+        Polynomial::monomial(N::one(), 1)
+    }
+
+    /// coefficients in ascending order, empty for the zero polynomial
+    pub fn coeffs(&self) -> &[N] {
+        &self.coeffs
+    }
+
+    /// coefficient of `x^n`
+    pub fn coeff(&self, n: usize) -> N {
+        self.coeffs.get(n).cloned().unwrap_or_default()
+    }
+
+    /// degree, `None` for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// coefficient of the highest power, zero for the zero polynomial
+    pub fn leading(&self) -> N {
+        self.coeffs.last().cloned().unwrap_or_default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// multiply every coefficient by `k`
+    pub fn scale(&self, k: N) -> Self {
+        Polynomial::new(self.coeffs.iter().map(|c| c.clone() * k.clone()).collect())
+    }
+
+    /// value at `x` by Horner's method
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, -2.0, 0.0, 1.0]);
+    /// assert_eq!(p.eval(&2.0), 5.0);
+    /// ```
+    pub fn eval(&self, x: &N) -> N {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(N::default(), |acc, c| acc * x.clone() + c.clone())
+    }
+
+    /// value at a square matrix by Horner's method, `c_0` stands for `c_0 I`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::matrix::Matrix;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// let m = Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0])?;
+    /// // Cayley–Hamilton
+    /// assert_eq!(m.char_poly()?.eval_matrix(&m)?, Matrix::zeros(2, 2)?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn eval_matrix(&self, a: &Matrix<N>) -> Result<Matrix<N>, RMatrixError> {
+        let (r, c) = a.dimensions();
+        if r != c {
+            return Err(RMatrixError::MatrixNotSquare);
+        }
+        let eye = Matrix::<N>::eyes(r, c)?;
+        self.coeffs
+            .iter()
+            .rev()
+            .try_fold(Matrix::zeros(r, c)?, |acc, k| {
+                acc.times(a)?.plus(&eye.smul(k.clone())?)
+            })
+    }
+
+    /// the derivative
+    pub fn derivative(&self) -> Self {
+        let mut n = N::default();
+        let coeffs = self
+            .coeffs
+            .iter()
+            .skip(1)
+            .map(|c| {
+                n = n.clone() + N::one();
+                c.clone() * n.clone()
+            })
+            .collect();
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<N: Number> Polynomial<N> {
+    /// divide by the leading coefficient, `None` for the zero polynomial
+    pub fn monic(&self) -> Option<Self> {
+        let l = self.coeffs.last()?;
+        Some(self.scale(N::one() / l.clone()))
+    }
+
+    /// quotient and remainder of the long division, `None` for a zero divisor
+    ///
+    /// the degree of the remainder is less than the degree of the divisor,
+    /// coefficients are divided by the leading coefficient of the divisor,
+    /// which is exact in fields like [`Rational`](crate::rational::Rational)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::polynomial::Polynomial;
+    /// let p = Polynomial::new(vec![-1.0, 0.0, 0.0, 1.0]);
+    /// let (q, r) = p.div_rem(&Polynomial::new(vec![-1.0, 1.0])).unwrap();
+    /// assert_eq!(q, Polynomial::new(vec![1.0, 1.0, 1.0]));
+    /// assert!(r.is_zero());
+    /// ```
+    pub fn div_rem(&self, rhs: &Self) -> Option<(Self, Self)> {
+        let d = rhs.degree()?;
+        let lead = rhs.leading();
+        let mut r = self.coeffs.clone();
+        if r.len() <= d {
+            return Some((Polynomial::zero(), self.clone()));
+        }
+        let mut q = vec![N::default(); r.len() - d];
+        for k in (0..q.len()).rev() {
+            let t = r[k + d].clone() / lead.clone();
+            for (j, c) in rhs.coeffs.iter().enumerate() {
+                r[k + j] = r[k + j].clone() - t.clone() * c.clone();
+            }
+            q[k] = t;
+        }
+        r.truncate(d);
+        Some((Polynomial::new(q), Polynomial::new(r)))
+    }
+}
@@ -0,0 +1,27 @@
+//! type of polynomials in one variable
+
+pub mod base;
+pub mod num;
+pub mod roots;
+pub mod utils;
+
+/// a polynomial `c_0 + c_1 x + ... + c_n x^n` with coefficients of a [`Ring`](crate::number::Ring) type
+///
+/// the coefficients are kept in ascending order without zero leading coefficients,
+/// so the zero polynomial has no coefficient
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        from = "Vec<N>",
+        into = "Vec<N>",
+        bound(
+            serialize = "N: crate::number::Ring + serde::Serialize",
+            deserialize = "N: crate::number::Ring + serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct Polynomial<N> {
+    coeffs: Vec<N>,
+}
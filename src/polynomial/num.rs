@@ -0,0 +1,46 @@
+//! arithmetic of Polynomial
+
+use crate::{number::Ring, polynomial::Polynomial};
+
+impl<N: Ring> std::ops::Add for Polynomial<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let n = self.coeffs.len().max(rhs.coeffs.len());
+        Polynomial::new((0..n).map(|i| self.coeff(i) + rhs.coeff(i)).collect())
+    }
+}
+
+impl<N: Ring> std::ops::Sub for Polynomial<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let n = self.coeffs.len().max(rhs.coeffs.len());
+        Polynomial::new((0..n).map(|i| self.coeff(i) - rhs.coeff(i)).collect())
+    }
+}
+
+impl<N: Ring> std::ops::Mul for Polynomial<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.is_zero() || rhs.is_zero() {
+            return Polynomial::zero();
+        }
+        let mut coeffs = vec![N::default(); self.coeffs.len() + rhs.coeffs.len() - 1];
+        for (i, a) in self.coeffs.iter().enumerate() {
+            for (j, b) in rhs.coeffs.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j].clone() + a.clone() * b.clone();
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<N: Ring> std::ops::Neg for Polynomial<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Polynomial::new(self.coeffs.into_iter().map(|c| -c).collect())
+    }
+}
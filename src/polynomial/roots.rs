@@ -0,0 +1,127 @@
+//! roots of polynomials as eigenvalues of companion matrices
+
+use crate::{complex::Complex, error::RMatrixError, matrix::Matrix, polynomial::Polynomial};
+
+/// roots of the zero polynomial are undefined
+fn check_nonzero<N>(p: &Polynomial<N>) -> Result<(), RMatrixError> {
+    if p.coeffs.is_empty() {
+        Err(RMatrixError::FunctionUndefined(
+            "every number is a root of the zero polynomial".to_string(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// number of zero roots, the leading exactly zero coefficients in ascending order
+fn zero_roots<N: crate::number::Ring>(p: &Polynomial<N>) -> usize {
+    p.coeffs.iter().take_while(|c| **c == N::default()).count()
+}
+
+impl Polynomial<Complex> {
+    /// all roots with multiplicity, the eigenvalues of the companion matrix
+    ///
+    /// the eigenvalues are the diagonal of the complex Schur form,
+    /// zero constant coefficients give exact zero roots
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::polynomial::Polynomial;
+    /// # use rmatrix_ks::complex::Complex;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// // x^2 - 2i x - 1 = (x - i)^2
+    /// let p = Polynomial::new(vec![Complex::from(-1.0), Complex::new(0.0, -2.0), Complex::from(1.0)]);
+    /// for r in p.roots()? {
+    ///     assert!((r - Complex::new(0.0, 1.0)).norm() < 1e-7);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn roots(&self) -> Result<Vec<Complex>, RMatrixError> {
+        check_nonzero(self)?;
+        let k = zero_roots(self);
+        let mut roots = vec![Complex::default(); k];
+        if self.coeffs.len() - k > 1 {
+            let (_, t) = Matrix::companion(&self.coeffs[k..])?.schur()?;
+            let n = t.dimensions().0;
+            for i in 1..=n {
+                roots.push(t.get(i, i)?);
+            }
+        }
+        Ok(roots)
+    }
+
+    /// roots refined by at most `steps` Newton iterations each
+    ///
+    /// an iteration is only taken if it makes the value of the polynomial smaller,
+    /// which cleans up rounding errors of the eigenvalues of badly scaled companion matrices
+    pub fn roots_polished(&self, steps: usize) -> Result<Vec<Complex>, RMatrixError> {
+        Ok(self.polish(self.roots()?, steps))
+    }
+
+    fn polish(&self, roots: Vec<Complex>, steps: usize) -> Vec<Complex> {
+        let d = self.derivative();
+        roots
+            .into_iter()
+            .map(|mut z| {
+                let mut value = self.eval(&z).norm();
+                for _ in 0..steps {
+                    let slope = d.eval(&z);
+                    if value == 0.0 || slope.norm() == 0.0 {
+                        break;
+                    }
+                    let next = z - self.eval(&z) / slope;
+                    let next_value = self.eval(&next).norm();
+                    if next_value >= value {
+                        break;
+                    }
+                    (z, value) = (next, next_value);
+                }
+                z
+            })
+            .collect()
+    }
+}
+
+impl Polynomial<f64> {
+    /// all roots with multiplicity, the eigenvalues of the companion matrix
+    ///
+    /// complex roots come in conjugate pairs, adjacent with the positive imaginary part last,
+    /// zero constant coefficients give exact zero roots
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::polynomial::Polynomial;
+    /// # use rmatrix_ks::error::RMatrixError;
+    /// # fn main() -> Result<(), RMatrixError> {
+    /// // x^3 - x = x (x - 1) (x + 1)
+    /// let p = Polynomial::new(vec![0.0, -1.0, 0.0, 1.0]);
+    /// let mut roots = p.roots()?.iter().map(|r| r.re()).collect::<Vec<_>>();
+    /// roots.sort_by(f64::total_cmp);
+    /// assert!((roots[0] + 1.0).abs() < 1e-12 && roots[1] == 0.0 && (roots[2] - 1.0).abs() < 1e-12);
+    /// // x^2 + 1
+    /// let i = Polynomial::new(vec![1.0, 0.0, 1.0]).roots()?;
+    /// assert!((i[1].im() - 1.0).abs() < 1e-12 && (i[0] + i[1]).norm() < 1e-12);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn roots(&self) -> Result<Vec<Complex>, RMatrixError> {
+        check_nonzero(self)?;
+        let k = zero_roots(self);
+        let mut roots = vec![Complex::default(); k];
+        if self.coeffs.len() - k > 1 {
+            roots.extend(Matrix::companion(&self.coeffs[k..])?.eigenvalues()?);
+        }
+        Ok(roots)
+    }
+
+    /// roots refined by at most `steps` Newton iterations each,
+    /// see [`Polynomial::<Complex>::roots_polished`]
+    pub fn roots_polished(&self, steps: usize) -> Result<Vec<Complex>, RMatrixError> {
+        let p = Polynomial::new(self.coeffs.iter().map(|&c| Complex::from(c)).collect());
+        Ok(p.polish(self.roots()?, steps))
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{
+    number::{pad_width, Ring},
+    polynomial::Polynomial,
+};
+
+impl<N: Ring> Default for Polynomial<N> {
+    fn default() -> Self {
+        Polynomial::zero()
+    }
+}
+
+impl<N: Ring> From<Vec<N>> for Polynomial<N> {
+    fn from(coeffs: Vec<N>) -> Self {
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<N> From<Polynomial<N>> for Vec<N> {
+    fn from(p: Polynomial<N>) -> Self {
+        p.coeffs
+    }
+}
+
+/// terms in descending powers of `x`, like `x^3 - 2x + 1`
+///
+/// exactly zero terms and unit coefficients are omitted,
+/// a coefficient with a sign inside like a complex number
+/// is put in parentheses, the precision applies to every coefficient and the width to the whole
+///
+/// # Examples
+///
+/// ```
+/// # use rmatrix_ks::polynomial::Polynomial;
+/// # use rmatrix_ks::complex::Complex;
+/// let p = Polynomial::new(vec![0.5, 0.0, -1.0]);
+/// assert_eq!(p.to_string(), "-x^2 + 0.5");
+/// assert_eq!(format!("{:.2}", p), "-x^2 + 0.50");
+/// let c = Polynomial::new(vec![Complex::new(1.0, -1.0), Complex::from(1.0)]);
+/// assert_eq!(c.to_string(), "x + (1-1I)");
+/// assert_eq!(Polynomial::<f64>::zero().to_string(), "0");
+/// assert_eq!(Polynomial::new(vec![2e-10, -3e-5, 1.0]).to_string(), "x^2 - 0.00003x + 0.0000000002");
+/// ```
+impl<N: Ring> std::fmt::Display for Polynomial<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = |c: &N| match f.precision() {
+            Some(p) => format!("{:.p$}", c),
+            None => c.to_string(),
+        };
+        let mut s = String::new();
+        for (n, c) in self.coeffs.iter().enumerate().rev() {
+            if *c == N::default() {
+                continue;
+            }
+            let unit = if *c == N::one() {
+                Some(false)
+            } else if *c == N::neg_one() {
+                Some(true)
+            } else {
+                None
+            };
+            let t = text(c);
+            let (negative, coeff) = match unit {
+                Some(negative) if n == 0 => (negative, text(&N::one())),
+                Some(negative) => (negative, String::new()),
+                None if t.get(1..).is_some_and(|r| r.contains(['+', '-'])) => {
+                    (false, format!("({})", t))
+                }
+                None => match t.strip_prefix('-') {
+                    Some(r) => (true, r.to_string()),
+                    None => (false, t),
+                },
+            };
+            s.push_str(match (s.is_empty(), negative) {
+                (true, false) => "",
+                (true, true) => "-",
+                (false, false) => " + ",
+                (false, true) => " - ",
+            });
+            s.push_str(&coeff);
+            match n {
+                0 => {}
+                1 => s.push('x'),
+                _ => s.push_str(&format!("x^{}", n)),
+            }
+        }
+        if s.is_empty() {
+            s.push('0');
+        }
+        pad_width(f, &s)
+    }
+}
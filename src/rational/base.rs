@@ -0,0 +1,115 @@
+//! basic implementation of Rational
+
+use crate::rational::Rational;
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl Rational {
+    /// generate a rational number, reduced to lowest terms
+    ///
+    /// # Panics
+    ///
+    /// panics if `den` is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rmatrix_ks::rational::Rational;
+    /// let r = Rational::new(6, -4);
+    /// assert_eq!((r.numer(), r.denom()), (-3, 2));
+    /// ```
+    pub fn new(num: i64, den: i64) -> Self {
+        Rational::checked_new(num as i128, den as i128)
+            .expect("rational number with zero denominator or overflow")
+    }
+
+    /// reduce `num / den`, `None` if `den` is zero or the result does not fit into `i64`
+    pub(crate) fn checked_new(num: i128, den: i128) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+        let g = gcd(num.unsigned_abs(), den.unsigned_abs()) as i128;
+        let (mut num, mut den) = (num / g, den / g);
+        if den < 0 {
+            num = num.checked_neg()?;
+            den = den.checked_neg()?;
+        }
+        Some(Rational {
+            num: i64::try_from(num).ok()?,
+            den: i64::try_from(den).ok()?,
+        })
+    }
+
+    /// an integer as a rational number
+    pub fn from_integer(n: i64) -> Self {
+        Rational { num: n, den: 1 }
+    }
+
+    /// get the numerator
+    pub fn numer(&self) -> i64 {
+        self.num
+    }
+
+    /// get the denominator, always positive
+    pub fn denom(&self) -> i64 {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    /// nearest floating point number
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    /// `1 / self`, `None` for zero
+    pub fn recip(&self) -> Option<Self> {
+        Rational::checked_new(self.den as i128, self.num as i128)
+    }
+
+    pub fn abs(&self) -> Self {
+        if self.num < 0 {
+            -*self
+        } else {
+            *self
+        }
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let (a, b, c, d) = self.widen(rhs);
+        Rational::checked_new(a.checked_mul(d)?.checked_add(c.checked_mul(b)?)?, b * d)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let (a, b, c, d) = self.widen(rhs);
+        Rational::checked_new(a.checked_mul(d)?.checked_sub(c.checked_mul(b)?)?, b * d)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let (a, b, c, d) = self.widen(rhs);
+        Rational::checked_new(a * c, b * d)
+    }
+
+    /// `None` if `rhs` is zero or on overflow
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        let (a, b, c, d) = self.widen(rhs);
+        Rational::checked_new(a * d, b * c)
+    }
+
+    /// both fractions with `i128` parts
+    fn widen(self, rhs: Self) -> (i128, i128, i128, i128) {
+        (
+            self.num as i128,
+            self.den as i128,
+            rhs.num as i128,
+            rhs.den as i128,
+        )
+    }
+}
@@ -0,0 +1,250 @@
+//! rational number of unbounded size
+
+use crate::{
+    bigint::BigInt,
+    error::RMatrixError,
+    number::{pad_exact, Number, Ring},
+    rational::Rational,
+};
+
+/// a fraction of two [`BigInt`] in lowest terms with a positive denominator
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BigRational {
+    num: BigInt,
+    den: BigInt,
+}
+
+impl BigRational {
+    /// generate a rational number, reduced to lowest terms
+    ///
+    /// # Panics
+    ///
+    /// panics if `den` is zero
+    pub fn new(num: BigInt, den: BigInt) -> Self {
+        if den.is_zero() {
+            panic!("rational number with zero denominator");
+        }
+        let g = num.gcd(&den);
+        let (num, den) = (num / g.clone(), den / g);
+        if den.is_negative() {
+            BigRational {
+                num: -num,
+                den: -den,
+            }
+        } else {
+            BigRational { num, den }
+        }
+    }
+
+    pub fn from_integer(n: BigInt) -> Self {
+        BigRational {
+            num: n,
+            den: BigInt::one(),
+        }
+    }
+
+    /// get the numerator
+    pub fn numer(&self) -> &BigInt {
+        &self.num
+    }
+
+    /// get the denominator, always positive
+    pub fn denom(&self) -> &BigInt {
+        &self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == BigInt::one()
+    }
+
+    /// nearest floating point number
+    pub fn to_f64(&self) -> f64 {
+        self.num.to_f64() / self.den.to_f64()
+    }
+
+    /// `1 / self`, `None` for zero
+    pub fn recip(&self) -> Option<Self> {
+        if self.num.is_zero() {
+            None
+        } else {
+            Some(BigRational::new(self.den.clone(), self.num.clone()))
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        BigRational {
+            num: self.num.abs(),
+            den: self.den.clone(),
+        }
+    }
+}
+
+impl From<BigInt> for BigRational {
+    fn from(n: BigInt) -> Self {
+        BigRational::from_integer(n)
+    }
+}
+
+impl From<Rational> for BigRational {
+    fn from(r: Rational) -> Self {
+        BigRational {
+            num: BigInt::from(r.numer()),
+            den: BigInt::from(r.denom()),
+        }
+    }
+}
+
+impl From<i64> for BigRational {
+    fn from(n: i64) -> Self {
+        BigRational::from_integer(BigInt::from(n))
+    }
+}
+
+impl Default for BigRational {
+    fn default() -> Self {
+        BigRational::from_integer(BigInt::default())
+    }
+}
+
+impl std::ops::Add for BigRational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.den == rhs.den {
+            BigRational::new(self.num + rhs.num, self.den)
+        } else {
+            BigRational::new(
+                self.num * rhs.den.clone() + rhs.num * self.den.clone(),
+                self.den * rhs.den,
+            )
+        }
+    }
+}
+
+impl std::ops::Sub for BigRational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl std::ops::Mul for BigRational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // cancel crosswise first to keep the products small
+        let g1 = self.num.gcd(&rhs.den);
+        let g2 = rhs.num.gcd(&self.den);
+        if g1.is_zero() || g2.is_zero() {
+            return BigRational::default();
+        }
+        BigRational::new(
+            (self.num / g1.clone()) * (rhs.num / g2.clone()),
+            (self.den / g2) * (rhs.den / g1),
+        )
+    }
+}
+
+impl std::ops::Div for BigRational {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        match rhs.recip() {
+            Some(r) => std::ops::Mul::mul(self, r),
+            None => panic!("rational division by zero"),
+        }
+    }
+}
+
+impl std::ops::Neg for BigRational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        BigRational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl PartialOrd for BigRational {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigRational {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.num.clone() * other.den.clone()).cmp(&(other.num.clone() * self.den.clone()))
+    }
+}
+
+impl std::fmt::Display for BigRational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = if self.is_integer() {
+            self.num.to_string()
+        } else {
+            format!("{}/{}", self.num, self.den)
+        };
+        pad_exact(f, self.num.is_negative(), &s)
+    }
+}
+
+/// parse `"3/4"`, `"-2"` or a decimal like `"0.125"`, of any length
+impl std::str::FromStr for BigRational {
+    type Err = RMatrixError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let failed = || RMatrixError::ParseFailed(value.to_owned());
+        let v = value.trim();
+        match v.split_once('/') {
+            Some((n, d)) => {
+                let d = d.parse::<BigInt>()?;
+                if d.is_zero() {
+                    return Err(failed());
+                }
+                Ok(BigRational::new(n.parse()?, d))
+            }
+            None => match v.split_once('.') {
+                Some((int, frac)) => {
+                    if frac.starts_with(['+', '-']) || (int.is_empty() && frac.is_empty()) {
+                        return Err(failed());
+                    }
+                    let digits = match int {
+                        "" | "+" | "-" => format!("{}0{}", int, frac),
+                        _ => format!("{}{}", int, frac),
+                    };
+                    let den = BigInt::from(10).pow(frac.len() as u32);
+                    Ok(BigRational::new(digits.parse().map_err(|_| failed())?, den))
+                }
+                None => Ok(BigRational::from_integer(v.parse()?)),
+            },
+        }
+    }
+}
+
+impl std::iter::Sum for BigRational {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let mut n = BigRational::default();
+        for i in iter {
+            n = n + i;
+        }
+        n
+    }
+}
+
+impl Number for BigRational {
+    fn one() -> Self {
+        BigRational::from_integer(BigInt::one())
+    }
+
+    fn neg_one() -> Self {
+        BigRational::from_integer(BigInt::neg_one())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+}
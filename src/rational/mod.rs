@@ -0,0 +1,17 @@
+//! type of exact rational number
+
+pub mod base;
+pub mod big;
+pub mod num;
+pub mod utils;
+
+/// a fraction `num / den` in lowest terms with a positive denominator
+///
+/// arithmetic is carried out in `i128` and checked,
+/// the operators panic if the reduced result does not fit into `i64`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rational {
+    num: i64,
+    den: i64,
+}
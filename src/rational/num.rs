@@ -0,0 +1,49 @@
+//! implementation of Number for Rational
+
+use crate::rational::Rational;
+
+impl std::ops::Add for Rational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("rational addition overflowed")
+    }
+}
+
+impl std::ops::Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("rational subtraction overflowed")
+    }
+}
+
+impl std::ops::Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("rational multiplication overflowed")
+    }
+}
+
+impl std::ops::Div for Rational {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        if rhs.numer() == 0 {
+            panic!("rational division by zero");
+        }
+        self.checked_div(rhs).expect("rational division overflowed")
+    }
+}
+
+impl std::ops::Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Rational::checked_new(-(self.numer() as i128), self.denom() as i128)
+            .expect("rational negation overflowed")
+    }
+}
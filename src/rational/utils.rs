@@ -0,0 +1,97 @@
+use crate::{error::RMatrixError, number::pad_exact, rational::Rational};
+
+impl Default for Rational {
+    fn default() -> Self {
+        Rational::from_integer(0)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Rational::from_integer(n)
+    }
+}
+
+impl From<i32> for Rational {
+    fn from(n: i32) -> Self {
+        Rational::from_integer(n as i64)
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // denominators are positive, so cross multiplication keeps the order
+        (self.numer() as i128 * other.denom() as i128)
+            .cmp(&(other.numer() as i128 * self.denom() as i128))
+    }
+}
+
+/// `n / d` for integers, or `n` alone
+impl std::fmt::Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = if self.is_integer() {
+            self.numer().to_string()
+        } else {
+            format!("{}/{}", self.numer(), self.denom())
+        };
+        pad_exact(f, self.numer() < 0, &s)
+    }
+}
+
+/// parse `"3/4"`, `"-2"` or a decimal like `"0.125"`
+///
+/// # Examples
+///
+/// ```
+/// # use rmatrix_ks::rational::Rational;
+/// assert_eq!("6/-8".parse::<Rational>().unwrap(), Rational::new(-3, 4));
+/// assert_eq!("-0.125".parse::<Rational>().unwrap(), Rational::new(-1, 8));
+/// assert!("1/0".parse::<Rational>().is_err());
+/// ```
+impl std::str::FromStr for Rational {
+    type Err = RMatrixError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let failed = || RMatrixError::ParseFailed(value.to_owned());
+        let v = value.trim();
+        let (num, den) = match v.split_once('/') {
+            Some((n, d)) => (
+                n.trim().parse::<i64>().map_err(|_| failed())? as i128,
+                d.trim().parse::<i64>().map_err(|_| failed())? as i128,
+            ),
+            None => match v.split_once('.') {
+                Some((int, frac)) => {
+                    let (negative, int) = match int.strip_prefix('-') {
+                        Some(int) => (true, int),
+                        None => (false, int.strip_prefix('+').unwrap_or(int)),
+                    };
+                    let digits = format!("{}{}", int, frac);
+                    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                        return Err(failed());
+                    }
+                    let num = digits.parse::<i128>().map_err(|_| failed())?;
+                    let den = 10i128.checked_pow(frac.len() as u32).ok_or_else(failed)?;
+                    (if negative { -num } else { num }, den)
+                }
+                None => (v.parse::<i64>().map_err(|_| failed())? as i128, 1),
+            },
+        };
+        Rational::checked_new(num, den).ok_or_else(failed)
+    }
+}
+
+impl std::iter::Sum for Rational {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let mut n = Rational::default();
+        for i in iter {
+            n = n + i;
+        }
+        n
+    }
+}
@@ -1 +1,10 @@
+use rmatrix_ks::{matrix::Matrix, number::Number};
 
+/// whether two matrices have the same shape and element-wise zero difference
+pub fn approx_eq<N: Number>(a: &Matrix<N>, b: &Matrix<N>) -> bool {
+    a.dimensions() == b.dimensions()
+        && (1..=a.dimensions().0).all(|i| {
+            std::iter::zip(a.get_row(i).unwrap(), b.get_row(i).unwrap())
+                .all(|(x, y)| (x - y).is_zero())
+        })
+}
@@ -3,7 +3,17 @@ mod common;
 use std::f64::consts::PI;
 
 use rand::{thread_rng, Rng};
-use rmatrix_ks::{matrix::Matrix, number::Number};
+use rmatrix_ks::{
+    bigint::BigInt,
+    bitmatrix::BitMatrix,
+    complex::Complex,
+    error::RMatrixError,
+    finite::{Gf, Gf2k},
+    matrix::{gram_schmidt::GramSchmidtMode, Matrix},
+    number::Number,
+    polynomial::Polynomial,
+    rational::{big::BigRational, Rational},
+};
 
 #[test]
 fn tr_ab_equals_tr_ba() {
@@ -16,3 +26,1785 @@ fn tr_ab_equals_tr_ba() {
         assert!(r.is_zero(), "{}?", r)
     }
 }
+
+fn npy_bytes(descr: &str, fortran_order: bool, shape: &str, payload: &[u8]) -> Vec<u8> {
+    let header = format!(
+        "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}\n",
+        descr,
+        if fortran_order { "True" } else { "False" },
+        shape
+    );
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(payload);
+    bytes
+}
+
+#[test]
+fn npy_dtypes_and_orders() {
+    // big-endian int32 in fortran order
+    let payload: Vec<u8> = [1i32, 3, 2, -4]
+        .iter()
+        .flat_map(|v| v.to_be_bytes())
+        .collect();
+    let bytes = npy_bytes(">i4", true, "(2, 2)", &payload);
+    let m = Matrix::<f64>::read_npy_from(&mut bytes.as_slice()).unwrap();
+    assert_eq!(
+        m,
+        Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, -4.0]).unwrap()
+    );
+
+    // 1-D arrays are column vectors
+    let payload: Vec<u8> = [0.5f32, 1.5].iter().flat_map(|v| v.to_le_bytes()).collect();
+    let bytes = npy_bytes("<f4", false, "(2,)", &payload);
+    let m = Matrix::<f64>::read_npy_from(&mut bytes.as_slice()).unwrap();
+    assert_eq!(m.dimensions(), (2, 1));
+
+    // complex data can only be loaded into complex matrices
+    let payload: Vec<u8> = [1.0f64, -2.0]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    let bytes = npy_bytes("<c16", false, "(1, 1)", &payload);
+    assert!(Matrix::<f64>::read_npy_from(&mut bytes.as_slice()).is_err());
+    let m = Matrix::<Complex>::read_npy_from(&mut bytes.as_slice()).unwrap();
+    assert_eq!(m.get(1, 1).unwrap(), Complex::new(1.0, -2.0));
+
+    let bytes = npy_bytes("<f8", false, "(1, 1, 1)", &[0u8; 8]);
+    assert!(matches!(
+        Matrix::<f64>::read_npy_from(&mut bytes.as_slice()),
+        Err(RMatrixError::DimensionUnsupported(_))
+    ));
+
+    // corrupt shapes fail without allocating the claimed size
+    let bytes = npy_bytes("<f8", false, "(4611686018427387904, 8)", &[0u8; 8]);
+    assert!(matches!(
+        Matrix::<f64>::read_npy_from(&mut bytes.as_slice()),
+        Err(RMatrixError::DimensionUnsupported(_))
+    ));
+    let bytes = npy_bytes("<f8", false, "(1000000000, 1000000000)", &[0u8; 8]);
+    assert!(matches!(
+        Matrix::<f64>::read_npy_from(&mut bytes.as_slice()),
+        Err(RMatrixError::IoFailed(_))
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip_and_validation() {
+    let mut m = Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+    m.tag = "weights".to_owned();
+    let json = serde_json::to_string(&m).unwrap();
+    let back: Matrix<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, m);
+    assert_eq!(back.tag, "weights");
+
+    let c = Complex::new(1.0, -2.0);
+    let back: Complex = serde_json::from_str(&serde_json::to_string(&c).unwrap()).unwrap();
+    assert_eq!(back, c);
+
+    // polynomials are their coefficients, trailing zeros are dropped on input
+    let p = Polynomial::new(vec![1.0, 0.0, -2.0]);
+    assert_eq!(serde_json::to_string(&p).unwrap(), "[1.0,0.0,-2.0]");
+    let back: Polynomial<f64> = serde_json::from_str("[1.0,0.0,-2.0,0.0]").unwrap();
+    assert_eq!(back, p);
+
+    let short = r#"{"data":[1.0,2.0,3.0],"shape":{"row":2,"col":2},"tag":"t"}"#;
+    assert!(serde_json::from_str::<Matrix<f64>>(short).is_err());
+    let empty = r#"{"data":[],"shape":{"row":0,"col":2},"tag":"t"}"#;
+    assert!(serde_json::from_str::<Matrix<f64>>(empty).is_err());
+    let huge = r#"{"data":[],"shape":{"row":4294967296,"col":4294967296},"tag":"t"}"#;
+    assert!(serde_json::from_str::<Matrix<f64>>(huge).is_err());
+
+    // finite field elements must already be reduced
+    let g = Gf::<7>::new(-1);
+    assert_eq!(serde_json::to_string(&g).unwrap(), "6");
+    assert_eq!(serde_json::from_str::<Gf<7>>("6").unwrap(), g);
+    assert!(serde_json::from_str::<Gf<7>>("100").is_err());
+    let b = Gf2k::<0x11B>::new(0x53);
+    assert_eq!(
+        serde_json::from_str::<Gf2k<0x11B>>(&serde_json::to_string(&b).unwrap()).unwrap(),
+        b
+    );
+    assert!(serde_json::from_str::<Gf2k<0x11B>>("256").is_err());
+}
+
+#[test]
+fn binary_round_trip_and_checksum() {
+    use rmatrix_ks::matrix::binary::BinaryOptions;
+
+    let mut m = Matrix::<f64>::rand(3, 4, -PI, PI).unwrap();
+    m.tag = "checkpoint".to_owned();
+    for options in [
+        BinaryOptions::default(),
+        BinaryOptions {
+            big_endian: true,
+            checksum: false,
+        },
+    ] {
+        let mut buffer = Vec::new();
+        m.write_to_with(&mut buffer, options).unwrap();
+        let back = Matrix::<f64>::read_from(&mut buffer.as_slice()).unwrap();
+        assert_eq!(back, m);
+        assert_eq!(back.tag, m.tag);
+    }
+
+    let mut buffer = Vec::new();
+    m.write_to(&mut buffer).unwrap();
+    let last = buffer.len() - 5;
+    buffer[last] ^= 0xFF;
+    assert!(matches!(
+        Matrix::<f64>::read_from(&mut buffer.as_slice()),
+        Err(RMatrixError::ChecksumMismatch(_, _))
+    ));
+
+    // exact and finite field elements
+    let q = Matrix::from_vec(
+        1,
+        3,
+        vec![
+            Rational::new(-3, 4),
+            Rational::new(5, 1),
+            Rational::default(),
+        ],
+    )
+    .unwrap();
+    let big = Matrix::from_vec(
+        1,
+        3,
+        vec![BigInt::from(-1), BigInt::from(7).pow(40), BigInt::default()],
+    )
+    .unwrap();
+    let fraction = Matrix::from_vec(
+        1,
+        1,
+        vec![BigRational::new(BigInt::from(2).pow(70), BigInt::from(-3))],
+    )
+    .unwrap();
+    let gf = Matrix::from_vec(1, 2, vec![Gf::<7>::new(3), Gf::new(6)]).unwrap();
+    let aes = Matrix::from_vec(1, 2, vec![Gf2k::<0x11B>::new(0x53), Gf2k::new(0xCA)]).unwrap();
+    for options in [
+        BinaryOptions::default(),
+        BinaryOptions {
+            big_endian: true,
+            checksum: true,
+        },
+    ] {
+        let mut buffer = Vec::new();
+        q.write_to_with(&mut buffer, options).unwrap();
+        assert_eq!(
+            Matrix::<Rational>::read_from(&mut buffer.as_slice()).unwrap(),
+            q
+        );
+        let mut buffer = Vec::new();
+        big.write_to_with(&mut buffer, options).unwrap();
+        assert_eq!(
+            Matrix::<BigInt>::read_from(&mut buffer.as_slice()).unwrap(),
+            big
+        );
+        let mut buffer = Vec::new();
+        fraction.write_to_with(&mut buffer, options).unwrap();
+        assert_eq!(
+            Matrix::<BigRational>::read_from(&mut buffer.as_slice()).unwrap(),
+            fraction
+        );
+        let mut buffer = Vec::new();
+        gf.write_to_with(&mut buffer, options).unwrap();
+        assert_eq!(
+            Matrix::<Gf<7>>::read_from(&mut buffer.as_slice()).unwrap(),
+            gf
+        );
+        // a residue modulo 7 is not necessarily one modulo 5
+        assert!(matches!(
+            Matrix::<Gf<5>>::read_from(&mut buffer.as_slice()),
+            Err(RMatrixError::ParseFailed(_))
+        ));
+        let mut buffer = Vec::new();
+        aes.write_to_with(&mut buffer, options).unwrap();
+        assert_eq!(
+            Matrix::<Gf2k<0x11B>>::read_from(&mut buffer.as_slice()).unwrap(),
+            aes
+        );
+    }
+
+    // corrupt lengths in the header fail without allocating them
+    let mut header = b"RMAT\x01\x01\x00\x00".to_vec();
+    for v in [u64::MAX / 2, 2] {
+        header.extend_from_slice(&v.to_le_bytes());
+    }
+    header.extend_from_slice(&0u32.to_le_bytes());
+    assert!(Matrix::<f64>::read_from(&mut header.as_slice()).is_err());
+    let mut header = b"RMAT\x01\x01\x00\x00".to_vec();
+    for v in [1u64 << 40, 1u64 << 20] {
+        header.extend_from_slice(&v.to_le_bytes());
+    }
+    header.extend_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+        Matrix::<f64>::read_from(&mut header.as_slice()),
+        Err(RMatrixError::IoFailed(_))
+    ));
+    header.truncate(header.len() - 4);
+    header.extend_from_slice(&0u32.to_le_bytes());
+    assert!(matches!(
+        Matrix::<f64>::read_from(&mut header.as_slice()),
+        Err(RMatrixError::IoFailed(_))
+    ));
+}
+
+#[test]
+fn mat_big_endian_complex_variable() {
+    // 2x1 big-endian complex double named "z"
+    let mut bytes: Vec<u8> = [1000i32, 2, 1, 1, 2]
+        .iter()
+        .flat_map(|v| v.to_be_bytes())
+        .collect();
+    bytes.extend_from_slice(b"z\0");
+    for v in [1.0f64, 2.0, -1.0, 0.5] {
+        bytes.extend_from_slice(&v.to_be_bytes());
+    }
+    let vars = Matrix::<Complex>::read_mat_from(&mut bytes.as_slice()).unwrap();
+    assert_eq!(vars.len(), 1);
+    assert_eq!(vars["z"].tag, "z");
+    assert_eq!(vars["z"].get(2, 1).unwrap(), Complex::new(2.0, 0.5));
+    assert!(Matrix::<f64>::read_mat_from(&mut bytes.as_slice()).is_err());
+
+    let mut buffer = Vec::new();
+    Matrix::write_mat_to(&mut buffer, &[&vars["z"]]).unwrap();
+    assert_eq!(
+        Matrix::<Complex>::read_mat_from(&mut buffer.as_slice()).unwrap(),
+        vars
+    );
+
+    // an empty 0x3 variable "e" before "z" is skipped
+    let mut empty: Vec<u8> = [1000i32, 0, 3, 0, 2]
+        .iter()
+        .flat_map(|v| v.to_be_bytes())
+        .collect();
+    empty.extend_from_slice(b"e\0");
+    empty.extend_from_slice(&bytes);
+    let vars = Matrix::<Complex>::read_mat_from(&mut empty.as_slice()).unwrap();
+    assert_eq!(vars.keys().collect::<Vec<_>>(), vec!["z"]);
+
+    // a corrupt header fails without allocating its dimensions
+    let mut corrupt: Vec<u8> = [0i32, i32::MAX, i32::MAX, 0, i32::MAX]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    corrupt.extend_from_slice(b"x\0");
+    assert!(matches!(
+        Matrix::<f64>::read_mat_from(&mut corrupt.as_slice()),
+        Err(RMatrixError::IoFailed(_))
+    ));
+}
+
+#[test]
+fn eigenvalues_match_trace_and_determinant() {
+    for n in 1..8 {
+        let a = Matrix::<f64>::rand(n, n, -PI, PI).unwrap();
+        let ev = a.eigenvalues().unwrap();
+        assert_eq!(ev.len(), n);
+        // non-real eigenvalues come in exact conjugate pairs
+        assert!(ev.iter().all(|v| v.im() == 0.0 || ev.contains(&v.conj())));
+        let (re_sum, im_sum) = ev
+            .iter()
+            .fold((0.0, 0.0), |(re, im), v| (re + v.re(), im + v.im()));
+        assert!((re_sum - a.tr().unwrap()).is_zero() && im_sum.is_zero());
+        // product of the eigenvalues, multiplied by hand
+        let (re, im) = ev.iter().fold((1.0, 0.0), |(re, im): (f64, f64), v| {
+            (re * v.re() - im * v.im(), re * v.im() + im * v.re())
+        });
+        let det = a.det().unwrap();
+        assert!(((re - det) / det.abs().max(1.0)).is_zero() && im.is_zero());
+    }
+}
+
+#[test]
+fn text_formats_round_trip() {
+    let m = Matrix::<f64>::rand(3, 2, -PI, PI).unwrap();
+    assert_eq!(Matrix::<f64>::from_text(&m.to_text()).unwrap(), m);
+    assert_eq!(Matrix::<f64>::from_csv(&m.to_csv()).unwrap(), m);
+    assert_eq!(
+        Matrix::<f64>::from_matrix_market(&m.to_matrix_market()).unwrap(),
+        m
+    );
+    assert!(matches!(
+        Matrix::<f64>::from_csv("1,2\n3\n"),
+        Err(RMatrixError::LengthInconsistent(1, 2))
+    ));
+
+    // malformed Matrix Market sizes, indices and entry counts
+    let mm =
+        |body: &str| Matrix::<f64>::from_matrix_market(&format!("%%MatrixMarket matrix {}", body));
+    assert!(matches!(
+        mm("array real general\n4294967296 4294967296\n1\n"),
+        Err(RMatrixError::ShapeUnreasonable)
+    ));
+    assert!(matches!(
+        mm("array real general\n2 2\n1\n"),
+        Err(RMatrixError::ParseFailed(_))
+    ));
+    assert!(matches!(
+        mm("array real general\n1 1\n1\n2\n"),
+        Err(RMatrixError::ParseFailed(_))
+    ));
+    assert!(matches!(
+        mm("coordinate real general\n2 2 1\n1.7 1 3\n"),
+        Err(RMatrixError::ParseFailed(_))
+    ));
+    assert!(matches!(
+        mm("coordinate real general\n2 2 5\n1 1 3\n"),
+        Err(RMatrixError::ParseFailed(_))
+    ));
+    assert!(matches!(
+        mm("coordinate real general\n2 2 1\n3 1 3\n"),
+        Err(RMatrixError::OutOfBoundary(3, 1))
+    ));
+    assert!(matches!(
+        mm("coordinate real general\n2.0 2 1\n1 1 3\n"),
+        Err(RMatrixError::ParseFailed(_))
+    ));
+}
+
+#[test]
+fn expression_session() {
+    use rmatrix_ks::expr::{Session, Value};
+
+    let mut s = Session::new();
+    s.execute("A = [1 2; 3 4]").unwrap();
+    s.execute("B = [0 1; 1 0]").unwrap();
+    let (name, value) = s.execute("inv(A) * B + 2 * eye(2)").unwrap();
+    assert_eq!(name, "ans");
+    let expected = Matrix::from_vec(2, 2, vec![3.0, -2.0, -0.5, 3.5]).unwrap();
+    match value {
+        Value::Matrix(m) => {
+            assert!(common::approx_eq(&m, &expected));
+            assert_eq!(m.tag, "ans");
+        }
+        Value::Scalar(_) => panic!("expected a matrix"),
+    }
+    // whitespace separates elements, `-` binds looser than `^`
+    assert_eq!(
+        s.execute("[1 -2, 3 - 1, -2^2]").unwrap().1,
+        Value::Matrix(Matrix::from_vec(1, 4, vec![1.0, -2.0, 2.0, -4.0]).unwrap())
+    );
+    assert_eq!(s.execute("det(A')").unwrap().1, Value::Scalar(-2.0));
+
+    let input = "A + [1 2 3]";
+    let e = s.execute(input).unwrap_err();
+    assert_eq!((e.span.start, e.span.end), (0, input.len()));
+    let e = s.execute("2 * C").unwrap_err();
+    assert_eq!((e.span.start, e.span.end), (4, 5));
+    assert!(s.execute("[1 2").is_err());
+    // sizes are bounded instead of overflowing or exhausting memory
+    assert!(s.execute("zeros(1e10)").is_err());
+    assert!(s.execute("eye(4294967296, 4294967296)").is_err());
+    assert!(s.execute("rand(1e5, 1e5)").is_err());
+    // a matrix function with a missing argument is not taken for a scalar one
+    let e = s.execute("powm(2)").unwrap_err();
+    assert!(e.to_string().contains("usage: powm(A, p)"), "{}", e);
+    assert_eq!(s.execute("cos(0)").unwrap().1, Value::Scalar(1.0));
+    s.remove("A");
+    assert!(s.get("A").is_none());
+}
+
+#[test]
+fn traced_row_reduction_replays() {
+    use rmatrix_ks::matrix::trace::RowOp;
+
+    let a = Matrix::from_vec(
+        3,
+        4,
+        vec![0.0, 2.0, 4.0, 2.0, 1.0, 1.0, 1.0, 0.0, 2.0, 4.0, 6.0, 2.0],
+    )
+    .unwrap();
+    let trace = a.row_reduce_traced().unwrap();
+    assert_eq!(trace.steps[0].0, RowOp::Swap(1, 2));
+    let expected = Matrix::from_vec(
+        3,
+        4,
+        vec![1.0, 0.0, -1.0, -1.0, 0.0, 1.0, 2.0, 1.0, 0.0, 0.0, 0.0, 0.0],
+    )
+    .unwrap();
+    assert!(common::approx_eq(trace.result(), &expected));
+    assert!(common::approx_eq(
+        &trace.replay(&a).unwrap(),
+        trace.result()
+    ));
+    // the elementary matrices multiply to the transform
+    let mut e = Matrix::<f64>::eyes(3, 3).unwrap();
+    for op in trace.ops() {
+        e = op.matrix(3).unwrap().times(&e).unwrap();
+    }
+    assert!(common::approx_eq(&e, &trace.transform().unwrap()));
+    assert!(common::approx_eq(&e.times(&a).unwrap(), &expected));
+    assert_eq!(
+        trace.to_latex().matches("xrightarrow").count(),
+        trace.steps.len()
+    );
+}
+
+#[test]
+fn echelon_of_tall_and_wide_matrices() {
+    // rank 1 with zero rows at the bottom
+    let tall = Matrix::from_vec(4, 2, vec![1.0, 2.0, 2.0, 4.0, 0.0, 0.0, 3.0, 6.0]).unwrap();
+    let e = tall.row_eliminate().unwrap();
+    assert_eq!(
+        (e.rank, e.pivots.clone(), e.free.clone()),
+        (1, vec![1], vec![2])
+    );
+    assert!(common::approx_eq(
+        &e.transform.times(&tall).unwrap(),
+        &e.matrix
+    ));
+    assert_eq!(tall.rank().unwrap(), 1);
+
+    // consistent overdetermined system
+    let a = Matrix::from_vec(3, 2, vec![1.0, 1.0, 1.0, -1.0, 2.0, 0.0]).unwrap();
+    let b = Matrix::from_vec(3, 1, vec![3.0, 1.0, 4.0]).unwrap();
+    let x = a.row_reduce().unwrap().particular_solution(&b).unwrap();
+    assert!(common::approx_eq(
+        &x,
+        &Matrix::from_vec(2, 1, vec![2.0, 1.0]).unwrap()
+    ));
+    let b = Matrix::from_vec(3, 1, vec![3.0, 1.0, 5.0]).unwrap();
+    assert!(matches!(
+        a.row_reduce().unwrap().particular_solution(&b),
+        Err(RMatrixError::LinearEquationsNoSolution(_))
+    ));
+
+    let wide = Matrix::from_vec(2, 4, vec![0.0, 1.0, 2.0, 3.0, 0.0, 2.0, 4.0, 7.0]).unwrap();
+    let e = wide.row_reduce().unwrap();
+    assert_eq!((e.pivots.clone(), e.free.clone()), (vec![2, 4], vec![1, 3]));
+    let singular = Matrix::from_vec(2, 2, vec![1.0, 2.0, 2.0, 4.0]).unwrap();
+    assert!(matches!(
+        singular.inverse(),
+        Err(RMatrixError::MatrixSingular)
+    ));
+    assert!(singular.det().unwrap().is_zero());
+}
+
+#[test]
+fn fundamental_subspaces() {
+    let a = Matrix::from_vec(
+        4,
+        5,
+        vec![
+            1.0, 2.0, 0.0, 1.0, 3.0, 2.0, 4.0, 1.0, 0.0, 5.0, 3.0, 6.0, 1.0, 1.0, 8.0, 0.0, 0.0,
+            1.0, -2.0, -1.0,
+        ],
+    )
+    .unwrap();
+    let rank = a.rank().unwrap();
+    assert_eq!(rank, 2);
+    let zero = |m: &Matrix<f64>| {
+        let (r, c) = m.dimensions();
+        (1..=r).all(|i| (1..=c).all(|j| m.get(i, j).unwrap().is_zero()))
+    };
+    let n = a.echelon_null_space().unwrap().unwrap();
+    assert_eq!((n.dimensions(), a.nullity().unwrap()), ((5, 3), 3));
+    assert!(zero(&a.times(&n).unwrap()));
+    let y = a.echelon_left_null_space().unwrap().unwrap();
+    assert_eq!((y.dimensions(), a.left_nullity().unwrap()), ((4, 2), 2));
+    assert!(zero(&y.transpose().unwrap().times(&a).unwrap()));
+    assert_eq!(
+        a.echelon_column_space().unwrap().unwrap().rank().unwrap(),
+        rank
+    );
+    assert!(a
+        .is_in_span(&a.echelon_column_space().unwrap().unwrap())
+        .unwrap());
+    assert!(a
+        .transpose()
+        .unwrap()
+        .is_in_span(&a.echelon_row_space().unwrap().unwrap())
+        .unwrap());
+
+    // the default bases of floats are orthonormal and span the same subspaces
+    let qn = a.null_space().unwrap().unwrap();
+    assert!(zero(&a.times(&qn).unwrap()));
+    assert!(common::approx_eq(
+        &qn.transpose().unwrap().times(&qn).unwrap(),
+        &Matrix::eyes(3, 3).unwrap()
+    ));
+    let qc = a.column_space().unwrap().unwrap();
+    assert!(a.is_in_span(&qc).unwrap() && qc.is_in_span(&a).unwrap());
+    let qy = a.left_null_space().unwrap().unwrap();
+    assert!(zero(&qy.transpose().unwrap().times(&a).unwrap()));
+    assert_eq!(a.row_space().unwrap().unwrap().dimensions(), (5, 2));
+
+    // planes x = 0 and y = 0 in space meet in the z axis
+    let yz = Matrix::from_vec(3, 2, vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0]).unwrap();
+    let xz = Matrix::from_vec(3, 2, vec![1.0, 0.0, 0.0, 0.0, 0.0, 1.0]).unwrap();
+    let meet = yz.subspace_intersection(&xz).unwrap().unwrap();
+    assert_eq!(meet.dimensions(), (3, 1));
+    assert!(meet.get(1, 1).unwrap().is_zero() && meet.get(2, 1).unwrap().is_zero());
+    assert_eq!(yz.subspace_sum(&xz).unwrap().unwrap().dimensions(), (3, 3));
+    let x = Matrix::from_vec(3, 1, vec![1.0, 0.0, 0.0]).unwrap();
+    assert!(yz.subspace_intersection(&x).unwrap().is_none());
+
+    // dimensions and spans use the relative tolerance of the bases
+    let tiny = Matrix::<f64>::eyes(2, 2).unwrap().smul(1e-9).unwrap();
+    assert!(tiny.null_space().unwrap().is_none());
+    assert_eq!(
+        (tiny.nullity().unwrap(), tiny.left_nullity().unwrap()),
+        (0, 0)
+    );
+    let e1 = Matrix::from_vec(2, 1, vec![1.0, 0.0]).unwrap();
+    let e2 = Matrix::from_vec(2, 1, vec![0.0, 1e-9]).unwrap();
+    assert!(!e1.is_in_span(&e2).unwrap());
+}
+
+#[test]
+fn exact_rational_elimination() {
+    // hilbert matrix, its inverse has integer entries
+    let n = 5;
+    let h = Matrix::from_vec(
+        n,
+        n,
+        (0..n * n)
+            .map(|k| Rational::new(1, (k / n + k % n + 1) as i64))
+            .collect(),
+    )
+    .unwrap();
+    let inv = h.inverse().unwrap();
+    assert_eq!(h.times(&inv).unwrap(), Matrix::eyes(n, n).unwrap());
+    assert_eq!(inv.get(1, 1).unwrap(), Rational::from_integer(25));
+    assert!((1..=n).all(|i| (1..=n).all(|j| inv.get(i, j).unwrap().is_integer())));
+    assert_eq!(h.det().unwrap(), Rational::new(1, 266_716_800_000));
+
+    // within the f64 tolerance but not singular
+    let m = Matrix::<f64>::from_text("1 1\n1 1.000000001").unwrap();
+    assert_eq!(m.rank().unwrap(), 1);
+    let m = Matrix::<Rational>::from_text("1 1\n1 1.000000001").unwrap();
+    assert_eq!(m.rank().unwrap(), 2);
+    let m = Matrix::<Rational>::from_text("1/2 -3/4\n2 -3").unwrap();
+    assert_eq!(m.rank().unwrap(), 1);
+    assert_eq!(
+        m.row_reduce().unwrap().matrix.get(1, 2).unwrap(),
+        "-3/2".parse().unwrap()
+    );
+
+    assert_eq!(format!("[{:>6.3}]", Rational::new(-1, 3)), "[  -1/3]");
+    assert!(Rational::new(1, 3) < Rational::new(1, 2));
+    assert!(Rational::new(i64::MAX, 1)
+        .checked_add(Rational::from_integer(1))
+        .is_none());
+    assert!("0.5.1".parse::<Rational>().is_err() && "1/".parse::<Rational>().is_err());
+}
+
+#[test]
+fn big_integer_arithmetic_matches_i128() {
+    use rmatrix_ks::number::Ring;
+
+    let mut rng = thread_rng();
+    for _ in 0..2000 {
+        let a: i128 = rng.gen_range(-(1i128 << 100)..(1i128 << 100)) >> rng.gen_range(0..100);
+        let b: i128 = rng.gen_range(-(1i128 << 60)..(1i128 << 60)) >> rng.gen_range(0..60);
+        let (ba, bb): (BigInt, BigInt) = (
+            a.to_string().parse().unwrap(),
+            b.to_string().parse().unwrap(),
+        );
+        assert_eq!((ba.clone() + bb.clone()).to_string(), (a + b).to_string());
+        assert_eq!((ba.clone() - bb.clone()).to_string(), (a - b).to_string());
+        if b != 0 {
+            let (q, r) = ba.div_rem(&bb);
+            assert_eq!(
+                (q.to_string(), r.to_string()),
+                ((a / b).to_string(), (a % b).to_string())
+            );
+        }
+        if a.unsigned_abs() < 1 << 64 {
+            assert_eq!((ba.clone() * bb.clone()).to_string(), (a * b).to_string());
+        }
+        assert_eq!(ba.cmp(&bb), a.cmp(&b));
+    }
+    // multi-limb divisors
+    let f30: BigInt = (1..=30).map(BigInt::from).fold(BigInt::one(), |a, b| a * b);
+    assert_eq!(f30.to_string(), "265252859812191058636308480000000");
+    let f20: BigInt = (1..=20).map(BigInt::from).fold(BigInt::one(), |a, b| a * b);
+    let p = f30.clone() / f20.clone();
+    assert_eq!(p.to_string(), "109027350432000");
+    assert!((f30.clone() % f20.clone()).is_zero() && (p * f20.clone() == f30));
+    assert_eq!(f30.gcd(&BigInt::from(1i64 << 40)), BigInt::from(1i64 << 26));
+    assert_eq!(BigInt::from(i64::MIN).to_i64(), Some(i64::MIN));
+}
+
+#[test]
+fn exact_big_rational_hilbert_inverse() {
+    let n = 20;
+    let h = Matrix::from_vec(
+        n,
+        n,
+        (0..n * n)
+            .map(|k| BigRational::new(BigInt::from(1), BigInt::from((k / n + k % n + 1) as i64)))
+            .collect(),
+    )
+    .unwrap();
+    let inv = h.inverse().unwrap();
+    assert_eq!(h.times(&inv).unwrap(), Matrix::eyes(n, n).unwrap());
+    assert!((1..=n).all(|i| (1..=n).all(|j| inv.get(i, j).unwrap().is_integer())));
+    assert_eq!(inv.get(1, 1).unwrap(), BigRational::from(400));
+    // the determinant of the inverse is an integer of 226 digits
+    let d = h.det().unwrap();
+    assert!(d.numer() == &BigInt::from(1) && d.denom().to_string().len() == 226);
+
+    // bareiss agrees with elimination over the rationals
+    let mut rng = thread_rng();
+    let ints: Vec<i64> = (0..100).map(|_| rng.gen_range(-1000..1000)).collect();
+    let a = Matrix::from_vec(10, 10, ints.iter().map(|&v| BigInt::from(v)).collect()).unwrap();
+    let q = Matrix::from_vec(10, 10, ints.iter().map(|&v| BigRational::from(v)).collect()).unwrap();
+    assert_eq!(
+        BigRational::from(a.det_bareiss().unwrap()),
+        q.det().unwrap()
+    );
+    assert_eq!(q.det_bareiss().unwrap(), q.det().unwrap());
+    // a truncating elimination would give 4
+    let a = Matrix::from_vec(2, 2, [2, 1, 1, 2].map(BigInt::from).to_vec()).unwrap();
+    assert_eq!(a.det_bareiss().unwrap(), BigInt::from(3));
+    assert_eq!(
+        a.char_poly().unwrap().eval(&BigInt::default()),
+        BigInt::from(3)
+    );
+
+    assert_eq!(
+        "-.5".parse::<BigRational>().unwrap(),
+        BigRational::new(BigInt::from(-1), BigInt::from(2))
+    );
+    assert_eq!(
+        format!("{:>8}|{:+}", BigRational::from(-3), BigInt::from(7)),
+        "      -3|+7"
+    );
+}
+
+#[test]
+fn complex_field_axioms_and_functions() {
+    let gen = || {
+        let mut rng = thread_rng();
+        Complex::new(rng.gen_range(-10.0..10.0), rng.gen_range(-10.0..10.0))
+    };
+    let close = |a: Complex, b: Complex| (a - b).norm() <= 1e-9 * (1.0 + a.norm().max(b.norm()));
+    let (zero, one) = (Complex::default(), Complex::one());
+    for _ in 0..1000 {
+        let (a, b, c) = (gen(), gen(), gen());
+        assert_eq!(a + b, b + a);
+        assert_eq!(a * b, b * a);
+        assert!(close((a + b) + c, a + (b + c)));
+        assert!(close((a * b) * c, a * (b * c)));
+        assert!(close(a * (b + c), a * b + a * c));
+        assert_eq!(a + zero, a);
+        assert_eq!(a * one, a);
+        assert_eq!(a + (-a), zero);
+        assert!(close(a - b, a + (-b)));
+        assert!(close(a * a.recip(), one));
+        assert!(close(a / b * b, a));
+        assert!(close(a * a.conj(), Complex::from(a.norm_sqr())));
+
+        let (r, theta) = a.to_polar();
+        assert!(close(Complex::from_polar(r, theta), a));
+        assert!(close(a.ln().exp(), a));
+        assert!(close(a.sqrt() * a.sqrt(), a) && a.sqrt().re() >= 0.0);
+        assert!(close(a.powi(3), a * a * a) && close(a.powi(-2), (a * a).recip()));
+        assert!(close(a.powf(0.5), a.sqrt()));
+        assert!(close(a.powc(Complex::from(2.0)), a * a));
+        assert!(close((a + b).exp(), a.exp() * b.exp()));
+
+        let small = a / 10.0;
+        assert!(close(
+            small.sin() * small.sin() + small.cos() * small.cos(),
+            one
+        ));
+        assert!(close(
+            small.cosh() * small.cosh() - small.sinh() * small.sinh(),
+            one
+        ));
+        assert!(close(small.tan(), small.sin() / small.cos()));
+        assert!(close(small.tanh(), small.sinh() / small.cosh()));
+        assert!(close(small.asin().sin(), small) && close(small.acos().cos(), small));
+        assert!(close(small.atan().tan(), small) && close(small.asinh().sinh(), small));
+        assert!(close(small.acosh().cosh(), small) && close(small.atanh().tanh(), small));
+
+        // mixed operators agree with the embedding of the reals
+        let x = thread_rng().gen_range(-10.0..10.0);
+        assert_eq!(a + x, a + Complex::from(x));
+        assert_eq!(x - a, Complex::from(x) - a);
+        assert_eq!(a * x, a * Complex::from(x));
+        assert_eq!(x / a, Complex::from(x) / a);
+    }
+    assert!(close(Complex::new(0.0, PI).exp(), -one));
+    let m = Matrix::from_vec(
+        2,
+        2,
+        vec![
+            one,
+            Complex::new(0.0, 1.0),
+            Complex::new(0.0, -1.0),
+            Complex::from(2.0),
+        ],
+    )
+    .unwrap();
+    assert!(close(m.det().unwrap(), one));
+    assert!(common::approx_eq(
+        &m.times(&m.inverse().unwrap()).unwrap(),
+        &Matrix::eyes(2, 2).unwrap()
+    ));
+}
+
+#[test]
+fn generic_complex_parts() {
+    // exact gaussian rationals
+    let q = |n: i64, d: i64| Rational::new(n, d);
+    let a = Complex::new(q(1, 2), q(-1, 3));
+    let b = Complex::new(q(2, 1), q(3, 4));
+    assert_eq!(a / b * b, a);
+    assert_eq!(a * a.recip(), Complex::one());
+    assert_eq!(a.norm_sqr(), q(13, 36));
+    let m = Matrix::from_vec(2, 2, vec![a, b, b.conj(), Complex::from(q(1, 1))]).unwrap();
+    assert_eq!(
+        m.times(&m.inverse().unwrap()).unwrap(),
+        Matrix::eyes(2, 2).unwrap()
+    );
+    assert_eq!("1/2-1/3I".parse::<Complex<Rational>>().unwrap(), a);
+
+    // single precision
+    let z = Complex::new(3.0f32, 4.0f32);
+    assert_eq!(z.norm(), 5.0f32);
+    assert!((z.sqrt() * z.sqrt() - z).norm() < 1e-5);
+    assert_eq!(2.0f32 * z, Complex::new(6.0f32, 8.0f32));
+    let m = Matrix::from_vec(2, 2, vec![1.0f32, 2.0, 3.0, 4.0]).unwrap();
+    assert!((m.det().unwrap() + 2.0).abs() < 1e-5);
+    let m = Matrix::from_vec(2, 2, vec![z, Complex::one(), Complex::default(), z.conj()]).unwrap();
+    assert!((m.det().unwrap() - Complex::from(25.0f32)).norm() < 1e-4);
+}
+
+#[test]
+fn complex_parse_and_format() {
+    let ok = |s: &str, re: f64, im: f64| {
+        assert_eq!(s.parse::<Complex>().unwrap(), Complex::new(re, im), "{}", s)
+    };
+    ok("2I", 0.0, 2.0);
+    ok("-I", 0.0, -1.0);
+    ok("i", 0.0, 1.0);
+    ok("1e-3+2e5I", 1e-3, 2e5);
+    ok("-1.5E+2-3e-1j", -150.0, -0.3);
+    ok("3 + 4 i", 3.0, 4.0);
+    ok("+7", 7.0, 0.0);
+    ok("( -1.0 , 2e1 )", -1.0, 20.0);
+    for bad in [
+        "", "I1", "1+2", "1++2i", "abc", "1+xi", "(1)", "(1,2,3)", "1e+i",
+    ] {
+        assert!(
+            matches!(bad.parse::<Complex>(), Err(RMatrixError::ParseFailed(_))),
+            "{}",
+            bad
+        );
+    }
+    // display round trips
+    let z = Complex::new(-1.25e-7, 3.5);
+    assert_eq!(z.to_string().parse::<Complex>().unwrap(), z);
+    assert_eq!(format!("{:+.2}", Complex::new(1.0, -2.0)), "+1.00-2.00I");
+    assert_eq!(format!("{:<8.1}|", Complex::new(1.0, 2.0)), "1.0+2.0I|");
+    assert_eq!(format!("{:<10.1}|", Complex::new(1.0, 2.0)), "1.0+2.0I  |");
+}
+
+#[test]
+fn matrix_exponential_logarithm_and_square_root() {
+    // the classic example of Moler and Van Loan with eigenvalues -1 and -17, which needs scaling
+    let a = Matrix::from_vec(2, 2, vec![-49.0, 24.0, -64.0, 31.0]).unwrap();
+    let (e, err) = a.expm_with_error().unwrap();
+    let expected = Matrix::from_vec(
+        2,
+        2,
+        vec![
+            -2.0 * (-1f64).exp() + 3.0 * (-17f64).exp(),
+            1.5 * (-1f64).exp() - 1.5 * (-17f64).exp(),
+            -4.0 * (-1f64).exp() + 4.0 * (-17f64).exp(),
+            3.0 * (-1f64).exp() - 2.0 * (-17f64).exp(),
+        ],
+    )
+    .unwrap();
+    assert!(e.subtract(&expected).unwrap().norm_1() < 1e-12 && err < 1e-10);
+
+    let mut rng = thread_rng();
+    for _ in 0..20 {
+        let a = Matrix::<f64>::rand(4, 4, -1.0, 1.0).unwrap();
+        // exp(A) exp(-A) = I
+        let inv = a.smul(-1.0).unwrap().expm().unwrap();
+        let i = a.expm().unwrap().times(&inv).unwrap();
+        assert!(i.subtract(&Matrix::eyes(4, 4).unwrap()).unwrap().norm_1() < 1e-12);
+        // the principal logarithm of exp(A) is A for small A
+        let (l, err) = a.expm().unwrap().logm_with_error().unwrap();
+        assert!(l.subtract(&a).unwrap().norm_1() < 1e-10 && err < 1e-10);
+        // symmetric positive definite matrices have a symmetric square root
+        let b = a
+            .times(&a.transpose().unwrap())
+            .unwrap()
+            .plus(
+                &Matrix::eyes(4, 4)
+                    .unwrap()
+                    .smul(rng.gen_range(0.1..2.0))
+                    .unwrap(),
+            )
+            .unwrap();
+        let (r, err) = b.sqrtm_with_error().unwrap();
+        assert!(err < 1e-12);
+        assert!(r.subtract(&r.transpose().unwrap()).unwrap().norm_1() < 1e-10);
+    }
+
+    // complex matrices, exp(i pi I) = -I
+    let m = Matrix::from_vec(
+        2,
+        2,
+        vec![
+            Complex::new(0.0, PI),
+            Complex::default(),
+            Complex::default(),
+            Complex::new(0.0, PI),
+        ],
+    )
+    .unwrap();
+    let e = m.expm().unwrap();
+    assert!(e.plus(&Matrix::eyes(2, 2).unwrap()).unwrap().norm_1() < 1e-12);
+    let c = Matrix::from_vec(
+        2,
+        2,
+        vec![
+            Complex::new(2.0, 1.0),
+            Complex::new(0.0, -1.0),
+            Complex::new(1.0, 0.5),
+            Complex::new(3.0, 0.0),
+        ],
+    )
+    .unwrap();
+    let r = c.sqrtm().unwrap();
+    assert!(r.times(&r).unwrap().subtract(&c).unwrap().norm_1() < 1e-12);
+    assert!(
+        c.logm()
+            .unwrap()
+            .expm()
+            .unwrap()
+            .subtract(&c)
+            .unwrap()
+            .norm_1()
+            < 1e-12
+    );
+
+    // real matrices with eigenvalues on the negative real axis
+    let neg = Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, -4.0]).unwrap();
+    assert!(matches!(
+        neg.logm(),
+        Err(RMatrixError::FunctionUndefined(_))
+    ));
+    assert!(matches!(
+        neg.sqrtm(),
+        Err(RMatrixError::FunctionUndefined(_))
+    ));
+    let singular = Matrix::from_vec(2, 2, vec![1.0, 1.0, 1.0, 1.0]).unwrap();
+    assert!(matches!(
+        singular.logm(),
+        Err(RMatrixError::FunctionUndefined(_))
+    ));
+    // singular positive semidefinite matrices still have a principal square root
+    let r = singular.sqrtm().unwrap();
+    assert!(common::approx_eq(
+        &r,
+        &singular.smul(0.5f64.sqrt()).unwrap()
+    ));
+    assert_eq!(
+        Matrix::<f64>::zeros(2, 2).unwrap().sqrtm().unwrap(),
+        Matrix::zeros(2, 2).unwrap()
+    );
+    for _ in 0..10 {
+        let b = Matrix::<f64>::rand(4, 2, -1.0, 1.0).unwrap();
+        let psd = b.times(&b.transpose().unwrap()).unwrap();
+        let (_, error) = psd.sqrtm_with_error().unwrap();
+        assert!(error < 1e-6, "{}", error);
+    }
+    let nilpotent = Matrix::from_vec(2, 2, vec![0.0, 1.0, 0.0, 0.0]).unwrap();
+    assert!(matches!(
+        nilpotent.sqrtm(),
+        Err(RMatrixError::FunctionUndefined(_))
+    ));
+    let wide = Matrix::<f64>::zeros(2, 3).unwrap();
+    assert!(matches!(wide.expm(), Err(RMatrixError::MatrixNotSquare)));
+    assert!(matches!(wide.sqrtm(), Err(RMatrixError::MatrixNotSquare)));
+}
+
+#[test]
+fn schur_parlett_matrix_functions() {
+    let eye = Matrix::<f64>::eyes(5, 5).unwrap();
+    for _ in 0..20 {
+        let a = Matrix::<f64>::rand(5, 5, -1.0, 1.0).unwrap();
+        // A = Q T Q^H with unitary Q and upper triangular T
+        let c = a.to_complex();
+        let (q, t) = c.schur().unwrap();
+        for i in 2..=5 {
+            for j in 1..i {
+                assert!(t.get(i, j).unwrap().norm() < 1e-12);
+            }
+        }
+        let back = q.times(&t).unwrap().times(&q.adjoint().unwrap()).unwrap();
+        assert!(back.subtract(&c).unwrap().norm_1() < 1e-12);
+        let qq = q.adjoint().unwrap().times(&q).unwrap();
+        assert!(qq.subtract(&Matrix::eyes(5, 5).unwrap()).unwrap().norm_1() < 1e-12);
+        // sin^2 + cos^2 = I and cosh^2 - sinh^2 = I
+        let (s, co) = (a.sinm().unwrap(), a.cosm().unwrap());
+        let one = s.times(&s).unwrap().plus(&co.times(&co).unwrap()).unwrap();
+        assert!(one.subtract(&eye).unwrap().norm_1() < 1e-10);
+        let (sh, ch) = (a.sinhm().unwrap(), a.coshm().unwrap());
+        let one = ch
+            .times(&ch)
+            .unwrap()
+            .subtract(&sh.times(&sh).unwrap())
+            .unwrap();
+        assert!(one.subtract(&eye).unwrap().norm_1() < 1e-10);
+        // funm of exp agrees with expm
+        let e = a.funm(|z| z.exp()).unwrap();
+        assert!(e.subtract(&a.expm().unwrap()).unwrap().norm_1() < 1e-10);
+        // cube root of a matrix with eigenvalues in the right half plane
+        let b = a.plus(&eye.smul(3.0).unwrap()).unwrap();
+        let r = b.powf(1.0 / 3.0).unwrap();
+        let cube = r.times(&r).unwrap().times(&r).unwrap();
+        assert!(cube.subtract(&b).unwrap().norm_1() < 1e-10);
+    }
+
+    // a rotation has the complex eigenvalues exp(+-i), its logarithm is real
+    let (s, c) = (1f64.sin(), 1f64.cos());
+    let rot = Matrix::from_vec(2, 2, vec![c, -s, s, c]).unwrap();
+    let l = rot.funm(|z| z.ln()).unwrap();
+    let gen = Matrix::from_vec(2, 2, vec![0.0, -1.0, 1.0, 0.0]).unwrap();
+    assert!(l.subtract(&gen).unwrap().norm_1() < 1e-12);
+    // cos of the generator is cosh(1) I
+    let cos = gen.cosm().unwrap();
+    let expected = Matrix::eyes(2, 2).unwrap().smul(1f64.cosh()).unwrap();
+    assert!(cos.subtract(&expected).unwrap().norm_1() < 1e-12);
+
+    // a defective eigenvalue and a close pair are evaluated in blocks
+    let jordan = Matrix::from_vec(
+        4,
+        4,
+        vec![
+            2.0, 1.0, 0.0, 0.5, 0.0, 2.0, 1.0, 0.0, 0.0, 0.0, 2.0, 1.0, 0.0, 0.0, 0.0, 2.05,
+        ],
+    )
+    .unwrap();
+    let e = jordan.funm(|z| z.exp()).unwrap();
+    assert!(e.subtract(&jordan.expm().unwrap()).unwrap().norm_1() < 1e-9);
+    let r = jordan.powf(0.5).unwrap();
+    assert!(r.subtract(&jordan.sqrtm().unwrap()).unwrap().norm_1() < 1e-9);
+
+    // a real matrix whose square root is not real
+    let neg = Matrix::from_vec(2, 2, vec![-1.0, 0.0, 0.0, 2.0]).unwrap();
+    assert!(matches!(
+        neg.powf(0.5),
+        Err(RMatrixError::FunctionUndefined(_))
+    ));
+    assert!(neg.to_complex().powf(0.5).is_ok());
+    // the inverses of the iterations pivot and test singularity relative to the norm
+    let small = Matrix::from_vec(2, 2, vec![1e-10, 0.0, 0.0, 4e-10]).unwrap();
+    let r = small.sqrtm().unwrap();
+    let expected = Matrix::from_vec(2, 2, vec![1e-5, 0.0, 0.0, 2e-5]).unwrap();
+    assert!(r.subtract(&expected).unwrap().norm_1() < 1e-15);
+    let l = small.logm().unwrap();
+    assert!((l.get(2, 2).unwrap() - 4e-10f64.ln()).abs() < 1e-10);
+    let swapped = Matrix::from_vec(2, 2, vec![1e-12, 1.0, 1.0, 1e-12]).unwrap();
+    let e = swapped.expm().unwrap();
+    assert!((e.get(1, 2).unwrap() - 1f64.sinh()).abs() < 1e-10);
+    for bad in [f64::INFINITY, f64::NAN] {
+        let m = Matrix::from_vec(2, 2, vec![1.0, bad, 0.0, 1.0]).unwrap();
+        assert!(matches!(m.expm(), Err(RMatrixError::FunctionUndefined(_))));
+    }
+    // the contour around a defective eigenvalue avoids the branch point at zero
+    let near = Matrix::from_vec(2, 2, vec![0.2, 1.0, 0.0, 0.2]).unwrap();
+    let r = near.powf(0.5).unwrap();
+    assert!(r.times(&r).unwrap().subtract(&near).unwrap().norm_1() < 1e-10);
+    assert!(r.subtract(&near.sqrtm().unwrap()).unwrap().norm_1() < 1e-10);
+    let c = near.to_complex().powf(0.5).unwrap();
+    assert!(
+        c.times(&c)
+            .unwrap()
+            .subtract(&near.to_complex())
+            .unwrap()
+            .norm_1()
+            < 1e-10
+    );
+    let tiny = Matrix::from_vec(2, 2, vec![1e-3, 1.0, 0.0, 1e-3]).unwrap();
+    let r = tiny.powf(0.5).unwrap();
+    assert!(r.times(&r).unwrap().subtract(&tiny).unwrap().norm_1() < 1e-8);
+    // close eigenvalues on both sides of the branch point have no contour
+    let across = Matrix::from_vec(2, 2, vec![0.01, 1.0, 0.0, -0.01]).unwrap();
+    assert!(matches!(
+        across.to_complex().powf(0.5),
+        Err(RMatrixError::FunctionUndefined(_))
+    ));
+    let wide = Matrix::<f64>::zeros(2, 3).unwrap();
+    assert!(matches!(wide.sinm(), Err(RMatrixError::MatrixNotSquare)));
+}
+
+#[test]
+fn matrix_powers_by_squaring() {
+    for _ in 0..10 {
+        // diagonally dominant, so the inverse is well conditioned
+        let a = Matrix::<f64>::rand(3, 3, -1.0, 1.0)
+            .unwrap()
+            .plus(&Matrix::eyes(3, 3).unwrap().smul(3.0).unwrap())
+            .unwrap();
+        let mut p = Matrix::eyes(3, 3).unwrap();
+        for n in 0..=12 {
+            let error = a.pow(n).unwrap().subtract(&p).unwrap().norm_1();
+            assert!(error < 1e-12 * p.norm_1());
+            p = p.times(&a).unwrap();
+        }
+        // A^-3 A^3 = I
+        let i = a.pow(-3).unwrap().times(&a.pow(3).unwrap()).unwrap();
+        assert!(i.subtract(&Matrix::eyes(3, 3).unwrap()).unwrap().norm_1() < 1e-8);
+    }
+
+    // exact Fibonacci numbers, F(200) = F(201) - F(199)
+    let fib = Matrix::from_vec(2, 2, [1, 1, 1, 0].map(BigInt::from).to_vec()).unwrap();
+    let p = fib.pow_unsigned(200).unwrap();
+    let f200 = p.get(1, 2).unwrap();
+    assert_eq!(
+        f200.to_string(),
+        "280571172992510140037611932413038677189525"
+    );
+    assert_eq!(p.get(1, 1).unwrap() - p.get(2, 2).unwrap(), f200);
+    assert_eq!(fib.pow_unsigned(0).unwrap(), Matrix::eyes(2, 2).unwrap());
+    // the modular power agrees with the reduced exact power
+    let m = BigInt::from(1_000_000_007);
+    let r = fib.pow_mod(200, &m).unwrap();
+    assert_eq!(r.get(1, 2).unwrap(), f200 % m.clone());
+    // negative elements are reduced to non-negative residues
+    let neg = Matrix::from_vec(2, 2, [-1, 0, 0, -1].map(BigInt::from).to_vec()).unwrap();
+    let r = neg.pow_mod(3, &BigInt::from(5)).unwrap();
+    assert_eq!(r.get(1, 1).unwrap(), BigInt::from(4));
+    // residue products are reduced before they are summed, so i64 does not overflow
+    let big = Matrix::from_vec(10, 10, vec![1_000_000_006i64; 100]).unwrap();
+    let r = big.pow_mod(2, &1_000_000_007).unwrap();
+    assert_eq!(r.get(1, 1).unwrap(), 10);
+    assert_eq!(big.pow_mod(0, &1).unwrap(), Matrix::zeros(10, 10).unwrap());
+    assert!(matches!(
+        neg.pow_mod(3, &BigInt::from(0)),
+        Err(RMatrixError::FunctionUndefined(_))
+    ));
+    assert!(matches!(
+        neg.pow_mod(3, &BigInt::from(-5)),
+        Err(RMatrixError::FunctionUndefined(_))
+    ));
+
+    let singular = Matrix::from_vec(2, 2, vec![1.0, 2.0, 2.0, 4.0]).unwrap();
+    assert!(matches!(
+        singular.pow(-1),
+        Err(RMatrixError::MatrixSingular)
+    ));
+    let wide = Matrix::<f64>::zeros(2, 3).unwrap();
+    assert!(matches!(wide.pow(2), Err(RMatrixError::MatrixNotSquare)));
+    let wide = Matrix::<BigInt>::zeros(2, 3).unwrap();
+    assert!(matches!(
+        wide.pow_mod(2, &BigInt::from(7)),
+        Err(RMatrixError::MatrixNotSquare)
+    ));
+}
+
+#[test]
+fn characteristic_and_minimal_polynomials() {
+    let mut rng = thread_rng();
+    let mut int = || Rational::from(rng.gen_range(-5..=5));
+    for _ in 0..10 {
+        let a = Matrix::from_vec(4, 4, (0..16).map(|_| int()).collect()).unwrap();
+        let p = a.char_poly().unwrap();
+        assert_eq!(p.degree(), Some(4));
+        assert_eq!(p.leading(), Rational::one());
+        // p(0) = det(-A) and tr(A) = -c_3
+        assert_eq!(p.eval(&Rational::default()), a.det().unwrap());
+        assert_eq!(p.coeff(3), -a.tr().unwrap());
+        // Cayley–Hamilton, and the minimal polynomial divides the characteristic one
+        assert_eq!(p.eval_matrix(&a).unwrap(), Matrix::zeros(4, 4).unwrap());
+        let m = a.min_poly().unwrap();
+        assert_eq!(m.eval_matrix(&a).unwrap(), Matrix::zeros(4, 4).unwrap());
+        let (_, r) = p.div_rem(&m).unwrap();
+        assert!(r.is_zero());
+        // the same integers as BigInt give the same coefficients
+        let b = Matrix::from_vec(
+            4,
+            4,
+            (1..=4)
+                .flat_map(|i| (1..=4).map(move |j| (i, j)))
+                .map(|(i, j)| BigInt::from(a.get(i, j).unwrap().numer()))
+                .collect(),
+        )
+        .unwrap();
+        let q = b.char_poly().unwrap();
+        for k in 0..=4 {
+            assert_eq!(q.coeff(k).to_string(), p.coeff(k).to_string());
+        }
+    }
+
+    // a Jordan block has equal minimal and characteristic polynomials
+    let j = Matrix::from_vec(
+        3,
+        3,
+        [2, 1, 0, 0, 2, 1, 0, 0, 2].map(Rational::from).to_vec(),
+    )
+    .unwrap();
+    assert_eq!(j.min_poly().unwrap(), j.char_poly().unwrap());
+    assert_eq!(j.min_poly().unwrap().to_string(), "x^3 - 6x^2 + 12x - 8");
+    let s = Matrix::<Rational>::eyes(3, 3)
+        .unwrap()
+        .smul(Rational::from(3))
+        .unwrap();
+    assert_eq!(s.min_poly().unwrap().to_string(), "x - 3");
+
+    // no division by the size, which vanishes in small characteristic
+    let mut rng = thread_rng();
+    for _ in 0..20 {
+        let a = Matrix::from_vec(
+            3,
+            3,
+            (0..9).map(|_| Gf::<3>::new(rng.gen_range(0..3))).collect(),
+        )
+        .unwrap();
+        let p = a.char_poly().unwrap();
+        assert_eq!(p.degree(), Some(3));
+        assert_eq!(p.eval(&Gf::default()), -a.det().unwrap());
+        assert_eq!(p.eval_matrix(&a).unwrap(), Matrix::zeros(3, 3).unwrap());
+        let m = a.min_poly().unwrap();
+        assert!(p.div_rem(&m).unwrap().1.is_zero());
+    }
+    let a = Matrix::from_vec(2, 2, vec![Gf::<2>::new(1); 4]).unwrap();
+    assert_eq!(a.char_poly().unwrap().to_string(), "x^2");
+
+    // small coefficients are kept, only exact zeros are trimmed
+    assert_eq!(Polynomial::new(vec![1.0, 1e-9]).degree(), Some(1));
+    let small = Polynomial::new(vec![1e-9, 2e-9, 1e-9]);
+    assert!(!small.is_zero() && small.degree() == Some(2));
+    let d = Matrix::from_vec(2, 2, vec![1e-5f64, 0.0, 0.0, 2e-5]).unwrap();
+    let m = d.min_poly().unwrap();
+    assert_eq!(m.degree(), Some(2));
+    assert!((m.coeff(0) - 2e-10).abs() < 1e-20 && (m.coeff(1) + 3e-5).abs() < 1e-15);
+
+    // polynomial arithmetic
+    let p = Polynomial::new(vec![1.0, -2.0, 0.0, 1.0]);
+    let q = Polynomial::new(vec![-1.0, 1.0]);
+    assert_eq!(p.to_string(), "x^3 - 2x + 1");
+    assert_eq!(
+        (p.clone() * q.clone()).eval(&3.0),
+        p.eval(&3.0) * q.eval(&3.0)
+    );
+    assert_eq!((p.clone() + q.clone()).to_string(), "x^3 - x");
+    assert_eq!((p.clone() - p.clone()), Polynomial::zero());
+    assert_eq!((-q.clone()).to_string(), "-x + 1");
+    assert_eq!(p.derivative().to_string(), "3x^2 - 2");
+    let (d, r) = p.div_rem(&q).unwrap();
+    assert_eq!(d * q.clone() + r, p);
+    assert!(p.div_rem(&Polynomial::zero()).is_none());
+    assert_eq!(format!("[{:>8}]", q), "[   x - 1]");
+
+    let wide = Matrix::<f64>::zeros(2, 3).unwrap();
+    assert!(matches!(
+        wide.char_poly(),
+        Err(RMatrixError::MatrixNotSquare)
+    ));
+    assert!(matches!(
+        wide.min_poly(),
+        Err(RMatrixError::MatrixNotSquare)
+    ));
+    assert!(matches!(
+        p.eval_matrix(&wide),
+        Err(RMatrixError::MatrixNotSquare)
+    ));
+}
+
+#[test]
+fn polynomial_roots_from_companion_matrices() {
+    // the characteristic polynomial of the companion matrix is the monic polynomial
+    let coeffs = [6, -5, -2, 1, 3].map(Rational::from);
+    let c = Matrix::companion(&coeffs).unwrap();
+    let monic = Polynomial::new(coeffs.to_vec()).monic().unwrap();
+    assert_eq!(c.char_poly().unwrap(), monic);
+
+    // (x - 1) (x - 2) ... (x - 8), the roots are badly conditioned
+    let p = (1..=8).fold(Polynomial::constant(1.0), |p, k| {
+        p * Polynomial::new(vec![-(k as f64), 1.0])
+    });
+    let error = |roots: &[Complex]| {
+        let mut re = roots.iter().map(|r| r.re()).collect::<Vec<_>>();
+        re.sort_by(f64::total_cmp);
+        assert!(roots.iter().all(|r| r.im().abs() < 1e-6));
+        re.iter()
+            .enumerate()
+            .map(|(i, r)| (r - (i + 1) as f64).abs())
+            .fold(0.0, f64::max)
+    };
+    let plain = error(&p.roots().unwrap());
+    let polished = error(&p.roots_polished(5).unwrap());
+    assert!(plain < 1e-6 && polished <= plain && polished < 1e-9);
+
+    // random complex roots are recovered
+    let mut rng = thread_rng();
+    for _ in 0..10 {
+        let roots = (0..5)
+            .map(|_| Complex::new(rng.gen_range(-3.0..3.0), rng.gen_range(-3.0..3.0)))
+            .collect::<Vec<_>>();
+        let p = roots
+            .iter()
+            .fold(Polynomial::constant(Complex::one()), |p, r| {
+                p * Polynomial::new(vec![-*r, Complex::one()])
+            });
+        let found = p.roots_polished(3).unwrap();
+        assert_eq!(found.len(), 5);
+        for r in roots.iter() {
+            let d = found
+                .iter()
+                .map(|f| (*f - *r).norm())
+                .fold(f64::MAX, f64::min);
+            assert!(d < 1e-6);
+        }
+    }
+
+    // zero roots are exact and constants have no root
+    let p = Polynomial::new(vec![0.0, 0.0, 2.0, 1.0]);
+    let roots = p.roots().unwrap();
+    assert_eq!(roots.iter().filter(|r| r.is_zero()).count(), 2);
+    assert!((roots[2].re() + 2.0).abs() < 1e-12);
+    assert!(Polynomial::constant(3.0).roots().unwrap().is_empty());
+    assert!(matches!(
+        Polynomial::<f64>::zero().roots(),
+        Err(RMatrixError::FunctionUndefined(_))
+    ));
+    assert!(matches!(
+        Matrix::companion(&[1.0, 0.0]),
+        Err(RMatrixError::ShapeUnreasonable)
+    ));
+    // only exact zeros count, small coefficients are kept
+    let roots = Polynomial::new(vec![1e-9, 1.0]).roots().unwrap();
+    assert!(roots.len() == 1 && (roots[0].re() + 1e-9).abs() < 1e-20);
+    let roots = Polynomial::new(vec![1e-9, 2e-9, 1e-9]).roots().unwrap();
+    assert!(roots.len() == 2 && roots.iter().all(|r| (r.re() + 1.0).abs() < 1e-6));
+}
+
+#[test]
+fn hermite_and_smith_normal_forms() {
+    let mut rng = thread_rng();
+    let unimodular = |m: &Matrix<i64>| m.det_bareiss().unwrap().abs() == 1;
+    for _ in 0..30 {
+        let (r, c) = (rng.gen_range(1..5), rng.gen_range(1..5));
+        let a =
+            Matrix::from_vec(r, c, (0..r * c).map(|_| rng.gen_range(-9..=9)).collect()).unwrap();
+
+        let (h, u) = a.hermite_normal_form().unwrap();
+        assert!(unimodular(&u));
+        assert_eq!(u.times(&a).unwrap(), h);
+        // echelon form with positive pivots and reduced elements above them
+        let mut last = 0;
+        for i in 1..=r {
+            match (1..=c).find(|&j| h.get(i, j).unwrap() != 0) {
+                Some(p) => {
+                    assert!(p > last);
+                    last = p;
+                    let pivot = h.get(i, p).unwrap();
+                    assert!(pivot > 0);
+                    assert!((1..i).all(|k| (0..pivot).contains(&h.get(k, p).unwrap())));
+                }
+                None => last = c + 1,
+            }
+        }
+
+        let (u, d, v) = a.smith_normal_form().unwrap();
+        assert!(unimodular(&u) && unimodular(&v));
+        assert_eq!(u.times(&a).unwrap().times(&v).unwrap(), d);
+        let diag = (1..=r.min(c))
+            .map(|i| d.get(i, i).unwrap())
+            .collect::<Vec<_>>();
+        for i in 1..=r {
+            for j in 1..=c {
+                assert!(i == j || d.get(i, j).unwrap() == 0);
+            }
+        }
+        assert!(diag.iter().all(|&x| x >= 0));
+        assert!(diag
+            .windows(2)
+            .all(|w| w[1] == 0 || (w[0] != 0 && w[1] % w[0] == 0)));
+        // the Hermite form is unique, equivalent matrices share it,
+        // a small unimodular factor keeps the i64 elements from overflowing
+        let e =
+            Matrix::from_vec(r, r, (0..r * r).map(|k| (k % r >= k / r) as i64).collect()).unwrap();
+        assert_eq!(e.times(&a).unwrap().hermite_normal_form().unwrap().0, h);
+        if r == c {
+            assert_eq!(diag.iter().product::<i64>(), a.det_bareiss().unwrap().abs());
+        }
+    }
+
+    // Z^3 / <rows> = Z/2 x Z/6 x Z/12
+    let a = Matrix::from_vec(
+        3,
+        3,
+        [2, 4, 4, -6, 6, 12, 10, -4, -16].map(BigInt::from).to_vec(),
+    )
+    .unwrap();
+    let (_, d, _) = a.smith_normal_form().unwrap();
+    let invariants = (1..=3)
+        .map(|i| d.get(i, i).unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(invariants, ["2", "6", "12"]);
+
+    // x solves A x = b over the integers iff D y = U b has an integer solution y, x = V y
+    let a = Matrix::from_vec(2, 2, vec![2i64, 4, 6, 8]).unwrap();
+    let b = Matrix::from_vec(2, 1, vec![2i64, 2]).unwrap();
+    let (u, d, v) = a.smith_normal_form().unwrap();
+    let ub = u.times(&b).unwrap();
+    let y = Matrix::from_vec(
+        2,
+        1,
+        (1..=2)
+            .map(|i| ub.get(i, 1).unwrap() / d.get(i, i).unwrap())
+            .collect(),
+    )
+    .unwrap();
+    assert_eq!(a.times(&v.times(&y).unwrap()).unwrap(), b);
+}
+
+#[test]
+fn finite_field_linear_algebra() {
+    type F = Gf<1_000_000_007>;
+    let mut rng = thread_rng();
+    let mut random = |n: usize| {
+        Matrix::from_vec(
+            n,
+            n,
+            (0..n * n)
+                .map(|_| F::new(rng.gen_range(0..1_000_000_007)))
+                .collect(),
+        )
+        .unwrap()
+    };
+    for _ in 0..10 {
+        let (a, b) = (random(4), random(4));
+        assert_eq!(
+            a.times(&b).unwrap().det().unwrap(),
+            a.det().unwrap() * b.det().unwrap()
+        );
+        assert_eq!(a.det().unwrap(), a.det_bareiss().unwrap());
+        if !a.det().unwrap().is_zero() {
+            let inv = a.inverse().unwrap();
+            assert_eq!(inv.times(&a).unwrap(), Matrix::eyes(4, 4).unwrap());
+        }
+    }
+    // Fermat's little theorem and inverses
+    let x = F::new(123_456_789);
+    assert_eq!(x.pow(1_000_000_006), F::one());
+    assert_eq!(x * x.inv().unwrap(), F::one());
+    assert_eq!(-x + x, F::default());
+
+    // full rank over the rationals but singular modulo 2 and 3
+    let m = Matrix::<Gf<2>>::from_text("1 1\n1 -1").unwrap();
+    assert_eq!(m.rank().unwrap(), 1);
+    assert!(matches!(m.inverse(), Err(RMatrixError::MatrixSingular)));
+    let m = Matrix::<Gf<3>>::from_text("1 1\n1 -2").unwrap();
+    assert_eq!(m.rank().unwrap(), 1);
+    assert_eq!(m.to_text(), "1 1\n1 1\n");
+    assert_eq!(
+        Matrix::<Gf<5>>::from_text("1 1\n1 -2")
+            .unwrap()
+            .rank()
+            .unwrap(),
+        2
+    );
+
+    // the field of AES, every non-zero element has an inverse
+    type B = Gf2k<0x11B>;
+    assert_eq!(B::K, 8);
+    for v in 1..256 {
+        let x = B::new(v);
+        assert_eq!(x * x.inv().unwrap(), B::one());
+        assert_eq!(x.pow(255), B::one());
+    }
+    assert_eq!(B::new(0x57) * B::new(0x83), B::new(0xC1));
+    assert!(B::default().inv().is_none());
+    // the inverse of the MixColumns matrix
+    let mix = Matrix::<B>::from_text("2 3 1 1\n1 2 3 1\n1 1 2 3\n3 1 1 2").unwrap();
+    let inv = Matrix::<B>::from_text("14 11 13 9\n9 14 11 13\n13 9 14 11\n11 13 9 14").unwrap();
+    assert_eq!(mix.inverse().unwrap(), inv);
+    assert!(mix.to_text().starts_with("0x2 0x3 0x1 0x1"));
+}
+
+#[test]
+fn bit_packed_gf2_matrices() {
+    let mut rng = thread_rng();
+    for _ in 0..20 {
+        let (r, c, k) = (
+            rng.gen_range(1..150),
+            rng.gen_range(1..150),
+            rng.gen_range(1..150),
+        );
+        // sparse rows make rank deficiency likely
+        let mut random = |r: usize, c: usize| {
+            let density = rng.gen_range(0.05..0.5);
+            let data = (0..r * c)
+                .map(|_| Gf::<2>::new(rng.gen_bool(density) as i64))
+                .collect();
+            Matrix::from_vec(r, c, data).unwrap()
+        };
+        let (a, b) = (random(r, c), random(c, k));
+        let (ba, bb) = (BitMatrix::from(&a), BitMatrix::from(&b));
+        assert_eq!(ba.to_matrix().unwrap(), a);
+        assert_eq!(ba.rank(), a.rank().unwrap());
+        assert_eq!(
+            ba.times(&bb).unwrap().to_matrix().unwrap(),
+            a.times(&b).unwrap()
+        );
+        assert_eq!(ba.transpose().to_matrix().unwrap(), a.transpose().unwrap());
+        assert_eq!(ba.transpose().transpose(), ba);
+        let (reduced, pivots) = ba.row_reduce();
+        let e = a.row_reduce().unwrap();
+        assert_eq!(reduced.to_matrix().unwrap(), e.matrix);
+        assert_eq!(pivots, e.pivots);
+        match ba.null_space().unwrap() {
+            Some(n) => {
+                assert_eq!(n.dimensions(), (c, c - pivots.len()));
+                assert!(ba.times(&n).unwrap().is_zero());
+                assert_eq!(n.rank(), c - pivots.len());
+            }
+            None => assert_eq!(pivots.len(), c),
+        }
+    }
+
+    // a large random square matrix is eliminated quickly
+    let n = 1000;
+    let mut big = BitMatrix::zeros(n, n).unwrap();
+    for i in 1..=n {
+        for j in 1..=n {
+            big.set(rng.gen(), i, j).unwrap();
+        }
+    }
+    // duplicated rows lower the rank
+    big.exchange_rows(1, 2).unwrap();
+    let mut dup = big.clone();
+    dup.add_row(2, 1).unwrap();
+    dup.add_row(1, 1).unwrap();
+    assert!(dup.rank() < n);
+    assert!(big.rank() + 10 >= n);
+
+    // the [7, 4] Hamming code corrects one error
+    let g = BitMatrix::from_text("1000110\n0100101\n0010011\n0001111").unwrap();
+    let h = g.parity_check_matrix().unwrap().unwrap();
+    assert!(h.syndrome(&g).unwrap().is_zero());
+    let back = h.generator_matrix().unwrap().unwrap();
+    assert_eq!(back.rank(), 4);
+    assert!(h.syndrome(&back).unwrap().is_zero());
+    let errors = BitMatrix::eyes(7).unwrap();
+    let s = h.syndrome(&errors).unwrap();
+    let syndromes = (1..=7)
+        .map(|i| (1..=3).map(|j| s.get(i, j).unwrap()).collect::<Vec<_>>())
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(syndromes.len(), 7);
+    assert!(!syndromes.contains(&vec![false; 3]));
+    assert!(BitMatrix::eyes(3)
+        .unwrap()
+        .parity_check_matrix()
+        .unwrap()
+        .is_none());
+
+    assert!(matches!(
+        BitMatrix::zeros(0, 3),
+        Err(RMatrixError::ShapeUnreasonable)
+    ));
+    assert!(matches!(
+        g.times(&g),
+        Err(RMatrixError::ShapeInconsistent(_, _))
+    ));
+    assert!(matches!(
+        g.get(5, 1),
+        Err(RMatrixError::OutOfBoundary(5, 1))
+    ));
+    assert!(matches!(
+        BitMatrix::from_text("10\n1"),
+        Err(RMatrixError::LengthInconsistent(1, 2))
+    ));
+}
+
+#[test]
+fn lll_lattice_reduction() {
+    // Gram–Schmidt data of the columns in floating point, for checking the conditions
+    fn gram_schmidt(b: &Matrix<f64>) -> (Vec<Vec<f64>>, Vec<f64>) {
+        let n = b.dimensions().1;
+        let cols = (1..=n).map(|j| b.get_col(j).unwrap()).collect::<Vec<_>>();
+        let mut star: Vec<Vec<f64>> = Vec::new();
+        let mut mu = vec![vec![0.0; n]; n];
+        let mut len = vec![0.0; n];
+        for i in 0..n {
+            let mut v = cols[i].clone();
+            for j in 0..i {
+                mu[i][j] = Matrix::dot(&cols[i], &star[j]).unwrap() / len[j];
+                v.iter_mut()
+                    .zip(&star[j])
+                    .for_each(|(x, s)| *x -= mu[i][j] * s);
+            }
+            len[i] = Matrix::dot(&v, &v).unwrap();
+            star.push(v);
+        }
+        (mu, len)
+    }
+    let mut rng = thread_rng();
+    for _ in 0..10 {
+        let n = rng.gen_range(2..6);
+        let ints = (0..n * n)
+            .map(|_| rng.gen_range(-50..=50))
+            .collect::<Vec<i64>>();
+        let b =
+            Matrix::from_vec(n, n, ints.iter().map(|&v| BigRational::from(v)).collect()).unwrap();
+        let (r, t) = match b.lll_reduce(BigRational::new(BigInt::from(3), BigInt::from(4))) {
+            Ok(v) => v,
+            Err(RMatrixError::MatrixSingular) => continue,
+            Err(e) => panic!("{}", e),
+        };
+        assert_eq!(b.times(&t).unwrap(), r);
+        assert_eq!(t.det().unwrap().abs(), BigRational::from(1));
+        assert!((1..=n).all(|i| (1..=n).all(|j| t.get(i, j).unwrap().is_integer())));
+        let rf = Matrix::from_vec(
+            n,
+            n,
+            (1..=n)
+                .flat_map(|i| (1..=n).map(move |j| (i, j)))
+                .map(|(i, j)| r.get(i, j).unwrap().to_f64())
+                .collect(),
+        )
+        .unwrap();
+        let (mu, len) = gram_schmidt(&rf);
+        for k in 1..n {
+            assert!((0..k).all(|j| mu[k][j].abs() <= 0.5 + 1e-9));
+            assert!(len[k] >= (0.75 - mu[k][k - 1].powi(2)) * len[k - 1] - 1e-6);
+        }
+        // the floating point variant reaches a basis of the same lattice
+        let bf = Matrix::from_vec(n, n, ints.iter().map(|&v| v as f64).collect()).unwrap();
+        let (rf2, tf) = bf.lll_reduce(0.75).unwrap();
+        assert!(bf.times(&tf).unwrap().subtract(&rf2).unwrap().norm_1() < 1e-9);
+        assert!((tf.det().unwrap().abs() - 1.0).abs() < 1e-9);
+    }
+
+    // the integer relation 2 - x^2 = 0 of x = sqrt(2) from a scaled lattice
+    let x = 2f64.sqrt();
+    let scaled = [1.0, x, x * x].map(|v| (v * 1e6).round() as i64);
+    let mut data = vec![0i64; 12];
+    for i in 0..3 {
+        data[i * 3 + i] = 1;
+        data[9 + i] = scaled[i];
+    }
+    let b = Matrix::from_vec(4, 3, data.into_iter().map(BigRational::from).collect()).unwrap();
+    let (r, _) = b
+        .lll_reduce(BigRational::new(BigInt::from(99), BigInt::from(100)))
+        .unwrap();
+    let relation = (1..=3)
+        .map(|i| r.get(i, 1).unwrap().to_f64() as i64)
+        .collect::<Vec<_>>();
+    let sign = relation[2].signum();
+    assert_eq!(
+        relation.iter().map(|v| v * sign).collect::<Vec<_>>(),
+        vec![-2, 0, 1]
+    );
+
+    let dependent = Matrix::from_vec(2, 2, vec![1.0, 2.0, 2.0, 4.0]).unwrap();
+    assert!(matches!(
+        dependent.lll_reduce(0.75),
+        Err(RMatrixError::MatrixSingular)
+    ));
+    // the dependence test is relative to the length of the columns
+    let tiny = Matrix::<f64>::eyes(2, 2).unwrap().smul(1e-5).unwrap();
+    let (r, t) = tiny.lll_reduce(0.75).unwrap();
+    assert_eq!(r, tiny);
+    assert_eq!(t, Matrix::eyes(2, 2).unwrap());
+    assert!(matches!(
+        Matrix::<f64>::eyes(2, 2).unwrap().lll_reduce(0.25),
+        Err(RMatrixError::FunctionUndefined(_))
+    ));
+}
+
+#[test]
+fn gram_schmidt_modes() {
+    let modes = [
+        GramSchmidtMode::Classical,
+        GramSchmidtMode::Modified,
+        GramSchmidtMode::Reorthogonalized,
+    ];
+    for _ in 0..10 {
+        let a = Matrix::<f64>::rand(6, 4, -1.0, 1.0).unwrap();
+        for mode in modes {
+            let g = a.gram_schmidt(mode).unwrap();
+            assert_eq!(g.rank, 4);
+            assert!(g.q.times(&g.r).unwrap().subtract(&a).unwrap().norm_1() < 1e-12);
+            assert!(g.orthogonality_loss().unwrap() < 1e-10);
+            for i in 2..=4 {
+                assert!((1..i).all(|j| g.r.get(i, j).unwrap() == 0.0));
+            }
+        }
+    }
+
+    // the loss of orthogonality on the ill conditioned Hilbert matrix
+    let n = 7;
+    let hilbert = Matrix::from_vec(
+        n,
+        n,
+        (0..n * n)
+            .map(|k| 1.0 / ((k / n + k % n + 1) as f64))
+            .collect(),
+    )
+    .unwrap();
+    let loss = modes.map(|mode| {
+        let g = hilbert.gram_schmidt(mode).unwrap();
+        assert!(
+            g.q.times(&g.r)
+                .unwrap()
+                .subtract(&hilbert)
+                .unwrap()
+                .norm_1()
+                < 1e-12
+        );
+        g.orthogonality_loss().unwrap()
+    });
+    assert!(loss[0] > 1e-3);
+    assert!(loss[1] < loss[0] && loss[1] > loss[2]);
+    assert!(loss[2] < 1e-12);
+
+    // dependent and zero columns, more columns than rows
+    let a = Matrix::from_vec(
+        3,
+        5,
+        vec![
+            1.0, 2.0, 0.0, 3.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0,
+        ],
+    )
+    .unwrap();
+    for mode in modes {
+        let g = a.gram_schmidt(mode).unwrap();
+        assert_eq!(g.dependent, vec![3, 4]);
+        assert_eq!(g.rank, 3);
+        assert!(g.q.times(&g.r).unwrap().subtract(&a).unwrap().norm_1() < 1e-12);
+        assert!(g.q.get_col(3).unwrap().iter().all(|v| *v == 0.0));
+    }
+}
+
+#[test]
+fn jordan_and_rational_canonical_forms() {
+    let r = |v: i64| Rational::from(v);
+    // A = P J P^(-1) with blocks J_3(2), J_1(2), J_2(-1) and J_1(1/2)
+    let p = Matrix::from_vec(
+        7,
+        7,
+        [
+            1, 1, 0, 0, 2, 0, 1, 0, 1, 1, 0, 0, -1, 0, 0, 0, 1, 1, 0, 0, 3, 0, 0, 0, 1, 1, 0, 0, 1,
+            0, 0, 0, 1, 1, 0, 0, 2, 0, 0, 0, 1, 1, 0, 0, 0, 1, 0, 0, 1,
+        ]
+        .map(Rational::from)
+        .to_vec(),
+    )
+    .unwrap();
+    let mut j = Matrix::<Rational>::zeros(7, 7).unwrap();
+    let diagonal = [r(2), r(2), r(2), r(2), r(-1), r(-1), Rational::new(1, 2)];
+    for (k, v) in diagonal.into_iter().enumerate() {
+        j.set(v, k + 1, k + 1).unwrap();
+    }
+    for k in [1, 2, 5] {
+        j.set(r(1), k, k + 1).unwrap();
+    }
+    let a = p.times(&j).unwrap().times(&p.inverse().unwrap()).unwrap();
+    let jordan = a.jordan_form().unwrap();
+    let sizes = jordan
+        .blocks
+        .iter()
+        .map(|b| (b.eigenvalue, b.size))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sizes,
+        vec![(r(-1), 2), (Rational::new(1, 2), 1), (r(2), 3), (r(2), 1)]
+    );
+    assert!(!jordan.p.det().unwrap().is_zero());
+    assert_eq!(
+        a.times(&jordan.p).unwrap(),
+        jordan.p.times(&jordan.j).unwrap()
+    );
+
+    // x^2 - 2 has no rational roots, but the rational canonical form exists
+    let b = Matrix::from_vec(2, 2, [1, 1, 1, -1].map(Rational::from).to_vec()).unwrap();
+    assert!(matches!(
+        b.jordan_form(),
+        Err(RMatrixError::FunctionUndefined(_))
+    ));
+    let frobenius = b.frobenius_form().unwrap();
+    assert_eq!(
+        frobenius.f,
+        Matrix::companion(&[r(-2), r(0), r(1)]).unwrap()
+    );
+
+    // large eigenvalues are found within the root bound without overflowing
+    for values in [vec![1000, 1001, 1002], vec![100003, 100019]] {
+        let n = values.len();
+        let mut d = Matrix::<Rational>::zeros(n, n).unwrap();
+        for (k, &v) in values.iter().enumerate() {
+            d.set(r(v), k + 1, k + 1).unwrap();
+        }
+        let jordan = d.jordan_form().unwrap();
+        let eigenvalues = jordan
+            .blocks
+            .iter()
+            .map(|b| b.eigenvalue)
+            .collect::<Vec<_>>();
+        assert_eq!(eigenvalues, values.into_iter().map(r).collect::<Vec<_>>());
+        assert_eq!(jordan.j, d);
+    }
+    let quarter = Matrix::from_vec(2, 2, vec![Rational::new(1, 4), r(3), r(0), r(-5)]).unwrap();
+    let blocks = quarter.jordan_form().unwrap().blocks;
+    assert_eq!(blocks[0].eigenvalue, r(-5));
+    assert_eq!(blocks[1].eigenvalue, Rational::new(1, 4));
+
+    // a scalar matrix has n equal invariant factors
+    let frobenius = Matrix::<Rational>::eyes(3, 3)
+        .unwrap()
+        .smul(r(2))
+        .unwrap()
+        .frobenius_form()
+        .unwrap();
+    assert_eq!(frobenius.invariant_factors.len(), 3);
+    assert!(frobenius
+        .invariant_factors
+        .iter()
+        .all(|f| f.to_string() == "x - 2"));
+
+    let mut rng = thread_rng();
+    let mut matrices = vec![a];
+    for _ in 0..10 {
+        let block =
+            Matrix::from_vec(3, 3, (0..9).map(|_| r(rng.gen_range(-2..=2))).collect()).unwrap();
+        // repeated blocks give several invariant factors
+        let mut m = Matrix::<Rational>::zeros(6, 6).unwrap();
+        for i in 1..=3 {
+            for k in 1..=3 {
+                m.set(block.get(i, k).unwrap(), i, k).unwrap();
+                m.set(block.get(i, k).unwrap(), i + 3, k + 3).unwrap();
+            }
+        }
+        matrices.push(
+            Matrix::from_vec(4, 4, (0..16).map(|_| r(rng.gen_range(-3..=3))).collect()).unwrap(),
+        );
+        matrices.push(m);
+    }
+    for a in matrices {
+        let frobenius = a.frobenius_form().unwrap();
+        assert!(!frobenius.p.det().unwrap().is_zero());
+        assert_eq!(
+            a.times(&frobenius.p).unwrap(),
+            frobenius.p.times(&frobenius.f).unwrap()
+        );
+        let factors = &frobenius.invariant_factors;
+        for w in factors.windows(2) {
+            assert!(w[1].div_rem(&w[0]).unwrap().1.is_zero());
+        }
+        let product = factors
+            .iter()
+            .fold(Polynomial::constant(r(1)), |p, f| p * f.clone());
+        assert_eq!(product, a.char_poly().unwrap());
+        assert_eq!(
+            factors.last().unwrap(),
+            &a.min_poly().unwrap().monic().unwrap()
+        );
+    }
+}